@@ -52,10 +52,7 @@ fn bench_single_thread_ops(c: &mut Criterion) {
     // Read
     group.bench_function("Read/RetroCell", |b| {
         let (_writer, reader) = RetroCell::new(create_data());
-        b.iter(|| match reader.try_read() {
-            ReadResult::Success(r) => do_work(&r),
-            ReadResult::Blocked(blocked) => do_work(&blocked.wait()),
-        })
+        b.iter(|| do_work(&reader.try_read().resolve()))
     });
 
     group.bench_function("Read/ArcSwap", |b| {
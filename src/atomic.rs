@@ -0,0 +1,163 @@
+//! `AtomicCell`-shaped facade over a [`RetroCell`].
+//!
+//! `AtomicCell` 形式的 [`RetroCell`] 外观。
+
+use crate::reader::Reader;
+use crate::shared::Shared;
+use crate::writer::RetroCell;
+use std::fmt;
+
+/// A [`RetroCell`] and a [`Reader`] bundled behind a
+/// `load`/`store`/`swap`/`compare_exchange` API, for code migrating from
+/// `AtomicCell`- or `ArcSwap`-style cells.
+///
+/// Loads clone the value and never block on readers. Writes take `&mut self`:
+/// the cell still has a single writer. Hand out [`reader`](Self::reader)s to
+/// other threads and reach for [`cell`](Self::cell) to adopt retro features.
+///
+/// 将 [`RetroCell`] 与 [`Reader`] 组合在 `load`/`store`/`swap`/`compare_exchange`
+/// 接口之后，便于从 `AtomicCell` 或 `ArcSwap` 风格的单元迁移。
+///
+/// 加载会克隆值且不会因读者而阻塞。写入需要 `&mut self`：该单元仍然只有一个写入者。
+/// 可将 [`reader`](Self::reader) 分发给其他线程，并通过 [`cell`](Self::cell) 逐步采用回溯特性。
+pub struct AtomicCell<T> {
+    cell: RetroCell<T>,
+    pub(crate) reader: Reader<T>,
+}
+
+// `&self` methods only go through the reader; the writer is reached via `&mut self`
+// `&self` 方法只经由读取者；写入者只能通过 `&mut self` 访问
+unsafe impl<T: Send + Sync> Sync for AtomicCell<T> {}
+
+impl<T> AtomicCell<T> {
+    /// Create a new cell holding `value`
+    ///
+    /// 创建一个保存 `value` 的新单元
+    #[inline]
+    pub fn new(value: T) -> Self {
+        let (cell, reader) = RetroCell::new(value);
+        Self { cell, reader }
+    }
+
+    /// Wrap an existing cell and one of its readers
+    ///
+    /// 包装已有的单元及其一个读取者
+    #[inline]
+    pub fn from_parts(cell: RetroCell<T>, reader: Reader<T>) -> Self {
+        debug_assert!(
+            Shared::ptr_eq(&cell.shared, &reader.shared),
+            "retro-cell: reader belongs to another cell",
+        );
+        Self { cell, reader }
+    }
+
+    /// Split back into the underlying cell and reader
+    ///
+    /// 拆分回底层的单元与读取者
+    #[inline]
+    pub fn into_parts(self) -> (RetroCell<T>, Reader<T>) {
+        (self.cell, self.reader)
+    }
+
+    /// A new reader for the same cell
+    ///
+    /// 同一单元的新读取者
+    #[inline]
+    pub fn reader(&self) -> Reader<T> {
+        self.reader.clone()
+    }
+
+    /// The underlying cell
+    ///
+    /// 底层单元
+    #[inline]
+    pub fn cell(&mut self) -> &mut RetroCell<T> {
+        &mut self.cell
+    }
+
+    /// Clone the current value
+    ///
+    /// 克隆当前值
+    #[inline]
+    pub fn load(&self) -> T
+    where
+        T: Clone,
+    {
+        self.reader.read_cloned()
+    }
+
+    /// Publish `value`
+    ///
+    /// 发布 `value`
+    #[inline]
+    pub fn store(&mut self, value: T) {
+        self.cell.store(value);
+    }
+
+    /// Publish `value` and return the previous value.
+    ///
+    /// The previous value is moved out if no reader holds it, cloned otherwise.
+    ///
+    /// 发布 `value` 并返回先前的值。
+    ///
+    /// 若先前的值没有读者持有则直接移出，否则克隆。
+    #[inline]
+    pub fn swap(&mut self, value: T) -> T
+    where
+        T: Clone,
+    {
+        self.cell.swap(value)
+    }
+
+    /// Publish `new` if the current value equals `current`.
+    ///
+    /// Returns the previous value on success and the actual value on failure.
+    ///
+    /// 若当前值等于 `current`，则发布 `new`。
+    ///
+    /// 成功时返回先前的值，失败时返回实际的当前值。
+    pub fn compare_exchange(&mut self, current: T, new: T) -> Result<T, T>
+    where
+        T: Clone + PartialEq,
+    {
+        if *self.cell.current() != current {
+            return Err(self.cell.current().clone());
+        }
+        Ok(self.swap(new))
+    }
+}
+
+impl<T: Default> AtomicCell<T> {
+    /// Take the value, leaving `T::default()` in its place
+    ///
+    /// 取出值，并在原处留下 `T::default()`
+    #[inline]
+    pub fn take(&mut self) -> T
+    where
+        T: Clone,
+    {
+        self.swap(T::default())
+    }
+}
+
+impl<T: Default> Default for AtomicCell<T> {
+    #[inline]
+    fn default() -> Self {
+        Self::new(T::default())
+    }
+}
+
+impl<T> From<T> for AtomicCell<T> {
+    #[inline]
+    fn from(value: T) -> Self {
+        Self::new(value)
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for AtomicCell<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AtomicCell")
+            .field("value", &*self.reader.read())
+            .finish()
+    }
+}
@@ -0,0 +1,64 @@
+use crate::version::Version;
+use crate::writer::WritePath;
+use std::borrow::Cow;
+use std::collections::{VecDeque, vec_deque};
+use std::time::SystemTime;
+
+/// One entry of a cell's audit trail
+///
+/// 单元审计记录中的一项
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+pub struct AuditRecord {
+    /// Version produced by the publication
+    ///
+    /// 此次发布产生的版本
+    pub version: Version,
+    /// Wall-clock time of the publication
+    ///
+    /// 发布时的挂钟时间
+    pub timestamp: SystemTime,
+    /// How the value was written
+    ///
+    /// 值的写入方式
+    pub path: WritePath,
+    /// Tag set with [`RetroCell::tag`](crate::RetroCell::tag) before the write
+    ///
+    /// 写入前通过 [`RetroCell::tag`](crate::RetroCell::tag) 设置的标签
+    pub tag: Option<Cow<'static, str>>,
+}
+
+/// Bounded audit trail, oldest records are evicted first
+///
+/// 有界审计记录，最旧的记录最先被淘汰
+pub(crate) struct AuditLog {
+    records: VecDeque<AuditRecord>,
+    capacity: usize,
+}
+
+impl AuditLog {
+    #[inline]
+    pub(crate) fn new(capacity: usize) -> Self {
+        Self {
+            records: VecDeque::new(),
+            capacity,
+        }
+    }
+
+    #[inline]
+    pub(crate) fn is_enabled(&self) -> bool {
+        self.capacity > 0
+    }
+
+    pub(crate) fn record(&mut self, record: AuditRecord) {
+        if self.records.len() == self.capacity {
+            self.records.pop_front();
+        }
+        self.records.push_back(record);
+    }
+
+    #[inline]
+    pub(crate) fn iter(&self) -> vec_deque::Iter<'_, AuditRecord> {
+        self.records.iter()
+    }
+}
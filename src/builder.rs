@@ -0,0 +1,350 @@
+use crate::error::Error;
+use crate::history::HistorySink;
+use crate::reader::Reader;
+use crate::shared::Hooks;
+use crate::sync::Notify;
+use crate::writer::{RetroCell, SnapshotPolicy};
+use std::fmt;
+#[cfg(feature = "checksum")]
+use std::hash::{Hash, Hasher};
+use std::iter;
+use std::marker::PhantomData;
+use std::time::Duration;
+
+/// Per-cell configuration chosen at construction
+///
+/// 构造时选定的单元配置
+#[derive(Clone, Copy, Debug, Default)]
+pub(crate) struct Config {
+    // Never keep more than two nodes (current + retro) alive
+    // 任何时刻最多只保留两个节点（当前 + 回溯）
+    pub(crate) double_buffer: bool,
+    // Maximum number of audit records kept (0 disables the audit trail)
+    // 保留的审计记录上限（0 表示禁用审计）
+    pub(crate) audit_capacity: usize,
+    // Retired nodes tracked without reallocating (0 picks a default)
+    // 无需重新分配即可跟踪的已退役节点数（0 表示使用默认值）
+    pub(crate) garbage_capacity: usize,
+}
+
+impl Config {
+    /// Initial capacity of the garbage ring: the retro node plus a few pinned
+    /// ones, or just the retro node in double-buffer mode
+    ///
+    /// 垃圾环的初始容量：回溯节点加上少量被占用的节点，双缓冲模式下仅为回溯节点
+    pub(crate) fn garbage_capacity(&self) -> usize {
+        match self.garbage_capacity {
+            0 if self.double_buffer => 2,
+            0 => 8,
+            capacity => capacity,
+        }
+    }
+}
+
+/// How a cell reports failures of its fallible operations.
+///
+/// Operations handing back a blocked reader or congested writer on timeout
+/// still hand it back under [`Error`](FailurePolicy::Error) and
+/// [`Hook`](FailurePolicy::Hook). A checksum mismatch has no caller to return
+/// to: only [`Hook`](FailurePolicy::Hook) lets the read go on.
+///
+/// 单元报告可失败操作之失败的方式。
+///
+/// 超时后交还被阻塞读取者或拥塞写入者的操作，在 [`Error`](FailurePolicy::Error)
+/// 与 [`Hook`](FailurePolicy::Hook) 下仍会交还它。校验和不匹配没有可返回的调用者：
+/// 只有 [`Hook`](FailurePolicy::Hook) 允许读取继续。
+#[derive(Clone, Copy, Debug, Default)]
+pub enum FailurePolicy {
+    /// Panic with the error
+    ///
+    /// 携带错误 panic
+    Panic,
+    /// Return the error to the caller
+    ///
+    /// 将错误返回给调用者
+    #[default]
+    Error,
+    /// Invoke the hook, then return the error to the caller
+    ///
+    /// 调用钩子，然后将错误返回给调用者
+    Hook(fn(&Error)),
+}
+
+impl FailurePolicy {
+    /// Apply the policy to an error about to be returned
+    ///
+    /// 对即将返回的错误应用此策略
+    #[cold]
+    pub(crate) fn apply(self, err: Error) -> Error {
+        match self {
+            FailurePolicy::Panic => panic!("retro-cell: {err}"),
+            FailurePolicy::Error => err,
+            FailurePolicy::Hook(hook) => {
+                hook(&err);
+                err
+            }
+        }
+    }
+}
+
+/// Builder for configuring a [`RetroCell`]
+///
+/// 用于配置 [`RetroCell`] 的构建器
+pub struct RetroCellBuilder<T> {
+    config: Config,
+    snapshot: Option<SnapshotPolicy<T>>,
+    hooks: Hooks<T>,
+    reclaim: Option<fn(&mut T)>,
+    sink: Option<Box<dyn HistorySink<T>>>,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<T> RetroCellBuilder<T> {
+    /// Create a builder with the default configuration
+    ///
+    /// 使用默认配置创建构建器
+    #[inline]
+    pub fn new() -> Self {
+        Self {
+            config: Config::default(),
+            snapshot: None,
+            hooks: Hooks::default(),
+            reclaim: None,
+            sink: None,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Strict double-buffer mode: the cell never holds more than two nodes.
+    ///
+    /// A COW write reuses the retro node instead of allocating, blocking until
+    /// the readers of that node have drained. This gives a hard memory bound,
+    /// but a [leaked](crate::Ref::leak) retro guard never drains.
+    ///
+    /// 严格双缓冲模式：单元最多只持有两个节点。
+    ///
+    /// COW 写入会复用回溯节点而非分配新节点，并阻塞直到该节点的读者排空。
+    /// 这提供了严格的内存上限，但被[泄漏](crate::Ref::leak)的回溯守卫永远不会排空。
+    #[inline]
+    pub fn double_buffer(mut self, enabled: bool) -> Self {
+        self.config.double_buffer = enabled;
+        self
+    }
+
+    /// Choose how fallible operations report failures: construction,
+    /// bounded-memory budget exhaustion, timed waits of readers and writers,
+    /// checksum mismatches and serde staging
+    ///
+    /// 选择可失败操作报告失败的方式：构造、有界内存预算耗尽、读者与写入者的限时等待、
+    /// 校验和不匹配以及 serde 暂存
+    #[inline]
+    pub fn failure_policy(mut self, policy: FailurePolicy) -> Self {
+        self.hooks.failure = policy;
+        self
+    }
+
+    /// Keep an audit trail of the last `capacity` publications (version,
+    /// timestamp, write path and tag), queryable via [`RetroCell::audit`]
+    ///
+    /// 保留最近 `capacity` 次发布的审计记录（版本、时间戳、写入方式及标签），
+    /// 可通过 [`RetroCell::audit`] 查询
+    #[inline]
+    pub fn audit_log(mut self, capacity: usize) -> Self {
+        self.config.audit_capacity = capacity;
+        self
+    }
+
+    /// Size the queue of retired nodes for `capacity` entries up front
+    /// (rounded up to a power of two).
+    ///
+    /// Retired nodes wait there until their readers drain. Writes never
+    /// allocate for the queue unless readers pin more than `capacity` retired
+    /// versions at once, in which case it grows on a cold path.
+    ///
+    /// 预先为 `capacity` 个条目分配已退役节点队列（向上取整为 2 的幂）。
+    ///
+    /// 已退役节点在队列中等待其读者排空。除非读者同时占用超过 `capacity` 个已退役版本，
+    /// 否则写入不会为该队列分配内存；超出时队列在冷路径上扩容。
+    #[inline]
+    pub fn garbage_capacity(mut self, capacity: usize) -> Self {
+        self.config.garbage_capacity = capacity;
+        self
+    }
+
+    /// Periodic auto-versioning for cells updated mostly in place.
+    ///
+    /// In-place writes never produce retro versions on their own. With this
+    /// policy, an in-place write clones the value it is about to modify into a
+    /// retro snapshot, at most once per `interval` (COW writes reset the timer).
+    ///
+    /// 针对主要原地更新的单元的定时自动版本化。
+    ///
+    /// 原地写入本身不会产生回溯版本。启用此策略后，原地写入会在修改前
+    /// 将值克隆为回溯快照，每个 `interval` 最多一次（COW 写入会重置计时）。
+    #[inline]
+    pub fn snapshot_interval(mut self, interval: Duration) -> Self
+    where
+        T: Clone,
+    {
+        self.snapshot = Some(SnapshotPolicy {
+            interval,
+            clone: T::clone,
+            last: None,
+        });
+        self
+    }
+
+    /// Run `reclaim` on retired values when their node is parked in the pool
+    /// for reuse (or, in double-buffer mode, taken back from the retro slot),
+    /// e.g. to release the backing memory of huge payloads (see
+    /// `MmapSlice::release` with the `mmap` feature). The value is
+    /// overwritten before the node is published again.
+    ///
+    /// 当已退役值的节点被放入池中等待复用（或在双缓冲模式下从回溯槽位收回）时，对其运行 `reclaim`，
+    /// 例如释放超大数据的底层内存（参见 `mmap` 特性下的 `MmapSlice::release`）。节点再次发布前该值会被覆盖。
+    #[inline]
+    pub fn reclaim_with(mut self, reclaim: fn(&mut T)) -> Self {
+        self.reclaim = Some(reclaim);
+        self
+    }
+
+    /// Hand versions to `sink` as they age out of memory instead of dropping
+    /// them (see [`HistorySink`])
+    ///
+    /// 在版本从内存中老化淘汰时将其交给 `sink`，而不是直接丢弃（参见 [`HistorySink`]）
+    #[inline]
+    pub fn history_sink(mut self, sink: impl HistorySink<T> + 'static) -> Self {
+        self.sink = Some(Box::new(sink));
+        self
+    }
+
+    /// Wake blocked readers through `notify` instead of a futex
+    ///
+    /// 通过 `notify` 而非 futex 唤醒被阻塞的读者
+    #[inline]
+    pub fn notify(mut self, notify: impl Notify + 'static) -> Self {
+        self.hooks.notify = Some(Box::new(notify));
+        self
+    }
+
+    /// Integrity checking (`checksum` feature): record `checksum(value)` for
+    /// every published version and verify it whenever a read guard is
+    /// created, panicking on mismatch.
+    ///
+    /// This catches torn or corrupted values, e.g. caused by unsound code
+    /// elsewhere in the process. The hasher would run on every read, so it
+    /// only runs in debug builds (`debug_assertions`); release builds skip
+    /// both hashing and verification, which keeps them compatible with the
+    /// `no-panic` certification.
+    ///
+    /// 完整性检查（`checksum` 特性）：为每个已发布版本记录 `checksum(value)`，
+    /// 并在每次创建读取守卫时验证，不匹配时 panic。
+    ///
+    /// 这可以捕获撕裂或损坏的值，例如由进程中其他不健全代码导致的损坏。
+    /// 哈希函数本会在每次读取时运行，因此仅在调试构建（`debug_assertions`）中运行；
+    /// release 构建跳过哈希与验证，从而与 `no-panic` 验证兼容。
+    #[cfg(feature = "checksum")]
+    #[inline]
+    pub fn checksum(mut self, checksum: fn(&T) -> u64) -> Self {
+        self.hooks.checksum = Some(checksum);
+        self
+    }
+
+    /// Integrity checking (`checksum` feature) using the [`Hash`] impl of `T`
+    /// with hasher `H`. See [`checksum`](Self::checksum).
+    ///
+    /// 使用 `T` 的 [`Hash`] 实现及哈希器 `H` 进行完整性检查（`checksum` 特性）。
+    /// 参见 [`checksum`](Self::checksum)。
+    #[cfg(feature = "checksum")]
+    #[inline]
+    pub fn checksum_with<H>(self) -> Self
+    where
+        T: Hash,
+        H: Hasher + Default,
+    {
+        self.checksum(hash_value::<T, H>)
+    }
+
+    /// Build the cell with the given initial value
+    ///
+    /// 使用给定初始值构建单元
+    #[inline]
+    pub fn build(self, initial: T) -> (RetroCell<T>, Reader<T>) {
+        let (mut cell, reader) = RetroCell::with_config(initial, self.config, self.hooks);
+        cell.snapshot = self.snapshot;
+        cell.reclaim = self.reclaim;
+        cell.sink = self.sink;
+        (cell, reader)
+    }
+
+    /// Build the cell restored with the versions that preceded `current`,
+    /// oldest first.
+    ///
+    /// The history is numbered as published first, so `current` gets the
+    /// version after the last of it. The newest prior version stays readable
+    /// through [`Reader::read_retro`]; older ones are handed to the
+    /// [`history_sink`](Self::history_sink), or dropped without one.
+    ///
+    /// 使用 `current` 之前的各版本（从旧到新）恢复并构建单元。
+    ///
+    /// 历史按最先发布的版本编号，因此 `current` 的版本紧随其最后一个版本。
+    /// 最新的先前版本仍可通过 [`Reader::read_retro`] 读取；更早的版本交给
+    /// [`history_sink`](Self::history_sink)，若未配置则被丢弃。
+    pub fn build_with_history<I>(self, current: T, history: I) -> (RetroCell<T>, Reader<T>)
+    where
+        I: IntoIterator<Item = T>,
+    {
+        let mut history = history.into_iter();
+        let Some(oldest) = history.next() else {
+            return self.build(current);
+        };
+        let (mut cell, reader) = self.build(oldest);
+        for value in history.chain(iter::once(current)) {
+            cell.install(value);
+            // Archive (or drop) versions older than the retro one right away
+            // 立即归档（或丢弃）比回溯版本更旧的版本
+            cell.collect_garbage();
+        }
+        (cell, reader)
+    }
+
+    /// Build the cell, reporting failures instead of panicking
+    ///
+    /// 构建单元，失败时返回错误而非 panic
+    #[inline]
+    pub fn try_build(self, initial: T) -> Result<(RetroCell<T>, Reader<T>), Error> {
+        let failure = self.hooks.failure;
+        let (mut cell, reader) = RetroCell::try_with_config(initial, self.config, self.hooks)
+            .map_err(|err| failure.apply(err))?;
+        cell.snapshot = self.snapshot;
+        cell.reclaim = self.reclaim;
+        cell.sink = self.sink;
+        Ok((cell, reader))
+    }
+}
+
+#[cfg(feature = "checksum")]
+fn hash_value<T: Hash, H: Hasher + Default>(value: &T) -> u64 {
+    let mut hasher = H::default();
+    value.hash(&mut hasher);
+    hasher.finish()
+}
+
+impl<T> Default for RetroCellBuilder<T> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> fmt::Debug for RetroCellBuilder<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RetroCellBuilder")
+            .field("config", &self.config)
+            .field(
+                "snapshot_interval",
+                &self.snapshot.as_ref().map(|policy| policy.interval),
+            )
+            .finish()
+    }
+}
@@ -0,0 +1,150 @@
+//! Monotonic clock used for version timestamps and latency measurements.
+//!
+//! With the `quanta` feature, `Instant::now()` is a calibrated TSC read instead
+//! of a system call, which is noticeable at high write rates.
+//!
+//! 用于版本时间戳和延迟测量的单调时钟。
+//!
+//! 启用 `quanta` 特性后，`Instant::now()` 使用经过校准的 TSC 读取而非系统调用，
+//! 这在高写入频率下效果明显。
+
+use std::fmt;
+use std::ops::{Add, Sub};
+use std::time::Duration;
+
+#[cfg(feature = "quanta")]
+type Inner = quanta::Instant;
+#[cfg(not(feature = "quanta"))]
+type Inner = std::time::Instant;
+
+/// A reading of the crate's monotonic clock, e.g. a version's
+/// [`publish_time`](crate::ReadMeta::publish_time).
+///
+/// The type is the same with or without the `quanta` feature, which only
+/// changes how [`now`](Self::now) reads the time.
+///
+/// 本 crate 单调时钟的一次读数，例如某版本的 [`publish_time`](crate::ReadMeta::publish_time)。
+///
+/// 无论是否启用 `quanta` 特性，该类型都相同；该特性只改变 [`now`](Self::now) 读取时间的方式。
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Instant(Inner);
+
+impl Instant {
+    /// The current time
+    ///
+    /// 当前时间
+    #[inline]
+    pub fn now() -> Self {
+        Self(Inner::now())
+    }
+
+    /// Time elapsed from `earlier` to `self`, or zero if `earlier` is later
+    ///
+    /// 从 `earlier` 到 `self` 经过的时间；若 `earlier` 更晚则为零
+    #[inline]
+    pub fn duration_since(&self, earlier: Instant) -> Duration {
+        self.0.saturating_duration_since(earlier.0)
+    }
+
+    /// Time elapsed since `self`
+    ///
+    /// 自 `self` 以来经过的时间
+    #[inline]
+    pub fn elapsed(&self) -> Duration {
+        Self::now().duration_since(*self)
+    }
+
+    /// `self + duration`, or `None` if it overflows
+    ///
+    /// `self + duration`；若溢出则为 `None`
+    #[inline]
+    pub fn checked_add(&self, duration: Duration) -> Option<Instant> {
+        self.0.checked_add(duration).map(Self)
+    }
+}
+
+impl Add<Duration> for Instant {
+    type Output = Instant;
+    #[inline]
+    fn add(self, duration: Duration) -> Instant {
+        Self(self.0 + duration)
+    }
+}
+
+impl Sub for Instant {
+    type Output = Duration;
+    #[inline]
+    fn sub(self, earlier: Instant) -> Duration {
+        self.duration_since(earlier)
+    }
+}
+
+impl fmt::Debug for Instant {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+/// Virtual clock for testing time-based behavior (publication times,
+/// [`snapshot_interval`](crate::RetroCellBuilder::snapshot_interval), the
+/// timeouts and deadlines of blocking waits) without real sleeps
+/// (`test-util` feature).
+///
+/// Time only moves when [`advance`](Self::advance) is called, and only for
+/// code run inside [`run`](Self::run) on the calling thread. Blocking waits
+/// can be made deterministic with a custom [`Notify`](crate::Notify).
+///
+/// 用于测试基于时间的行为（发布时间、[`snapshot_interval`](crate::RetroCellBuilder::snapshot_interval)、
+/// 阻塞等待的超时与截止时间）而无需真实睡眠的虚拟时钟（`test-util` 特性）。
+///
+/// 时间只在调用 [`advance`](Self::advance) 时前进，并且只对在调用线程上于
+/// [`run`](Self::run) 内运行的代码生效。阻塞等待可借助自定义 [`Notify`](crate::Notify) 变得确定。
+#[cfg(feature = "test-util")]
+pub struct MockClock {
+    clock: quanta::Clock,
+    mock: std::sync::Arc<quanta::Mock>,
+}
+
+#[cfg(feature = "test-util")]
+impl MockClock {
+    /// Create a clock standing still at an arbitrary origin
+    ///
+    /// 创建一个停在任意起点的时钟
+    pub fn new() -> Self {
+        let (clock, mock) = quanta::Clock::mock();
+        Self { clock, mock }
+    }
+
+    /// Move the clock forward by `by`
+    ///
+    /// 将时钟向前推进 `by`
+    #[inline]
+    pub fn advance(&self, by: Duration) {
+        self.mock.increment(by);
+    }
+
+    /// Run `f` with [`Instant::now`] reading this clock on the calling thread
+    ///
+    /// 运行 `f`，期间调用线程上的 [`Instant::now`] 读取此时钟
+    #[inline]
+    pub fn run<R>(&self, f: impl FnOnce() -> R) -> R {
+        quanta::with_clock(&self.clock, f)
+    }
+}
+
+#[cfg(feature = "test-util")]
+impl Default for MockClock {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(feature = "test-util")]
+impl fmt::Debug for MockClock {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MockClock")
+            .field("now", &self.clock.now())
+            .finish()
+    }
+}
@@ -0,0 +1,166 @@
+//! Thread-local retain coalescing (`coalesce` feature).
+//!
+//! Each thread tracks the nodes it already holds an atomic retain on, together
+//! with the number of live guards sharing that retain. A nested read of the
+//! same node only bumps the thread-local counter; the atomic release happens
+//! when the last of those guards is dropped.
+//!
+//! 线程本地持有合并（`coalesce` 特性）。
+//!
+//! 每个线程记录其已持有原子计数的节点，以及共享该计数的存活守卫数量。
+//! 对同一节点的嵌套读取只需增加线程本地计数；当最后一个守卫析构时才执行原子释放。
+
+use crate::shared::Node;
+use crate::utils::hot_path;
+use crate::version::Version;
+use std::cell::Cell;
+use std::fmt;
+use std::marker::PhantomData;
+use std::ops::Deref;
+
+// Fixed capacity keeps every operation allocation- and panic-free; guards that
+// don't fit simply hold their own retain
+// 固定容量使所有操作都无需分配且不会 panic；放不下的守卫自行持有计数
+const SLOTS: usize = 8;
+
+// (node address, live guards); address 0 marks a free slot
+// (节点地址, 存活守卫数)；地址 0 表示空闲槽位
+#[cfg(not(feature = "loom"))]
+std::thread_local! {
+    static HELD: [Cell<(usize, usize)>; SLOTS] = const { [const { Cell::new((0, 0)) }; SLOTS] };
+}
+
+#[cfg(feature = "loom")]
+loom::thread_local! {
+    static HELD: [Cell<(usize, usize)>; SLOTS] = [const { Cell::new((0, 0)) }; SLOTS];
+}
+
+/// Share an existing retain on `addr` if this thread holds one
+///
+/// 若当前线程已持有 `addr` 的计数，则共享该计数
+#[inline]
+pub(crate) fn reuse(addr: usize) -> bool {
+    HELD.try_with(|held| {
+        for slot in held {
+            let (held_addr, guards) = slot.get();
+            if held_addr == addr {
+                slot.set((addr, guards + 1));
+                return true;
+            }
+        }
+        false
+    })
+    .unwrap_or(false)
+}
+
+/// Record a fresh retain on `addr`. Returns `true` if the thread already held
+/// one, in which case the caller must drop its extra retain.
+///
+/// 记录对 `addr` 的新计数。若线程已持有计数则返回 `true`，此时调用者须释放多余的计数。
+#[inline]
+pub(crate) fn register(addr: usize) -> bool {
+    HELD.try_with(|held| {
+        let mut free = None;
+        for slot in held {
+            let (held_addr, guards) = slot.get();
+            if held_addr == addr {
+                slot.set((addr, guards + 1));
+                return true;
+            }
+            if held_addr == 0 && free.is_none() {
+                free = Some(slot);
+            }
+        }
+        if let Some(slot) = free {
+            slot.set((addr, 1));
+        }
+        false
+    })
+    .unwrap_or(false)
+}
+
+/// Drop one guard on `addr`. Returns `true` if other guards still share the
+/// retain, in which case the caller must not release it.
+///
+/// 释放 `addr` 上的一个守卫。若仍有其他守卫共享该计数则返回 `true`，此时调用者不得释放计数。
+#[inline]
+pub(crate) fn release(addr: usize) -> bool {
+    HELD.try_with(|held| {
+        for slot in held {
+            let (held_addr, guards) = slot.get();
+            if held_addr == addr {
+                if guards > 1 {
+                    slot.set((addr, guards - 1));
+                    return true;
+                }
+                slot.set((0, 0));
+                return false;
+            }
+        }
+        false
+    })
+    .unwrap_or(false)
+}
+
+/// Read guard returned by [`Reader::read_nested`](crate::Reader::read_nested).
+///
+/// Nested guards of one thread on the same version share a single retain, so
+/// unlike [`Ref`](crate::Ref) this guard is neither `Send` nor `Sync`.
+///
+/// 由 [`Reader::read_nested`](crate::Reader::read_nested) 返回的读取守卫。
+///
+/// 同一线程对同一版本的嵌套守卫共享一次计数，因此与 [`Ref`](crate::Ref) 不同，此守卫既不是 `Send` 也不是 `Sync`。
+pub struct NestedRef<'a, T> {
+    node: &'a Node<T>,
+    _not_send: PhantomData<*const ()>,
+}
+
+impl<'a, T> NestedRef<'a, T> {
+    /// Wrap a node whose retain is registered for this thread
+    ///
+    /// 包装一个其计数已为当前线程登记的节点
+    #[inline(always)]
+    pub(crate) fn new(node: &'a Node<T>) -> Self {
+        Self {
+            node,
+            _not_send: PhantomData,
+        }
+    }
+
+    /// Version of the value behind this guard
+    ///
+    /// 此守卫背后值的版本
+    #[inline]
+    pub fn version(this: &Self) -> Version {
+        this.node.version()
+    }
+}
+
+impl<'a, T> Deref for NestedRef<'a, T> {
+    type Target = T;
+    #[inline(always)]
+    fn deref(&self) -> &T {
+        unsafe { &*self.node.data.get() }
+    }
+}
+
+impl<'a, T> Drop for NestedRef<'a, T> {
+    #[inline(always)]
+    fn drop(&mut self) {
+        hot_path! {
+            if release(self.node as *const Node<T> as usize) {
+                return;
+            }
+            self.node.reader_count.release()
+        }
+    }
+}
+
+impl<'a, T: fmt::Debug> fmt::Debug for NestedRef<'a, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("NestedRef")
+            .field("value", &**self)
+            .field("version", &NestedRef::version(self))
+            .finish()
+    }
+}
@@ -0,0 +1,202 @@
+//! Type-erased read access.
+//!
+//! 类型擦除的读取访问。
+
+use crate::atomic::AtomicCell;
+use crate::mapped::MappedReader;
+use crate::reader::{MappedRef, Reader, Ref};
+use std::fmt;
+use std::ops::Deref;
+use std::sync::Arc;
+
+/// Object-safe "something readable yielding `&T`".
+///
+/// Implemented by [`Reader`], [`MappedReader`] and [`AtomicCell`]; implement
+/// it for your own wrappers to inject them wherever a [`DynReader`] is
+/// expected.
+///
+/// 对象安全的“可读取并产出 `&T` 的东西”。
+///
+/// 已由 [`Reader`]、[`MappedReader`] 与 [`AtomicCell`] 实现；为自己的包装类型实现它，
+/// 即可在任何需要 [`DynReader`] 的地方注入。
+pub trait ReadAccess<T: ?Sized>: Send + Sync {
+    /// Read the latest value (block until available)
+    ///
+    /// 读取最新值（阻塞直到可用）
+    fn read(&self) -> ReadGuard<'_, T>;
+
+    /// Read the retro value, if any
+    ///
+    /// 读取回溯值（如果有）
+    fn read_retro(&self) -> Option<ReadGuard<'_, T>>;
+}
+
+/// Guard returned by [`ReadAccess`], dereferencing to the value read.
+///
+/// Guards of this crate convert into it without allocating; any other guard
+/// is boxed by [`ReadGuard::new`].
+///
+/// 由 [`ReadAccess`] 返回的守卫，解引用为所读取的值。
+///
+/// 本 crate 的守卫可无需分配地转换为它；其他守卫由 [`ReadGuard::new`] 装箱。
+pub struct ReadGuard<'a, T: ?Sized> {
+    inner: Guard<'a, T>,
+}
+
+// Crate guards are all mapped guards (a `Ref` maps to its whole value)
+// 本 crate 的守卫都是映射守卫（`Ref` 映射到其整个值）
+enum Guard<'a, T: ?Sized> {
+    Mapped(MappedRef<'a, T>),
+    Boxed(Box<dyn Deref<Target = T> + 'a>),
+}
+
+impl<'a, T: ?Sized> ReadGuard<'a, T> {
+    /// Wrap any guard, e.g. one of a custom [`ReadAccess`] source
+    ///
+    /// 包装任意守卫，例如自定义 [`ReadAccess`] 来源的守卫
+    #[inline]
+    pub fn new(guard: impl Deref<Target = T> + 'a) -> Self {
+        Self {
+            inner: Guard::Boxed(Box::new(guard)),
+        }
+    }
+}
+
+impl<'a, T: ?Sized> Deref for ReadGuard<'a, T> {
+    type Target = T;
+    #[inline]
+    fn deref(&self) -> &T {
+        match &self.inner {
+            Guard::Mapped(guard) => guard,
+            Guard::Boxed(guard) => guard,
+        }
+    }
+}
+
+impl<'a, T> From<Ref<'a, T>> for ReadGuard<'a, T> {
+    #[inline]
+    fn from(guard: Ref<'a, T>) -> Self {
+        Ref::map(guard, |value| value).into()
+    }
+}
+
+impl<'a, T: ?Sized> From<MappedRef<'a, T>> for ReadGuard<'a, T> {
+    #[inline]
+    fn from(guard: MappedRef<'a, T>) -> Self {
+        Self {
+            inner: Guard::Mapped(guard),
+        }
+    }
+}
+
+impl<'a, T: ?Sized + fmt::Debug> fmt::Debug for ReadGuard<'a, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&**self, f)
+    }
+}
+
+impl<T: Send + Sync> ReadAccess<T> for Reader<T> {
+    #[inline]
+    fn read(&self) -> ReadGuard<'_, T> {
+        Reader::read(self).into()
+    }
+
+    #[inline]
+    fn read_retro(&self) -> Option<ReadGuard<'_, T>> {
+        Reader::read_retro(self).map(Into::into)
+    }
+}
+
+impl<T: Send + Sync, U: ?Sized> ReadAccess<U> for MappedReader<T, U> {
+    #[inline]
+    fn read(&self) -> ReadGuard<'_, U> {
+        MappedReader::read(self).into()
+    }
+
+    #[inline]
+    fn read_retro(&self) -> Option<ReadGuard<'_, U>> {
+        MappedReader::read_retro(self).map(Into::into)
+    }
+}
+
+impl<T: Send + Sync> ReadAccess<T> for AtomicCell<T> {
+    #[inline]
+    fn read(&self) -> ReadGuard<'_, T> {
+        self.reader.read().into()
+    }
+
+    #[inline]
+    fn read_retro(&self) -> Option<ReadGuard<'_, T>> {
+        self.reader.read_retro().map(Into::into)
+    }
+}
+
+/// Cloneable, type-erased reader, for components that accept any source of
+/// `T` without becoming generic over it
+///
+/// 可克隆、类型擦除的读取者，供接受任意 `T` 来源而不必对其泛型化的组件使用
+pub struct DynReader<T: ?Sized> {
+    inner: Arc<dyn ReadAccess<T>>,
+}
+
+impl<T: ?Sized> DynReader<T> {
+    /// Erase the type of `source`
+    ///
+    /// 擦除 `source` 的类型
+    #[inline]
+    pub fn new(source: impl ReadAccess<T> + 'static) -> Self {
+        Self {
+            inner: Arc::new(source),
+        }
+    }
+
+    /// Read the latest value (block until available)
+    ///
+    /// 读取最新值（阻塞直到可用）
+    #[inline]
+    pub fn read(&self) -> ReadGuard<'_, T> {
+        self.inner.read()
+    }
+
+    /// Read the retro value, if any
+    ///
+    /// 读取回溯值（如果有）
+    #[inline]
+    pub fn read_retro(&self) -> Option<ReadGuard<'_, T>> {
+        self.inner.read_retro()
+    }
+}
+
+impl<T: ?Sized> ReadAccess<T> for DynReader<T> {
+    #[inline]
+    fn read(&self) -> ReadGuard<'_, T> {
+        self.inner.read()
+    }
+
+    #[inline]
+    fn read_retro(&self) -> Option<ReadGuard<'_, T>> {
+        self.inner.read_retro()
+    }
+}
+
+impl<T: ?Sized> Clone for DynReader<T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<T: Send + Sync + 'static> From<Reader<T>> for DynReader<T> {
+    #[inline]
+    fn from(reader: Reader<T>) -> Self {
+        Self::new(reader)
+    }
+}
+
+impl<T: ?Sized> fmt::Debug for DynReader<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DynReader").finish_non_exhaustive()
+    }
+}
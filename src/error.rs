@@ -0,0 +1,77 @@
+use std::fmt;
+
+/// Errors reported by fallible RetroCell operations
+///
+/// RetroCell 可失败操作报告的错误
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Error {
+    /// Allocating a node of `size` bytes failed
+    ///
+    /// 分配 `size` 字节的节点失败
+    AllocFailed { size: usize },
+    /// Double-buffer mode cannot publish without waiting: the retro node
+    /// still has active readers
+    ///
+    /// 双缓冲模式无法在不等待的情况下发布：回溯节点仍有活跃读者
+    BudgetExhausted,
+    /// A writer panicked while holding the cell (or in the middle of an
+    /// in-place write); the value may be inconsistent
+    ///
+    /// 写入者在持有单元时（或在原地写入过程中）panic；值可能不一致
+    Poisoned,
+    /// The writer was dropped: no new version will be published
+    ///
+    /// 写入者已被析构：不会再发布新版本
+    Closed,
+    /// A deadline passed while waiting for the in-place lock to be released
+    /// or for readers to drain
+    ///
+    /// 等待原地锁释放或读者排空时超过了截止时间
+    TimedOut,
+    /// A read guard found a value whose checksum differs from the one
+    /// recorded at publication (`checksum` feature, debug builds)
+    ///
+    /// 读取守卫发现值的校验和与发布时记录的不同（`checksum` 特性，调试构建）
+    #[cfg(feature = "checksum")]
+    Corrupted { expected: u64, actual: u64 },
+    /// The serialize/deserialize round-trip staging a copy failed
+    /// (`serde` feature)
+    ///
+    /// 用于暂存副本的序列化/反序列化往返失败（`serde` 特性）
+    #[cfg(feature = "serde")]
+    Serde(String),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::AllocFailed { size } => {
+                write!(f, "failed to allocate a {size}-byte node")
+            }
+            Error::BudgetExhausted => {
+                write!(f, "node budget exhausted: the retro node is still being read")
+            }
+            Error::Poisoned => {
+                write!(f, "a writer panicked while holding the cell")
+            }
+            Error::Closed => {
+                write!(f, "the writer is gone: no new version will be published")
+            }
+            Error::TimedOut => {
+                write!(f, "timed out waiting for the lock or for readers to drain")
+            }
+            #[cfg(feature = "checksum")]
+            Error::Corrupted { expected, actual } => write!(
+                f,
+                "published value corrupted (checksum {actual:#018x}, expected {expected:#018x})"
+            ),
+            #[cfg(feature = "serde")]
+            Error::Serde(msg) => {
+                write!(f, "failed to stage a copy through serde: {msg}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for Error {}
@@ -0,0 +1,146 @@
+//! Publishing values received from a channel or a stream.
+//!
+//! 发布从通道或流中接收到的值。
+
+use crate::writer::RetroCell;
+use std::fmt;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{Receiver, RecvTimeoutError};
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+// How often a feeder thread blocked on an idle channel checks for a stop request
+// 阻塞于空闲通道的馈送线程检查停止请求的频率
+const STOP_POLL: Duration = Duration::from_millis(10);
+
+impl<T: Send + Sync + 'static> RetroCell<T> {
+    /// Move the writer to a new thread that publishes every value received
+    /// from `receiver`.
+    ///
+    /// With `coalesce`, values already queued behind the one received are
+    /// drained first and only the latest is published. The thread exits when
+    /// every sender is dropped or [`FeedHandle::stop`] is called; both
+    /// [`FeedHandle::join`] and `stop` hand the writer back.
+    ///
+    /// 将写入者移到新线程，由其发布从 `receiver` 接收到的每个值。
+    ///
+    /// 启用 `coalesce` 时，会先取出排在所接收值之后的已排队值，只发布最新的值。
+    /// 当所有发送端都被丢弃或调用 [`FeedHandle::stop`] 时线程退出；
+    /// [`FeedHandle::join`] 与 `stop` 都会交还写入者。
+    pub fn feed_from(mut self, receiver: Receiver<T>, coalesce: bool) -> FeedHandle<T> {
+        let stop = Arc::new(AtomicBool::new(false));
+        let thread = {
+            let stop = stop.clone();
+            thread::spawn(move || {
+                while !stop.load(Ordering::Relaxed) {
+                    let mut value = match receiver.recv_timeout(STOP_POLL) {
+                        Ok(value) => value,
+                        Err(RecvTimeoutError::Timeout) => continue,
+                        Err(RecvTimeoutError::Disconnected) => break,
+                    };
+                    if coalesce {
+                        value = receiver.try_iter().last().unwrap_or(value);
+                    }
+                    self.store(value);
+                }
+                self
+            })
+        };
+        FeedHandle { stop, thread }
+    }
+}
+
+/// Handle to a feeder thread started by [`RetroCell::feed_from`]
+///
+/// 由 [`RetroCell::feed_from`] 启动的馈送线程的句柄
+pub struct FeedHandle<T> {
+    stop: Arc<AtomicBool>,
+    thread: JoinHandle<RetroCell<T>>,
+}
+
+impl<T> FeedHandle<T> {
+    /// Stop after the value being published (if any) and get the writer back.
+    /// Values still queued in the channel are not published.
+    ///
+    /// 在正在发布的值（如果有）之后停止，并取回写入者。通道中仍在排队的值不会被发布。
+    pub fn stop(self) -> RetroCell<T> {
+        self.stop.store(true, Ordering::Relaxed);
+        self.join()
+    }
+
+    /// Wait until every sender is dropped and the channel is drained, then
+    /// get the writer back
+    ///
+    /// 等待所有发送端被丢弃且通道排空，然后取回写入者
+    pub fn join(self) -> RetroCell<T> {
+        match self.thread.join() {
+            Ok(cell) => cell,
+            Err(payload) => std::panic::resume_unwind(payload),
+        }
+    }
+
+    /// Whether the feeder thread has exited
+    ///
+    /// 馈送线程是否已退出
+    #[inline]
+    pub fn is_finished(&self) -> bool {
+        self.thread.is_finished()
+    }
+}
+
+impl<T> fmt::Debug for FeedHandle<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FeedHandle")
+            .field("finished", &self.is_finished())
+            .finish()
+    }
+}
+
+#[cfg(feature = "stream")]
+impl<T> RetroCell<T> {
+    /// Publish every value yielded by `stream`, completing when it ends.
+    /// Drop the future to stop feeding.
+    ///
+    /// With `coalesce`, values the stream has ready at once are collapsed
+    /// into the latest one.
+    ///
+    /// 发布 `stream` 产生的每个值，流结束时完成。丢弃该 future 即可停止馈送。
+    ///
+    /// 启用 `coalesce` 时，流同时就绪的多个值会合并为最新的一个。
+    pub async fn feed_from_stream<S>(&mut self, stream: S, coalesce: bool)
+    where
+        S: futures_core::Stream<Item = T>,
+    {
+        use std::task::Poll;
+
+        let mut stream = std::pin::pin!(stream);
+        let mut done = false;
+        loop {
+            let next = std::future::poll_fn(|cx| {
+                let mut latest = None;
+                while !done {
+                    match stream.as_mut().poll_next(cx) {
+                        Poll::Ready(Some(value)) => {
+                            latest = Some(value);
+                            if !coalesce {
+                                break;
+                            }
+                        }
+                        Poll::Ready(None) => done = true,
+                        Poll::Pending if latest.is_none() => return Poll::Pending,
+                        Poll::Pending => break,
+                    }
+                }
+                Poll::Ready(latest)
+            })
+            .await;
+            match next {
+                Some(value) => {
+                    self.store(value);
+                }
+                None => return,
+            }
+        }
+    }
+}
@@ -0,0 +1,148 @@
+//! Cells sealed for the rest of their lifetime.
+//!
+//! 在剩余生命周期内被封存的单元。
+
+use crate::error::Error;
+use crate::reader::{Reader, Ref};
+use crate::rt::sync::atomic::Ordering;
+use crate::shared::{PTR_MASK, Node, Shared};
+use crate::version::Version;
+use crate::writer::RetroCell;
+use std::fmt;
+use std::ops::Deref;
+use std::ptr::NonNull;
+
+impl<T> RetroCell<T> {
+    /// Seal the cell: the current value can never change again.
+    ///
+    /// The writer is consumed and readers report
+    /// [`is_frozen`](Reader::is_frozen). The returned handle reads the value
+    /// as a plain reference, without any reader count or validation.
+    ///
+    /// 封存单元：当前值再也不会改变。
+    ///
+    /// 写入者被消耗，读者会报告 [`is_frozen`](Reader::is_frozen)。
+    /// 返回的句柄以普通引用读取值，无需任何读者计数或验证。
+    pub fn freeze(self) -> FrozenCell<T> {
+        let shared = self.shared.clone();
+        // Marked before the writer is dropped, so readers never see it closed
+        // 在析构写入者之前标记，使读者永远不会看到单元被关闭
+        shared.frozen.store(true, Ordering::Release);
+        drop(self);
+        let curr_ptr = (shared.current.load(Ordering::Acquire) & PTR_MASK) as *mut Node<T>;
+        FrozenCell {
+            node: NonNull::new(curr_ptr).expect("retro-cell: cell has no current version"),
+            shared,
+        }
+    }
+}
+
+impl<T> Reader<T> {
+    /// Whether the writer sealed the cell with [`RetroCell::freeze`] or was
+    /// dropped; if so, every read returns the same value from now on
+    ///
+    /// 写入者是否已通过 [`RetroCell::freeze`] 封存单元或已被析构；若是，此后每次读取都返回相同的值
+    #[inline]
+    pub fn is_frozen(&self) -> bool {
+        self.shared.frozen.load(Ordering::Acquire)
+    }
+
+    /// Whether the writer was dropped without [`RetroCell::freeze`]; reads
+    /// keep returning the last value. A frozen cell is not closed.
+    ///
+    /// 写入者是否未经 [`RetroCell::freeze`] 即被析构；读取仍返回最后的值。已封存的单元不算关闭。
+    #[inline]
+    pub fn is_closed(&self) -> bool {
+        self.shared.closed.load(Ordering::Acquire)
+    }
+
+    /// Read the current value until the writer is dropped, then fail with
+    /// [`Error::Closed`] (see [`is_closed`](Self::is_closed)). A frozen cell
+    /// keeps being read.
+    ///
+    /// The last value stays available through [`read`](Self::read).
+    ///
+    /// 在写入者被析构前读取当前值，之后以 [`Error::Closed`] 失败（参见 [`is_closed`](Self::is_closed)）。
+    /// 已封存的单元仍可读取。
+    ///
+    /// 最后的值仍可通过 [`read`](Self::read) 获取。
+    #[inline]
+    pub fn read_or_closed(&self) -> Result<Ref<'_, T>, Error> {
+        if self.is_closed() {
+            return Err(Error::Closed);
+        }
+        Ok(self.read())
+    }
+}
+
+/// Immutable cell produced by [`RetroCell::freeze`].
+///
+/// Dereferences to the final value. Clones share it, and regular readers of
+/// the cell keep working.
+///
+/// 由 [`RetroCell::freeze`] 产生的不可变单元。
+///
+/// 解引用为最终值。克隆共享该值，单元的普通读者仍可继续使用。
+pub struct FrozenCell<T> {
+    // Kept alive by `shared`; no writer is left to retire it
+    // 由 `shared` 保持存活；已没有写入者可以退役它
+    node: NonNull<Node<T>>,
+    shared: Shared<T>,
+}
+
+unsafe impl<T: Send + Sync> Send for FrozenCell<T> {}
+unsafe impl<T: Send + Sync> Sync for FrozenCell<T> {}
+
+impl<T> FrozenCell<T> {
+    /// The final value
+    ///
+    /// 最终值
+    #[inline(always)]
+    pub fn get(&self) -> &T {
+        unsafe { &*self.node.as_ref().data.get() }
+    }
+
+    /// Version of the final value
+    ///
+    /// 最终值的版本
+    #[inline]
+    pub fn version(&self) -> Version {
+        unsafe { self.node.as_ref() }.version()
+    }
+
+    /// Create a regular reader of the cell
+    ///
+    /// 创建该单元的普通读取者
+    #[inline]
+    pub fn reader(&self) -> Reader<T> {
+        Reader::new(self.shared.clone())
+    }
+}
+
+impl<T> Deref for FrozenCell<T> {
+    type Target = T;
+    #[inline(always)]
+    fn deref(&self) -> &T {
+        self.get()
+    }
+}
+
+impl<T> Clone for FrozenCell<T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            node: self.node,
+            shared: self.shared.clone(),
+        }
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for FrozenCell<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FrozenCell")
+            .field("value", self.get())
+            .field("version", &self.version())
+            .field("cell", &self.shared.id)
+            .finish()
+    }
+}
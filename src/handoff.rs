@@ -0,0 +1,140 @@
+//! Lock-free handoff of the writer between threads.
+//!
+//! 在线程之间无锁地移交写入者。
+
+use crate::writer::RetroCell;
+use std::cell::UnsafeCell;
+use std::fmt;
+use std::ops::{Deref, DerefMut};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+impl<T> RetroCell<T> {
+    /// Turn the writer into a [`HandoffWriter`] that any thread can claim
+    ///
+    /// 将写入者转换为任意线程都可以获取的 [`HandoffWriter`]
+    #[inline]
+    pub fn into_shared(self) -> HandoffWriter<T> {
+        HandoffWriter {
+            inner: Arc::new(Handoff {
+                held: AtomicBool::new(false),
+                cell: UnsafeCell::new(self),
+            }),
+        }
+    }
+}
+
+/// Shared right to write, claimed with a single CAS.
+///
+/// Where [`WriterToken`](crate::WriterToken) parks contending threads on a
+/// mutex, [`try_lock_writer`](Self::try_lock_writer) never blocks: a task
+/// hopping between the threads of a work-stealing runtime claims the writer,
+/// writes, and releases it wherever it is resumed.
+///
+/// 通过单次 CAS 获取的共享写入权。
+///
+/// [`WriterToken`](crate::WriterToken) 会使竞争线程在互斥锁上睡眠，而
+/// [`try_lock_writer`](Self::try_lock_writer) 从不阻塞：在工作窃取运行时的线程间迁移的任务
+/// 获取写入者、写入，并在其恢复运行的任意线程上释放。
+pub struct HandoffWriter<T> {
+    inner: Arc<Handoff<T>>,
+}
+
+struct Handoff<T> {
+    held: AtomicBool,
+    cell: UnsafeCell<RetroCell<T>>,
+}
+
+// The flag grants `&mut RetroCell` to one thread at a time
+// 该标志每次只向一个线程授予 `&mut RetroCell`
+unsafe impl<T> Send for Handoff<T> where RetroCell<T>: Send {}
+unsafe impl<T> Sync for Handoff<T> where RetroCell<T>: Send {}
+
+impl<T> HandoffWriter<T> {
+    /// Claim the writer if no other handle holds it
+    ///
+    /// 若没有其他句柄持有写入者，则获取它
+    #[inline]
+    pub fn try_lock_writer(&self) -> Option<WriterLease<'_, T>> {
+        self.inner
+            .held
+            .compare_exchange(false, true, Ordering::Acquire, Ordering::Relaxed)
+            .ok()?;
+        Some(WriterLease {
+            cell: unsafe { &mut *self.inner.cell.get() },
+            held: &self.inner.held,
+        })
+    }
+
+    /// Whether a handle currently holds the writer
+    ///
+    /// 当前是否有句柄持有写入者
+    #[inline]
+    pub fn is_locked(&self) -> bool {
+        self.inner.held.load(Ordering::Relaxed)
+    }
+
+    /// Take the writer back if this is the last handle
+    ///
+    /// 若这是最后一个句柄，则取回写入者
+    pub fn into_inner(self) -> Option<RetroCell<T>> {
+        let inner = Arc::into_inner(self.inner)?;
+        Some(inner.cell.into_inner())
+    }
+}
+
+impl<T> Clone for HandoffWriter<T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<T> fmt::Debug for HandoffWriter<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("HandoffWriter")
+            .field("locked", &self.is_locked())
+            .finish()
+    }
+}
+
+/// The writer claimed from a [`HandoffWriter`], released on drop
+///
+/// 从 [`HandoffWriter`] 获取的写入者，析构时释放
+pub struct WriterLease<'a, T> {
+    cell: &'a mut RetroCell<T>,
+    held: &'a AtomicBool,
+}
+
+impl<'a, T> Deref for WriterLease<'a, T> {
+    type Target = RetroCell<T>;
+    #[inline]
+    fn deref(&self) -> &RetroCell<T> {
+        self.cell
+    }
+}
+
+impl<'a, T> DerefMut for WriterLease<'a, T> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut RetroCell<T> {
+        self.cell
+    }
+}
+
+impl<'a, T> Drop for WriterLease<'a, T> {
+    #[inline]
+    fn drop(&mut self) {
+        self.held.store(false, Ordering::Release);
+    }
+}
+
+impl<'a, T> fmt::Debug for WriterLease<'a, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WriterLease")
+            .field("cell", &self.cell.shared.id)
+            .field("version", &self.cell.version())
+            .finish()
+    }
+}
@@ -0,0 +1,198 @@
+use crate::clock::Instant;
+use crate::rt::sync::atomic::{AtomicBool, AtomicPtr, AtomicU32, Ordering, fence};
+use crate::shared::{Node, SharedState};
+use crate::utils::{CachePadded, hot_path};
+use std::ptr;
+
+/// A per-thread announcement slot: the node its owner is currently reading
+///
+/// 每线程的声明槽位：其所有者当前正在读取的节点
+pub(crate) struct Slot<T> {
+    // Hot: written by the owning reader on every read
+    // Hot: 所属读者每次读取时写入
+    pub(crate) hazard: CachePadded<AtomicPtr<Node<T>>>,
+    in_use: AtomicBool,
+    // Immutable once the slot is linked into the registry
+    // 槽位链接进注册表后不再改变
+    next: *mut Slot<T>,
+    pub(crate) shared: *const SharedState<T>,
+}
+
+unsafe impl<T: Send + Sync> Send for Slot<T> {}
+unsafe impl<T: Send + Sync> Sync for Slot<T> {}
+
+impl<T> Slot<T> {
+    /// Stop announcing, waking the writer if it waits for the slot to clear
+    ///
+    /// 停止声明，若写入者正在等待槽位清空则将其唤醒
+    #[inline]
+    pub(crate) fn clear(&self) {
+        hot_path! {
+            self.hazard.store(ptr::null_mut(), Ordering::SeqCst);
+            let registry = unsafe { &(*self.shared).slots };
+            if registry.waiting.load(Ordering::SeqCst) {
+                registry.epoch.fetch_add(1, Ordering::Release);
+                crate::rt::wake_all(&registry.epoch);
+            }
+        }
+    }
+
+    /// Give the slot back to the registry for reuse
+    ///
+    /// 将槽位归还注册表以供复用
+    #[inline]
+    pub(crate) fn unregister(&self) {
+        self.in_use.store(false, Ordering::Release);
+    }
+}
+
+/// Append-only lock-free list of reader slots
+///
+/// 只追加的无锁读者槽位链表
+pub(crate) struct Registry<T> {
+    head: AtomicPtr<Slot<T>>,
+    // Set while the writer sleeps until some slot stops announcing a node
+    // 写入者睡眠等待某槽位停止声明节点时置位
+    waiting: AtomicBool,
+    epoch: AtomicU32,
+}
+
+impl<T> Registry<T> {
+    pub(crate) fn new() -> Self {
+        Self {
+            head: AtomicPtr::new(ptr::null_mut()),
+            waiting: AtomicBool::new(false),
+            epoch: AtomicU32::new(0),
+        }
+    }
+
+    /// Claim a free slot, or link a new one
+    ///
+    /// 占用一个空闲槽位，或链接一个新槽位
+    pub(crate) fn register(&self, shared: *const SharedState<T>) -> &Slot<T> {
+        let mut curr = self.head.load(Ordering::Acquire);
+        while let Some(slot) = unsafe { curr.as_ref() } {
+            if !slot.in_use.load(Ordering::Relaxed)
+                && slot
+                    .in_use
+                    .compare_exchange(false, true, Ordering::Acquire, Ordering::Relaxed)
+                    .is_ok()
+            {
+                return slot;
+            }
+            curr = slot.next;
+        }
+
+        let slot = Box::into_raw(Box::new(Slot {
+            hazard: CachePadded {
+                value: AtomicPtr::new(ptr::null_mut()),
+            },
+            in_use: AtomicBool::new(true),
+            next: ptr::null_mut(),
+            shared,
+        }));
+        let mut head = self.head.load(Ordering::Relaxed);
+        loop {
+            unsafe { (*slot).next = head };
+            match self
+                .head
+                .compare_exchange_weak(head, slot, Ordering::Release, Ordering::Relaxed)
+            {
+                Ok(_) => return unsafe { &*slot },
+                Err(actual) => head = actual,
+            }
+        }
+    }
+
+    /// Whether any slot announces `node`.
+    ///
+    /// Must run after the writer's store that unpublishes or locks `node`;
+    /// the fence pairs with the readers' SeqCst announce-then-validate.
+    ///
+    /// 是否有槽位声明了 `node`。
+    ///
+    /// 必须在写入者取消发布或锁定 `node` 的存储之后运行；
+    /// 该屏障与读者的 SeqCst "先声明后验证" 配对。
+    #[inline]
+    pub(crate) fn protects(&self, node: *const Node<T>) -> bool {
+        let mut curr = self.head.load(Ordering::Acquire);
+        if curr.is_null() {
+            return false;
+        }
+        fence(Ordering::SeqCst);
+        while let Some(slot) = unsafe { curr.as_ref() } {
+            if ptr::eq(slot.hazard.load(Ordering::SeqCst), node) {
+                return true;
+            }
+            curr = slot.next;
+        }
+        false
+    }
+
+    /// Number of slots announcing `node`, for monitoring
+    ///
+    /// 声明了 `node` 的槽位数，用于监控
+    pub(crate) fn announcing(&self, node: *const Node<T>) -> usize {
+        let mut count = 0;
+        let mut curr = self.head.load(Ordering::Acquire);
+        while let Some(slot) = unsafe { curr.as_ref() } {
+            if ptr::eq(slot.hazard.load(Ordering::Acquire), node) {
+                count += 1;
+            }
+            curr = slot.next;
+        }
+        count
+    }
+
+    /// Writer only: wait until no slot announces `node`
+    ///
+    /// 仅供 Writer 使用：等待直到没有槽位声明 `node`
+    pub(crate) fn wait_clear(&self, node: *const Node<T>) {
+        if !self.protects(node) {
+            return;
+        }
+        loop {
+            self.waiting.store(true, Ordering::SeqCst);
+            let epoch = self.epoch.load(Ordering::SeqCst);
+            if !self.protects(node) {
+                break;
+            }
+            crate::rt::wait(&self.epoch, epoch);
+        }
+        self.waiting.store(false, Ordering::Relaxed);
+    }
+
+    /// Writer only: like [`wait_clear`](Self::wait_clear), but give up at
+    /// `deadline`. Returns whether the slots cleared.
+    ///
+    /// 仅供 Writer 使用：与 [`wait_clear`](Self::wait_clear) 相同，但在 `deadline` 时放弃。返回槽位是否已清空。
+    pub(crate) fn wait_clear_until(&self, node: *const Node<T>, deadline: Instant) -> bool {
+        if !self.protects(node) {
+            return true;
+        }
+        let cleared = loop {
+            self.waiting.store(true, Ordering::SeqCst);
+            let epoch = self.epoch.load(Ordering::SeqCst);
+            if !self.protects(node) {
+                break true;
+            }
+            let now = Instant::now();
+            if now >= deadline {
+                break false;
+            }
+            crate::rt::wait_timeout(&self.epoch, epoch, deadline.duration_since(now));
+        };
+        self.waiting.store(false, Ordering::Relaxed);
+        cleared
+    }
+}
+
+impl<T> Drop for Registry<T> {
+    fn drop(&mut self) {
+        let mut curr = self.head.load(Ordering::Relaxed);
+        while !curr.is_null() {
+            let slot = unsafe { Box::from_raw(curr) };
+            curr = slot.next;
+        }
+    }
+}
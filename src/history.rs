@@ -0,0 +1,41 @@
+//! Archiving versions as they age out of the cell.
+//!
+//! 在版本从单元中老化淘汰时将其归档。
+
+use crate::version::Version;
+
+/// Destination for versions leaving the cell's in-memory history, set with
+/// [`RetroCellBuilder::history_sink`](crate::RetroCellBuilder::history_sink).
+///
+/// A retired version ages out when its node is reclaimed: once no reader
+/// holds it and a newer retro version has been published. Instead of being
+/// dropped silently, it is handed to the sink first, e.g. to serialize it to
+/// disk or send it to an archive service. Versions overwritten by in-place
+/// writes never become history and are not archived.
+///
+/// Runs on the writer thread, inside the write that triggers reclamation.
+///
+/// 离开单元内存历史的版本的去处，通过
+/// [`RetroCellBuilder::history_sink`](crate::RetroCellBuilder::history_sink) 设置。
+///
+/// 已退役版本在其节点被回收时老化淘汰：即没有读者持有它且已发布了更新的回溯版本之后。
+/// 它不会被静默丢弃，而是先交给接收器，例如序列化到磁盘或发送到归档服务。
+/// 被原地写入覆盖的版本不会成为历史，也不会被归档。
+///
+/// 在写入者线程上、于触发回收的写入之内运行。
+pub trait HistorySink<T>: Send {
+    /// Archive `value`, published as `version`
+    ///
+    /// 归档以 `version` 发布的 `value`
+    fn archive(&mut self, version: Version, value: &T);
+}
+
+impl<T, F> HistorySink<T> for F
+where
+    F: FnMut(Version, &T) + Send,
+{
+    #[inline]
+    fn archive(&mut self, version: Version, value: &T) {
+        self(version, value)
+    }
+}
@@ -0,0 +1,116 @@
+use crate::rt::sync::atomic::Ordering;
+use crate::shared::{Node, PTR_MASK, TAG_MASK};
+use crate::writer::RetroCell;
+use std::ptr;
+
+/// Writer-side consistency checks for phase boundaries.
+///
+/// All checks panic on violation in debug builds and compile to nothing in
+/// release builds, like `debug_assert!`. Racing readers may briefly hold a
+/// transient count, so call them when readers are known to be quiescent.
+///
+/// 用于阶段边界的写入端一致性检查。
+///
+/// 所有检查在调试构建中违反时 panic，在 release 构建中不产生任何代码，
+/// 与 `debug_assert!` 相同。竞争中的读者可能短暂持有临时计数，
+/// 因此请在确认读者已静止时调用。
+impl<T> RetroCell<T> {
+    /// Assert that no guard (counted or local) is held on any version this
+    /// cell keeps alive: current, retro and retired. Catches leaked guards.
+    ///
+    /// 断言此单元保留的任何版本（当前、回溯及已退役）上都没有守卫（计数或本地）。
+    /// 用于捕获泄漏的守卫。
+    pub fn assert_no_readers(&self) {
+        if !cfg!(debug_assertions) {
+            return;
+        }
+        let current = (self.shared.current.load(Ordering::Acquire) & PTR_MASK) as *mut Node<T>;
+        self.assert_idle(current, "current");
+        for node in self.garbage.iter() {
+            let role = if ptr::eq(node, self.shared.previous.load(Ordering::Acquire)) {
+                "retro"
+            } else {
+                "retired"
+            };
+            self.assert_idle(node, role);
+        }
+    }
+
+    /// Assert that the current version is not locked for an in-place write,
+    /// e.g. by a leaked [`InPlaceGuard`](crate::InPlaceGuard)
+    ///
+    /// 断言当前版本未被原地写入锁定，例如由泄漏的 [`InPlaceGuard`](crate::InPlaceGuard) 导致
+    pub fn assert_unlocked(&self) {
+        if !cfg!(debug_assertions) {
+            return;
+        }
+        assert!(
+            self.shared.current.load(Ordering::Acquire) & TAG_MASK == 0,
+            "retro-cell: cell {:?} is still locked in place",
+            self.shared.id,
+        );
+    }
+
+    /// Walk current/previous/garbage/pool and assert their consistency: every
+    /// node is owned by exactly one of them and the published retro version
+    /// is the newest retired one
+    ///
+    /// 遍历当前/回溯/垃圾队列/池并断言其一致性：每个节点恰好归属其中之一，
+    /// 且已发布的回溯版本是最新退役的版本
+    pub fn validate_invariants(&self) {
+        if !cfg!(debug_assertions) {
+            return;
+        }
+        let id = self.shared.id;
+        let current = (self.shared.current.load(Ordering::Acquire) & PTR_MASK) as *mut Node<T>;
+        assert!(
+            !current.is_null(),
+            "retro-cell: cell {id:?} has no current version"
+        );
+
+        let previous = self.shared.previous.load(Ordering::Acquire);
+        assert!(
+            previous.is_null()
+                || self
+                    .garbage
+                    .back()
+                    .is_some_and(|back| ptr::eq(back, previous)),
+            "retro-cell: cell {id:?} publishes a retro version it does not track",
+        );
+
+        let mut owned: Vec<*const Node<T>> =
+            Vec::with_capacity(1 + self.garbage.len() + self.pool.len());
+        owned.push(current);
+        owned.extend(self.garbage.iter().map(|node| node as *const Node<T>));
+        owned.extend(self.pool.iter().map(|node| &**node as *const Node<T>));
+        owned.sort_unstable();
+        assert!(
+            owned.windows(2).all(|pair| pair[0] != pair[1]),
+            "retro-cell: cell {id:?} tracks a node twice (current/garbage/pool overlap)",
+        );
+    }
+
+    /// All of the above: no readers, unlocked and structurally consistent
+    ///
+    /// 以上全部：无读者、未锁定且结构一致
+    #[inline]
+    pub fn debug_assert_quiescent(&self) {
+        self.assert_unlocked();
+        self.assert_no_readers();
+        self.validate_invariants();
+    }
+
+    fn assert_idle(&self, node: *mut Node<T>, role: &str) {
+        let readers = unsafe { &*node }.reader_count.count();
+        assert!(
+            readers == 0,
+            "retro-cell: cell {:?}: {readers} reader(s) still hold the {role} version",
+            self.shared.id,
+        );
+        assert!(
+            !self.shared.slots.protects(node),
+            "retro-cell: cell {:?}: a local reader still holds the {role} version",
+            self.shared.id,
+        );
+    }
+}
@@ -8,22 +8,210 @@
 //!
 //! - **Retroactive Reading**: Readers can access the previous version during writes to avoid waiting.
 //! - **Congestion Control**: Writers can detect congestion and choose to wait or force an update.
+//! - **Local Readers**: A `ReaderFactory` hands out pointer-sized per-thread readers that announce
+//!   their version in a private slot instead of updating a shared reader count.
+//! - **Panic-Free Hot Paths**: With the `no-panic` feature, optimized builds fail to link unless
+//!   reads, guard drops and in-place lock/unlock provably cannot panic.
+//! - **Integrity Checks**: With the `checksum` feature, every published version can be checksummed
+//!   and verified whenever a read guard is created in debug builds.
+//! - **Retain Coalescing**: With the `coalesce` feature, nested `Reader::read_nested` guards of the
+//!   same version on one thread share a single reader count.
+//! - **Huge Values**: With the `mmap` feature, `MmapSlice` keeps payloads in anonymous page mappings
+//!   that can be released while their node waits for reuse. The experimental `page-cow` feature
+//!   adds `CowPages` (Linux only), whose clones copy only the pages modified since a sealed base.
+//! - **Compact Nodes**: With the `compact` feature, the per-version reader count is not
+//!   cache-padded, trading false-sharing protection for a smaller footprint per version.
+//! - **Static Cells**: `RetroCell::new_in` places the shared state in a `SharedStorage` static, so
+//!   cloning and dropping readers touches no reference count.
+//! - **Reactive Signals**: With the `futures-signals` feature, `Reader::to_signal` drives
+//!   frameworks built on `futures_signals::signal::Signal`.
+//! - **Atomic Facade**: `AtomicCell` offers `load`/`store`/`swap`/`compare_exchange` for code
+//!   migrating from `AtomicCell`- or `ArcSwap`-style cells.
+//! - **Select**: `select` and `select_async` wait for the first of several cells to publish.
+//! - **Serde COW**: With the `serde` feature, `write_cow_serde` stages copies of non-`Clone` values
+//!   through a serialize/deserialize round-trip.
 //!
 //! ## 特性
 //!
 //! - **回溯读取**：读者可以在写入时读取先前版本以避免等待。
 //! - **拥塞控制**：写入者可以检测拥塞并选择等待或强制更新。
+//! - **本地读者**：`ReaderFactory` 分发指针大小的每线程读者，它们在私有槽位中声明所读版本，
+//!   而不是更新共享的读者计数。
+//! - **无 panic 热路径**：启用 `no-panic` 特性后，除非读取、守卫析构及原地加锁/解锁
+//!   可证明不会 panic，否则优化构建将无法链接。
+//! - **完整性检查**：启用 `checksum` 特性后，可为每个已发布版本计算校验和，
+//!   并在调试构建中每次创建读取守卫时验证。
+//! - **计数合并**：启用 `coalesce` 特性后，同一线程对同一版本的嵌套 `Reader::read_nested`
+//!   守卫共享一次读者计数。
+//! - **超大值**：启用 `mmap` 特性后，`MmapSlice` 将数据保存在匿名页映射中，
+//!   并可在其节点等待复用时释放。实验性的 `page-cow` 特性新增 `CowPages`（仅限 Linux），
+//!   其克隆只复制自已封印基底以来修改过的页。
+//! - **紧凑节点**：启用 `compact` 特性后，每个版本的读者计数不再进行缓存行填充，
+//!   以牺牲伪共享保护换取更小的单版本内存占用。
+//! - **静态单元**：`RetroCell::new_in` 将共享状态置于 `SharedStorage` 静态变量中，
+//!   克隆和析构读取者都不涉及引用计数。
+//! - **响应式信号**：启用 `futures-signals` 特性后，`Reader::to_signal` 可驱动基于
+//!   `futures_signals::signal::Signal` 的框架。
+//! - **原子外观**：`AtomicCell` 提供 `load`/`store`/`swap`/`compare_exchange`，
+//!   便于从 `AtomicCell` 或 `ArcSwap` 风格的单元迁移。
+//! - **多单元等待**：`select` 与 `select_async` 等待多个单元中第一个发布的单元。
+//! - **Serde COW**：启用 `serde` 特性后，`write_cow_serde` 通过序列化/反序列化往返
+//!   为未实现 `Clone` 的值暂存副本。
 
+mod atomic;
+mod audit;
+mod builder;
+#[cfg(feature = "coalesce")]
+mod coalesce;
+mod clock;
+mod dynamic;
+mod error;
+mod feed;
+mod frozen;
+mod handoff;
+mod hazard;
+mod history;
+mod invariants;
+mod local;
+mod mapped;
+mod mirror;
+#[cfg(all(feature = "mmap", unix))]
+mod mmap;
+mod multi;
+#[cfg(all(feature = "page-cow", target_os = "linux"))]
+mod pages;
 mod reader;
+mod recycle;
+mod ring;
 mod rt;
+mod select;
+#[cfg(feature = "serde")]
+mod serial;
+mod session;
 mod shared;
+#[cfg(feature = "futures-signals")]
+mod signal;
+mod slot;
+#[cfg(feature = "stats")]
+mod stats;
 mod sync;
+mod token;
+mod updater;
+mod updates;
 mod utils;
+mod version;
+#[cfg(not(feature = "loom"))]
+mod weak;
 mod writer;
 
+// Re-export the atomic facade
+// 导出原子外观类型
+pub use atomic::AtomicCell;
+// Re-export audit types
+// 导出审计类型
+pub use audit::AuditRecord;
+// Re-export builder types
+// 导出构建器类型
+pub use builder::{FailurePolicy, RetroCellBuilder};
+// Re-export the coalescing read guard
+// 导出合并计数的读取守卫
+#[cfg(feature = "coalesce")]
+pub use coalesce::NestedRef;
+// Re-export the clock reading used for publication times
+// 导出用于发布时间的时钟读数
+pub use clock::Instant;
+// Re-export the virtual clock for tests
+// 导出用于测试的虚拟时钟
+#[cfg(feature = "test-util")]
+pub use clock::MockClock;
+// Re-export type-erased reader types
+// 导出类型擦除的读取者类型
+pub use dynamic::{DynReader, ReadAccess, ReadGuard};
+// Re-export error types
+// 导出错误类型
+pub use error::Error;
+// Re-export feeder types
+// 导出馈送类型
+pub use feed::FeedHandle;
+// Re-export frozen cells
+// 导出封存单元
+pub use frozen::FrozenCell;
+// Re-export lock-free writer handoff types
+// 导出无锁写入者移交类型
+pub use handoff::{HandoffWriter, WriterLease};
+// Re-export the history archiving trait
+// 导出历史归档 trait
+pub use history::HistorySink;
+// Re-export local reader types
+// 导出本地读取器类型
+pub use local::{LocalReader, LocalRef, ReaderFactory};
+// Re-export projected reader types
+// 导出投影读取器类型
+pub use mapped::MappedReader;
+// Re-export mirroring types
+// 导出镜像类型
+pub use mirror::MirroredWriter;
+// Re-export mmap storage
+// 导出 mmap 存储
+#[cfg(all(feature = "mmap", unix))]
+pub use mmap::MmapSlice;
+// Re-export page-granular COW storage
+// 导出页粒度写时复制存储
+#[cfg(all(feature = "page-cow", target_os = "linux"))]
+pub use pages::CowPages;
+// Re-export the multi-producer writer
+// 导出多生产者写入者
+pub use multi::SharedWriter;
 // Re-export reader types
 // 导出读取器类型
-pub use reader::{BlockedReader, ReadResult, Reader, Ref};
+pub use reader::{
+    BlockedReader, MappedRef, ReadMeta, ReadPolicy, ReadResult, Reader, Ref, leaked_pins,
+};
+// Re-export the node recycling trait
+// 导出节点复用 trait
+pub use recycle::Recycle;
+// Re-export multi-cell waiting
+// 导出多单元等待
+pub use select::{Waitable, select, select_async};
+// Re-export read session types
+// 导出读取会话类型
+pub use session::ReadSession;
+// Re-export shared storage
+// 导出共享存储
+pub use shared::SharedStorage;
+// Re-export the signal adapter
+// 导出信号适配器
+#[cfg(feature = "futures-signals")]
+pub use signal::ReaderSignal;
+// Re-export the handoff slot
+// 导出交接槽类型
+pub use slot::RetroSlot;
+// Re-export reader statistics
+// 导出读取者统计类型
+#[cfg(feature = "stats")]
+pub use stats::ReaderStats;
+// Re-export the wake mechanism and wait observer traits
+// 导出唤醒机制与等待观察者 trait
+pub use sync::{Notify, WaitObserver};
+// Re-export writer token types
+// 导出写入令牌类型
+pub use token::{WriterGuard, WriterToken};
+// Re-export the update queue handle
+// 导出更新队列句柄
+pub use updater::Updater;
+// Re-export update iteration types
+// 导出更新迭代类型
+pub use updates::{Change, Updates};
+// Re-export version types
+// 导出版本类型
+pub use version::Version;
+// Re-export weak reader types
+// 导出弱读取器类型
+#[cfg(not(feature = "loom"))]
+pub use weak::WeakReader;
 // Re-export writer types
 // 导出写入器类型
-pub use writer::{CongestedWriter, InPlaceGuard, RetroCell, WriteOutcome};
+pub use writer::{
+    CongestedWriter, InPlaceGuard, MappedInPlaceGuard, RetroCell, WriteOutcome, WritePath,
+    WriteReport,
+};
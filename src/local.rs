@@ -0,0 +1,225 @@
+use crate::hazard::Slot;
+use crate::reader::Reader;
+use crate::rt::sync::atomic::Ordering;
+use crate::shared::{LOCKED, Node, PTR_MASK, Shared, SharedState, TAG_MASK};
+use crate::utils::{Backoff, hot_path};
+use std::marker::PhantomData;
+use std::ops::Deref;
+
+/// Owner of the shared state that hands out per-thread [`LocalReader`]s.
+///
+/// Local readers don't touch the per-version reader count: each one
+/// announces the version it reads in its own cache-padded slot, so reads
+/// from many threads don't contend on a shared counter.
+///
+/// 持有共享状态并分发每线程 [`LocalReader`] 的所有者。
+///
+/// 本地读者不操作每个版本的读者计数：每个读者在自己的缓存行填充槽位中
+/// 声明其读取的版本，因此多线程读取不会在共享计数上竞争。
+pub struct ReaderFactory<T> {
+    shared: Shared<T>,
+}
+
+impl<T> ReaderFactory<T> {
+    /// Register a lightweight reader handle for the calling thread.
+    ///
+    /// Registration claims a slot once; slots are recycled when handles
+    /// are dropped.
+    ///
+    /// 为调用线程注册一个轻量读取句柄。
+    ///
+    /// 注册时占用一次槽位；句柄析构后槽位会被复用。
+    #[inline]
+    pub fn register(&self) -> LocalReader<'_, T> {
+        LocalReader::register(&self.shared)
+    }
+
+    /// Create a regular (counted) reader for the same cell
+    ///
+    /// 为同一单元创建普通（计数）读取者
+    #[inline]
+    pub fn reader(&self) -> Reader<T> {
+        Reader::new(self.shared.clone())
+    }
+}
+
+impl<T> From<Reader<T>> for ReaderFactory<T> {
+    #[inline]
+    fn from(reader: Reader<T>) -> Self {
+        Self {
+            shared: reader.shared.clone(),
+        }
+    }
+}
+
+impl<T> Reader<T> {
+    /// Like [`ReaderFactory::register`], without a factory: read through a
+    /// per-thread slot for as long as the returned handle lives, keeping it
+    /// for tight read loops.
+    ///
+    /// 与 [`ReaderFactory::register`] 相同，但无需工厂：在返回的句柄存活期间通过每线程槽位读取，
+    /// 适用于密集读取循环。
+    #[inline]
+    pub fn pin(&self) -> LocalReader<'_, T> {
+        LocalReader::register(&self.shared)
+    }
+}
+
+/// Pointer-sized per-thread reader registered with a [`ReaderFactory`].
+///
+/// A local reader holds at most one guard at a time: reads borrow it mutably.
+///
+/// 在 [`ReaderFactory`] 注册的指针大小的每线程读取者。
+///
+/// 本地读者同一时刻最多持有一个守卫：读取操作以可变方式借用它。
+pub struct LocalReader<'f, T> {
+    slot: &'f Slot<T>,
+}
+
+impl<'f, T> LocalReader<'f, T> {
+    /// Claim a slot of `shared` for the calling thread
+    ///
+    /// 为调用线程占用 `shared` 的一个槽位
+    #[inline]
+    fn register(shared: &'f SharedState<T>) -> Self {
+        LocalReader {
+            slot: shared.slots.register(shared),
+        }
+    }
+
+    #[inline(always)]
+    fn shared(&self) -> &'f SharedState<T> {
+        unsafe { &*self.slot.shared }
+    }
+
+    /// Try to read the current value without blocking; `None` while a writer
+    /// holds the in-place lock
+    ///
+    /// 尝试非阻塞地读取当前值；写入者持有原地锁时返回 `None`
+    #[inline]
+    pub fn try_read(&mut self) -> Option<LocalRef<'_, T>> {
+        self.acquire_current()
+            .map(|node| LocalRef::new(self.slot, node))
+    }
+
+    /// Read the latest data (block until available)
+    ///
+    /// 读取最新数据（阻塞直到可用）
+    pub fn read(&mut self) -> LocalRef<'_, T> {
+        let shared = self.shared();
+        loop {
+            if let Some(node) = self.acquire_current() {
+                return LocalRef::new(self.slot, node);
+            }
+
+            let ticket = shared.notifier.ticket();
+            // If lock is released after getting ticket, retry immediately
+            // 获取 ticket 后若锁释放，立即重试
+            if (shared.current.load(Ordering::Acquire) & TAG_MASK) == 0 {
+                continue;
+            }
+            shared.notifier.wait_ticket(ticket);
+        }
+    }
+
+    /// Read historical data (if available)
+    ///
+    /// 读取历史数据（如果有）
+    pub fn read_retro(&mut self) -> Option<LocalRef<'_, T>> {
+        let node = hot_path! {
+            let shared = self.shared();
+            let mut backoff = Backoff::new();
+            loop {
+                let prev_ptr = shared.previous.load(Ordering::Acquire);
+                if prev_ptr.is_null() {
+                    return None;
+                }
+                self.slot.hazard.store(prev_ptr, Ordering::SeqCst);
+
+                // The writer may reclaim the retro node (double-buffer mode)
+                // 写入者可能会回收回溯节点（双缓冲模式）
+                if shared.previous.load(Ordering::SeqCst) == prev_ptr {
+                    return Some(unsafe { &*prev_ptr });
+                }
+                self.slot.clear();
+                backoff.snooze();
+            }
+        }?;
+        Some(LocalRef::new(self.slot, node))
+    }
+
+    /// Announce the current node in the slot, validating that it is still
+    /// published and unlocked
+    ///
+    /// 在槽位中声明当前节点，并验证其仍处于发布且未锁定状态
+    #[inline]
+    fn acquire_current(&self) -> Option<&'f Node<T>> {
+        hot_path! {
+            let shared = self.shared();
+            let mut backoff = Backoff::new();
+            loop {
+                let curr_val = shared.current.load(Ordering::Acquire);
+                if (curr_val & TAG_MASK) == LOCKED {
+                    return None;
+                }
+                let ptr = (curr_val & PTR_MASK) as *mut Node<T>;
+                self.slot.hazard.store(ptr, Ordering::SeqCst);
+
+                // Pairs with the fence in the writer's slot scan
+                // 与写入者扫描槽位时的屏障配对
+                if shared.current.load(Ordering::SeqCst) == curr_val {
+                    return Some(unsafe { &*ptr });
+                }
+                self.slot.clear();
+                backoff.snooze();
+            }
+        }
+    }
+}
+
+impl<'f, T> Drop for LocalReader<'f, T> {
+    #[inline]
+    fn drop(&mut self) {
+        // A leaked guard must not keep announcing through a recycled slot
+        // 泄漏的守卫不得通过被复用的槽位继续声明
+        self.slot.clear();
+        self.slot.unregister();
+    }
+}
+
+/// RAII guard returned by a [`LocalReader`]
+///
+/// [`LocalReader`] 返回的 RAII 守卫
+pub struct LocalRef<'r, T> {
+    slot: &'r Slot<T>,
+    node: &'r Node<T>,
+    _reader: PhantomData<&'r mut ()>,
+}
+
+impl<'r, T> LocalRef<'r, T> {
+    #[inline(always)]
+    fn new(slot: &'r Slot<T>, node: &'r Node<T>) -> Self {
+        #[cfg(feature = "checksum")]
+        node.verify(unsafe { &(*slot.shared).hooks });
+        Self {
+            slot,
+            node,
+            _reader: PhantomData,
+        }
+    }
+}
+
+impl<'r, T> Deref for LocalRef<'r, T> {
+    type Target = T;
+    #[inline(always)]
+    fn deref(&self) -> &T {
+        unsafe { &*self.node.data.get() }
+    }
+}
+
+impl<'r, T> Drop for LocalRef<'r, T> {
+    #[inline(always)]
+    fn drop(&mut self) {
+        self.slot.clear();
+    }
+}
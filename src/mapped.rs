@@ -0,0 +1,100 @@
+//! Reader handles pre-composed with a projection.
+//!
+//! 预先组合了投影的读取者句柄。
+
+use crate::reader::{MappedRef, Reader, Ref};
+use std::fmt;
+use std::sync::Arc;
+
+type Projection<T, U> = Arc<dyn Fn(&T) -> &U + Send + Sync>;
+
+/// Reader of a part of a cell's value, created with [`Reader::map`].
+///
+/// Library boundaries can hand out e.g. a reader of one config section
+/// without exposing the whole top-level type. Clones share the projection.
+///
+/// 单元值某一部分的读取者，由 [`Reader::map`] 创建。
+///
+/// 库边界可以分发例如某个配置段的读取者，而无需暴露整个顶层类型。克隆共享该投影。
+pub struct MappedReader<T, U: ?Sized> {
+    reader: Reader<T>,
+    project: Projection<T, U>,
+}
+
+impl<T> Reader<T> {
+    /// Turn this reader into a reader of the part of the value selected by
+    /// `project`
+    ///
+    /// 将此读取者转换为 `project` 所选取的值的部分的读取者
+    #[inline]
+    pub fn map<U: ?Sized, F>(self, project: F) -> MappedReader<T, U>
+    where
+        F: Fn(&T) -> &U + Send + Sync + 'static,
+    {
+        MappedReader {
+            reader: self,
+            project: Arc::new(project),
+        }
+    }
+}
+
+impl<T, U: ?Sized> MappedReader<T, U> {
+    /// Read the latest part (block until available)
+    ///
+    /// 读取最新的部分（阻塞直到可用）
+    #[inline]
+    pub fn read(&self) -> MappedRef<'_, U> {
+        Ref::map(self.reader.read(), |value| (self.project)(value))
+    }
+
+    /// Read the part of the retro value (if available)
+    ///
+    /// 读取回溯值的部分（如果有）
+    #[inline]
+    pub fn read_retro(&self) -> Option<MappedRef<'_, U>> {
+        let retro = self.reader.read_retro()?;
+        Some(Ref::map(retro, |value| (self.project)(value)))
+    }
+
+    /// The underlying reader of the whole value
+    ///
+    /// 整个值的底层读取者
+    #[inline]
+    pub fn reader(&self) -> &Reader<T> {
+        &self.reader
+    }
+
+    /// Narrow the projection further
+    ///
+    /// 进一步收窄投影
+    pub fn map<V: ?Sized, F>(self, project: F) -> MappedReader<T, V>
+    where
+        T: 'static,
+        U: 'static,
+        F: Fn(&U) -> &V + Send + Sync + 'static,
+    {
+        let outer = self.project;
+        MappedReader {
+            reader: self.reader,
+            project: Arc::new(move |value| project(outer(value))),
+        }
+    }
+}
+
+impl<T, U: ?Sized> Clone for MappedReader<T, U> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            reader: self.reader.clone(),
+            project: self.project.clone(),
+        }
+    }
+}
+
+impl<T, U: ?Sized> fmt::Debug for MappedReader<T, U> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MappedReader")
+            .field("cell", &self.reader.shared.id)
+            .finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,119 @@
+//! Hot-standby replication of one cell's publications into another.
+//!
+//! 将一个单元的发布热备复制到另一个单元。
+
+use crate::writer::{RetroCell, WriteOutcome, WritePath};
+use std::fmt;
+
+impl<T: Clone> RetroCell<T> {
+    /// Write through a [`MirroredWriter`] that replicates every publication
+    /// into `standby`, version numbers included.
+    ///
+    /// If `standby` lags behind, the current value is replicated first.
+    ///
+    /// 通过 [`MirroredWriter`] 写入，它将每次发布（包括版本号）复制到 `standby`。
+    ///
+    /// 若 `standby` 落后，则首先复制当前值。
+    pub fn mirror_to<'a>(&'a mut self, standby: &'a mut RetroCell<T>) -> MirroredWriter<'a, T> {
+        if standby.version() != self.version() {
+            standby.replicate(self.current().clone(), self.version(), WritePath::Cow);
+        }
+        MirroredWriter {
+            primary: self,
+            standby,
+        }
+    }
+}
+
+/// Writer publishing to a primary cell and replicating into a standby one.
+///
+/// Each publication is replicated through the same [`WritePath`], so the
+/// standby's readers see the same versions and the same retro values. An
+/// in-place replication waits for the standby's readers to drain.
+///
+/// 向主单元发布并复制到备用单元的写入者。
+///
+/// 每次发布都会通过相同的 [`WritePath`] 复制，因此备用单元的读者看到相同的版本
+/// 和相同的回溯值。原地复制会等待备用单元的读者排空。
+pub struct MirroredWriter<'a, T: Clone> {
+    primary: &'a mut RetroCell<T>,
+    standby: &'a mut RetroCell<T>,
+}
+
+impl<'a, T: Clone> MirroredWriter<'a, T> {
+    /// Update the value in place if the primary has no readers, by COW
+    /// otherwise, then replicate it
+    ///
+    /// 若主单元没有读者则原地更新，否则通过 COW 更新，然后进行复制
+    pub fn write<F, R>(&mut self, f: F) -> R
+    where
+        F: FnOnce(&mut T) -> R,
+    {
+        let (result, path) = match self.primary.try_write() {
+            WriteOutcome::InPlace(mut guard) => (f(&mut guard), WritePath::InPlace),
+            WriteOutcome::Congested(writer) => (writer.perform_cow(f), WritePath::Cow),
+        };
+        self.replicate(path);
+        result
+    }
+
+    /// Perform a COW update, then replicate it
+    ///
+    /// 执行 COW 更新，然后进行复制
+    pub fn write_cow<F, R>(&mut self, f: F) -> R
+    where
+        F: FnOnce(&mut T) -> R,
+    {
+        let result = self.primary.write_cow(f);
+        self.replicate(WritePath::Cow);
+        result
+    }
+
+    /// Publish `value`, then replicate it
+    ///
+    /// 发布 `value`，然后进行复制
+    pub fn store(&mut self, value: T) {
+        let path = match self.primary.try_write() {
+            WriteOutcome::InPlace(mut guard) => {
+                *guard = value;
+                WritePath::InPlace
+            }
+            WriteOutcome::Congested(writer) => {
+                writer.cell.install(value);
+                WritePath::Cow
+            }
+        };
+        self.replicate(path);
+    }
+
+    /// The primary cell
+    ///
+    /// 主单元
+    #[inline]
+    pub fn primary(&self) -> &RetroCell<T> {
+        self.primary
+    }
+
+    /// The standby cell
+    ///
+    /// 备用单元
+    #[inline]
+    pub fn standby(&self) -> &RetroCell<T> {
+        self.standby
+    }
+
+    fn replicate(&mut self, path: WritePath) {
+        self.standby
+            .replicate(self.primary.current().clone(), self.primary.version(), path);
+    }
+}
+
+impl<'a, T: Clone> fmt::Debug for MirroredWriter<'a, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MirroredWriter")
+            .field("primary", &self.primary.shared.id)
+            .field("standby", &self.standby.shared.id)
+            .field("version", &self.primary.version())
+            .finish()
+    }
+}
@@ -0,0 +1,157 @@
+//! Page-mapped storage for huge values (`mmap` feature, unix only).
+//!
+//! 用于超大值的页映射存储（`mmap` 特性，仅限 unix）。
+
+use std::alloc::{Layout, handle_alloc_error};
+use std::fmt;
+use std::ops::{Deref, DerefMut};
+use std::ptr::{self, NonNull};
+
+/// A fixed-length slice living in its own anonymous `mmap` region instead of
+/// the heap.
+///
+/// Meant for multi-hundred-MB payloads (model weights, lookup tables) stored
+/// in a [`RetroCell`](crate::RetroCell). Cloning (e.g. by `write_cow`) maps a
+/// fresh region. Pair it with
+/// [`RetroCellBuilder::reclaim_with`](crate::RetroCellBuilder::reclaim_with)`(MmapSlice::release)`
+/// so retired versions parked for reuse give their pages back to the OS.
+///
+/// 位于独立匿名 `mmap` 区域（而非堆）中的定长切片。
+///
+/// 适用于存储在 [`RetroCell`](crate::RetroCell) 中的数百 MB 级数据（模型权重、查找表）。
+/// 克隆（例如 `write_cow`）会映射新的区域。配合
+/// [`RetroCellBuilder::reclaim_with`](crate::RetroCellBuilder::reclaim_with)`(MmapSlice::release)`
+/// 使用，可使等待复用的已退役版本将其内存页归还给操作系统。
+pub struct MmapSlice<T: Copy> {
+    ptr: NonNull<T>,
+    len: usize,
+    // Size of the mapping in bytes (0 if nothing is mapped)
+    // 映射的字节大小（未映射时为 0）
+    mapped: usize,
+}
+
+unsafe impl<T: Copy + Send> Send for MmapSlice<T> {}
+unsafe impl<T: Copy + Sync> Sync for MmapSlice<T> {}
+
+impl<T: Copy> MmapSlice<T> {
+    // Anonymous mappings are page-aligned
+    // 匿名映射按页对齐
+    const MAPPABLE: () = assert!(align_of::<T>() <= 4096);
+
+    /// Map a region of `len` elements, all set to `value`
+    ///
+    /// 映射一个包含 `len` 个元素的区域，所有元素均设为 `value`
+    pub fn filled(len: usize, value: T) -> Self {
+        let this = Self::map(len);
+        // Zero pages are not necessarily valid `T`s, so no slice may exist
+        // before every element is written through the raw pointer
+        // 零页不一定是有效的 `T`，因此在通过裸指针写入所有元素之前不得存在切片
+        let base = this.ptr.as_ptr();
+        for i in 0..len {
+            unsafe { base.add(i).write(value) };
+        }
+        this
+    }
+
+    /// Map a region holding a copy of `src`
+    ///
+    /// 映射一个保存 `src` 副本的区域
+    pub fn from_slice(src: &[T]) -> Self {
+        let this = Self::map(src.len());
+        unsafe { ptr::copy_nonoverlapping(src.as_ptr(), this.ptr.as_ptr(), src.len()) };
+        this
+    }
+
+    /// Return the pages to the OS and leave the slice empty. The mapping
+    /// itself is kept until drop.
+    ///
+    /// 将内存页归还给操作系统并使切片为空。映射本身保留到析构为止。
+    pub fn release(this: &mut Self) {
+        if this.mapped > 0 {
+            unsafe {
+                libc::madvise(
+                    this.ptr.as_ptr().cast(),
+                    this.mapped,
+                    libc::MADV_DONTNEED,
+                )
+            };
+        }
+        this.len = 0;
+    }
+
+    // The contents are uninitialized (zero pages) until written
+    // 在写入之前内容未初始化（零页）
+    fn map(len: usize) -> Self {
+        #[allow(clippy::let_unit_value)]
+        let () = Self::MAPPABLE;
+        let layout = match Layout::array::<T>(len) {
+            Ok(layout) => layout,
+            Err(_) => capacity_overflow(),
+        };
+        if layout.size() == 0 {
+            return Self {
+                ptr: NonNull::dangling(),
+                len,
+                mapped: 0,
+            };
+        }
+        let addr = unsafe {
+            libc::mmap(
+                ptr::null_mut(),
+                layout.size(),
+                libc::PROT_READ | libc::PROT_WRITE,
+                libc::MAP_PRIVATE | libc::MAP_ANONYMOUS,
+                -1,
+                0,
+            )
+        };
+        if addr == libc::MAP_FAILED {
+            handle_alloc_error(layout);
+        }
+        Self {
+            ptr: unsafe { NonNull::new_unchecked(addr.cast()) },
+            len,
+            mapped: layout.size(),
+        }
+    }
+}
+
+#[cold]
+fn capacity_overflow() -> ! {
+    panic!("retro-cell: MmapSlice capacity overflow")
+}
+
+impl<T: Copy> Deref for MmapSlice<T> {
+    type Target = [T];
+    #[inline]
+    fn deref(&self) -> &[T] {
+        unsafe { std::slice::from_raw_parts(self.ptr.as_ptr(), self.len) }
+    }
+}
+
+impl<T: Copy> DerefMut for MmapSlice<T> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut [T] {
+        unsafe { std::slice::from_raw_parts_mut(self.ptr.as_ptr(), self.len) }
+    }
+}
+
+impl<T: Copy> Clone for MmapSlice<T> {
+    fn clone(&self) -> Self {
+        Self::from_slice(self)
+    }
+}
+
+impl<T: Copy> Drop for MmapSlice<T> {
+    fn drop(&mut self) {
+        if self.mapped > 0 {
+            unsafe { libc::munmap(self.ptr.as_ptr().cast(), self.mapped) };
+        }
+    }
+}
+
+impl<T: Copy + fmt::Debug> fmt::Debug for MmapSlice<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
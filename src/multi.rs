@@ -0,0 +1,142 @@
+//! Writer handles shared by several producer threads.
+//!
+//! 由多个生产者线程共享的写入者句柄。
+
+use crate::error::Error;
+use crate::reader::Reader;
+use crate::token::WriterToken;
+use crate::version::Version;
+use crate::writer::{RetroCell, WritePath, WriteReport};
+use std::fmt;
+
+/// Cloneable writer that lets several threads publish to one cell.
+///
+/// Each write checks the writer out of an internal [`WriterToken`] only for
+/// its own duration, and still takes the in-place or COW path on its own.
+/// Reads stay lock-free. As with the token, a producer panicking while it
+/// writes poisons the writer: writes fail with [`Error::Poisoned`] until
+/// [`clear_poison`](Self::clear_poison) is called.
+///
+/// 允许多个线程向同一单元发布的可克隆写入者。
+///
+/// 每次写入仅在自身期间从内部的 [`WriterToken`] 签出写入者，且仍各自选择原地或 COW 路径。
+/// 读取保持无锁。与令牌相同，生产者在写入时 panic 会使写入者中毒：
+/// 在调用 [`clear_poison`](Self::clear_poison) 之前，写入均以 [`Error::Poisoned`] 失败。
+pub struct SharedWriter<T> {
+    token: WriterToken<T>,
+}
+
+impl<T> SharedWriter<T> {
+    /// Share the writer between producers
+    ///
+    /// 在生产者之间共享写入者
+    #[inline]
+    pub fn new(cell: RetroCell<T>) -> Self {
+        Self {
+            token: WriterToken::new(cell),
+        }
+    }
+
+    /// Apply `f` in place if no reader holds the current value, otherwise
+    /// through COW, like [`RetroCell::write`]
+    ///
+    /// 与 [`RetroCell::write`] 相同：没有读者持有当前值时原地应用 `f`，否则通过 COW 应用
+    pub fn write<F>(&self, f: F) -> Result<WriteReport, Error>
+    where
+        T: Clone,
+        F: FnOnce(&mut T),
+    {
+        Ok(self.token.acquire()?.write(f))
+    }
+
+    /// Publish `value`, like [`RetroCell::store`]
+    ///
+    /// 与 [`RetroCell::store`] 相同地发布 `value`
+    pub fn store(&self, value: T) -> Result<WritePath, Error> {
+        Ok(self.token.acquire()?.store(value))
+    }
+
+    /// Run `f` with exclusive access to the writer, e.g. to combine a check
+    /// and a write atomically with respect to other producers
+    ///
+    /// 以独占方式访问写入者并运行 `f`，例如使检查与写入相对其他生产者而言是原子的
+    pub fn with<F, R>(&self, f: F) -> Result<R, Error>
+    where
+        F: FnOnce(&mut RetroCell<T>) -> R,
+    {
+        Ok(f(&mut *self.token.acquire()?))
+    }
+
+    /// Whether a producer panicked while writing and the poison has not been
+    /// cleared yet (`false` while another producer is writing)
+    ///
+    /// 是否有生产者在写入时 panic 且尚未清除中毒状态（其他生产者正在写入时为 `false`）
+    pub fn is_poisoned(&self) -> bool {
+        self.token.is_poisoned()
+    }
+
+    /// Run `f` with the writer even if poisoned, to inspect or repair the
+    /// value before clearing the poison
+    ///
+    /// 即使已中毒也以写入者运行 `f`，以便在清除中毒状态前检查或修复值
+    pub fn recover<F, R>(&self, f: F) -> R
+    where
+        F: FnOnce(&mut RetroCell<T>) -> R,
+    {
+        f(&mut self.token.recover())
+    }
+
+    /// Declare the value consistent again, so producers can write
+    ///
+    /// 声明值已恢复一致，使生产者可以再次写入
+    pub fn clear_poison(&self) {
+        self.token.clear_poison();
+    }
+
+    /// Create a new reader of the cell
+    ///
+    /// 创建该单元的新读取者
+    #[inline]
+    pub fn new_reader(&self) -> Reader<T> {
+        self.token.recover().new_reader()
+    }
+
+    /// Version of the currently published value
+    ///
+    /// 当前已发布值的版本
+    #[inline]
+    pub fn version(&self) -> Version {
+        self.token.recover().version()
+    }
+
+    /// Take the writer back if this is the last handle
+    ///
+    /// 若这是最后一个句柄，则取回写入者
+    pub fn into_inner(self) -> Option<RetroCell<T>> {
+        self.token.into_inner()
+    }
+}
+
+impl<T> From<RetroCell<T>> for SharedWriter<T> {
+    #[inline]
+    fn from(cell: RetroCell<T>) -> Self {
+        Self::new(cell)
+    }
+}
+
+impl<T> Clone for SharedWriter<T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            token: self.token.clone(),
+        }
+    }
+}
+
+impl<T> fmt::Debug for SharedWriter<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SharedWriter")
+            .field("token", &self.token)
+            .finish()
+    }
+}
@@ -0,0 +1,268 @@
+//! Page-granular copy-on-write storage for huge values (`page-cow` feature,
+//! Linux only, experimental).
+//!
+//! 用于超大值的页粒度写时复制存储（`page-cow` 特性，仅限 Linux，实验性）。
+
+use std::alloc::{Layout, handle_alloc_error};
+use std::fmt;
+use std::io;
+use std::ops::{Deref, Range};
+use std::os::fd::{AsRawFd, FromRawFd, OwnedFd};
+use std::ptr::{self, NonNull};
+use std::sync::Arc;
+
+/// A fixed-length slice whose clones share unmodified pages with the OS.
+///
+/// The contents live in a sealed `memfd` (the base) that every clone maps
+/// `MAP_PRIVATE`: a clone only copies the pages its source modified since
+/// the base was written, and the kernel copies a page on first write. A
+/// [`write_cow`](crate::RetroCell::write_cow) of a huge, mostly unchanged
+/// table therefore physically copies only the pages touched by the writes
+/// since the base was taken.
+///
+/// Writes go through [`set`](Self::set) and [`slice_mut`](Self::slice_mut),
+/// which record the pages they touch. Modified pages accumulate from
+/// version to version; [`rebase`](Self::rebase) writes the contents into a
+/// fresh base once [`dirty_pages`](Self::dirty_pages) grows too large.
+///
+/// 克隆之间与操作系统共享未修改内存页的定长切片。
+///
+/// 内容位于一个已封印的 `memfd`（基底）中，每个克隆都以 `MAP_PRIVATE` 映射它：
+/// 克隆只复制其来源自基底写入以来修改过的页，内核则在首次写入时复制页。
+/// 因此对大部分未变的超大表执行 [`write_cow`](crate::RetroCell::write_cow) 时，
+/// 只会物理复制自基底生成以来被写入触及的页。
+///
+/// 写入通过 [`set`](Self::set) 与 [`slice_mut`](Self::slice_mut) 进行，它们会记录所触及的页。
+/// 修改过的页在版本之间累积；当 [`dirty_pages`](Self::dirty_pages) 过大时，
+/// [`rebase`](Self::rebase) 会将内容写入新的基底。
+pub struct CowPages<T: Copy> {
+    ptr: NonNull<T>,
+    len: usize,
+    // Size of the mapping in bytes (0 if nothing is mapped)
+    // 映射的字节大小（未映射时为 0）
+    mapped: usize,
+    // Never written once sealed, so private mappings never see it change
+    // 封印后不再写入，因此私有映射不会看到其变化
+    base: Option<Arc<OwnedFd>>,
+    // One bit per page modified since the base was written
+    // 自基底写入以来修改过的页，每页一位
+    dirty: Vec<u64>,
+}
+
+unsafe impl<T: Copy + Send> Send for CowPages<T> {}
+unsafe impl<T: Copy + Sync> Sync for CowPages<T> {}
+
+impl<T: Copy> CowPages<T> {
+    // Private mappings are page-aligned
+    // 私有映射按页对齐
+    const MAPPABLE: () = assert!(align_of::<T>() <= 4096);
+
+    /// Create a slice of `len` elements, all set to `value`
+    ///
+    /// 创建一个包含 `len` 个元素的切片，所有元素均设为 `value`
+    pub fn filled(len: usize, value: T) -> Self {
+        Self::with_base(len, |base| {
+            for i in 0..len {
+                unsafe { base.add(i).write(value) };
+            }
+        })
+    }
+
+    /// Create a slice holding a copy of `src`
+    ///
+    /// 创建一个保存 `src` 副本的切片
+    pub fn from_slice(src: &[T]) -> Self {
+        Self::with_base(src.len(), |base| unsafe {
+            ptr::copy_nonoverlapping(src.as_ptr(), base, src.len())
+        })
+    }
+
+    /// Set the element at `index`
+    ///
+    /// 设置 `index` 处的元素
+    #[inline]
+    pub fn set(&mut self, index: usize, value: T) {
+        self.slice_mut(index..index + 1)[0] = value;
+    }
+
+    /// Mutable access to the elements in `range`, recording the pages they
+    /// span as modified
+    ///
+    /// 以可变方式访问 `range` 中的元素，并将其所跨的页记录为已修改
+    pub fn slice_mut(&mut self, range: Range<usize>) -> &mut [T] {
+        assert!(
+            range.start <= range.end && range.end <= self.len,
+            "retro-cell: range {range:?} out of bounds for CowPages of length {}",
+            self.len,
+        );
+        if range.start < range.end {
+            let size = size_of::<T>();
+            let page = page_size();
+            for index in range.start * size / page..(range.end * size).div_ceil(page) {
+                self.dirty[index / 64] |= 1 << (index % 64);
+            }
+        }
+        unsafe {
+            std::slice::from_raw_parts_mut(self.ptr.as_ptr().add(range.start), range.len())
+        }
+    }
+
+    /// Number of pages modified since the base was written, i.e. copied by
+    /// each clone
+    ///
+    /// 自基底写入以来修改过的页数，即每次克隆需要复制的页数
+    pub fn dirty_pages(&self) -> usize {
+        self.dirty.iter().map(|bits| bits.count_ones() as usize).sum()
+    }
+
+    /// Write the contents into a fresh base, so clones stop copying the
+    /// pages modified so far. Costs one full copy.
+    ///
+    /// 将内容写入新的基底，使克隆不再复制迄今修改过的页。代价为一次完整复制。
+    pub fn rebase(&mut self) {
+        *self = Self::from_slice(self);
+    }
+
+    // Write the base through a shared mapping, seal it and map it privately
+    // 通过共享映射写入基底，封印后再以私有方式映射
+    fn with_base(len: usize, init: impl FnOnce(*mut T)) -> Self {
+        #[allow(clippy::let_unit_value)]
+        let () = Self::MAPPABLE;
+        let layout = match Layout::array::<T>(len) {
+            Ok(layout) => layout,
+            Err(_) => capacity_overflow(),
+        };
+        if layout.size() == 0 {
+            return Self::unmapped(len);
+        }
+        let size = layout.size().next_multiple_of(page_size());
+        let flags = libc::MFD_CLOEXEC | libc::MFD_ALLOW_SEALING;
+        let fd = unsafe { libc::memfd_create(c"retro-cell".as_ptr(), flags) };
+        if fd < 0 {
+            os_failure("memfd_create");
+        }
+        let fd = unsafe { OwnedFd::from_raw_fd(fd) };
+        if unsafe { libc::ftruncate(fd.as_raw_fd(), size as libc::off_t) } != 0 {
+            os_failure("ftruncate");
+        }
+        let shared = map(&fd, size, libc::MAP_SHARED, layout);
+        init(shared.cast());
+        unsafe { libc::munmap(shared, size) };
+        // Sealed against writes, so the base never changes under private mappings
+        // 封印以禁止写入，使基底在私有映射之下永不改变
+        let seals = libc::F_SEAL_SEAL | libc::F_SEAL_SHRINK | libc::F_SEAL_GROW | libc::F_SEAL_WRITE;
+        if unsafe { libc::fcntl(fd.as_raw_fd(), libc::F_ADD_SEALS, seals) } != 0 {
+            os_failure("sealing the memfd");
+        }
+        let private = map(&fd, size, libc::MAP_PRIVATE, layout);
+        Self {
+            ptr: unsafe { NonNull::new_unchecked(private.cast()) },
+            len,
+            mapped: size,
+            base: Some(Arc::new(fd)),
+            dirty: vec![0; (size / page_size()).div_ceil(64)],
+        }
+    }
+
+    // Zero-sized contents need no mapping
+    // 零大小的内容无需映射
+    fn unmapped(len: usize) -> Self {
+        Self {
+            ptr: NonNull::dangling(),
+            len,
+            mapped: 0,
+            base: None,
+            dirty: Vec::new(),
+        }
+    }
+}
+
+fn map(fd: &OwnedFd, size: usize, flags: libc::c_int, layout: Layout) -> *mut libc::c_void {
+    let addr = unsafe {
+        libc::mmap(
+            ptr::null_mut(),
+            size,
+            libc::PROT_READ | libc::PROT_WRITE,
+            flags,
+            fd.as_raw_fd(),
+            0,
+        )
+    };
+    if addr == libc::MAP_FAILED {
+        handle_alloc_error(layout);
+    }
+    addr
+}
+
+#[inline]
+fn page_size() -> usize {
+    unsafe { libc::sysconf(libc::_SC_PAGESIZE) as usize }
+}
+
+#[cold]
+fn capacity_overflow() -> ! {
+    panic!("retro-cell: CowPages capacity overflow")
+}
+
+#[cold]
+fn os_failure(what: &str) -> ! {
+    panic!("retro-cell: {what} failed: {}", io::Error::last_os_error())
+}
+
+impl<T: Copy> Deref for CowPages<T> {
+    type Target = [T];
+    #[inline]
+    fn deref(&self) -> &[T] {
+        unsafe { std::slice::from_raw_parts(self.ptr.as_ptr(), self.len) }
+    }
+}
+
+impl<T: Copy> Clone for CowPages<T> {
+    fn clone(&self) -> Self {
+        let Some(base) = &self.base else {
+            return Self::unmapped(self.len);
+        };
+        // Already known to fit: the source was mapped with it
+        // 已知有效：来源即以它映射
+        let layout = Layout::array::<T>(self.len).unwrap_or_else(|_| capacity_overflow());
+        let addr = map(base, self.mapped, libc::MAP_PRIVATE, layout);
+        // Unmodified pages come from the base; only the modified ones are copied
+        // 未修改的页来自基底；只复制修改过的页
+        let page = page_size();
+        for (word, bits) in self.dirty.iter().enumerate() {
+            let mut bits = *bits;
+            while bits != 0 {
+                let offset = (word * 64 + bits.trailing_zeros() as usize) * page;
+                unsafe {
+                    ptr::copy_nonoverlapping(
+                        self.ptr.as_ptr().cast::<u8>().add(offset),
+                        addr.cast::<u8>().add(offset),
+                        page,
+                    )
+                };
+                bits &= bits - 1;
+            }
+        }
+        Self {
+            ptr: unsafe { NonNull::new_unchecked(addr.cast()) },
+            len: self.len,
+            mapped: self.mapped,
+            base: Some(base.clone()),
+            dirty: self.dirty.clone(),
+        }
+    }
+}
+
+impl<T: Copy> Drop for CowPages<T> {
+    fn drop(&mut self) {
+        if self.mapped > 0 {
+            unsafe { libc::munmap(self.ptr.as_ptr().cast(), self.mapped) };
+        }
+    }
+}
+
+impl<T: Copy + fmt::Debug> fmt::Debug for CowPages<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
@@ -1,16 +1,40 @@
-use crate::rt::sync::Arc;
+#[cfg(feature = "coalesce")]
+use crate::coalesce::{self, NestedRef};
+use crate::clock::Instant;
+use crate::error::Error;
 use crate::rt::sync::atomic::Ordering;
-use crate::shared::{LOCKED, Node, PTR_MASK, SharedState, TAG_MASK};
-use crate::utils::Backoff;
+use crate::sync::{RefCount, WaitObserver, WaitPhase, observe};
+use crate::shared::{LOCKED, Node, PTR_MASK, Shared, SharedState, Stamp, TAG_MASK};
+use crate::utils::{Backoff, hot_path};
+use crate::version::Version;
+#[cfg(feature = "stats")]
+use crate::stats::{Counters, ReaderStats};
+use std::borrow::Cow;
+use std::fmt;
+use std::mem;
 use std::ops::Deref;
+use std::ptr;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize as StdAtomicUsize, Ordering as StdOrdering};
+use std::time::Duration;
 
 /// RAII guard for reading values
 ///
 /// 用于读取值的 RAII 守卫
 pub struct Ref<'a, T> {
     pub(crate) node: &'a Node<T>,
+    pub(crate) shared: &'a SharedState<T>,
+    // Obtained from the retro slot rather than as the current version
+    // 取自回溯槽位而非作为当前版本获得
+    retro: bool,
 }
 
+// The guard only hands out `&T` and releases through an atomic count, so it
+// can move to and be shared with other threads like `&T`
+// 守卫只提供 `&T` 并通过原子计数释放，因此可以像 `&T` 一样移动到其他线程或在线程间共享
+unsafe impl<'a, T: Send + Sync> Send for Ref<'a, T> {}
+unsafe impl<'a, T: Send + Sync> Sync for Ref<'a, T> {}
+
 impl<'a, T> Deref for Ref<'a, T> {
     type Target = T;
     #[inline(always)]
@@ -19,13 +43,316 @@ impl<'a, T> Deref for Ref<'a, T> {
     }
 }
 
+impl<'a, T: fmt::Debug> fmt::Debug for Ref<'a, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Ref")
+            .field("value", &**self)
+            .field("cell", &self.shared.id)
+            .finish()
+    }
+}
+
+// Process-wide number of outstanding leaked guards (diagnostics only)
+// 进程范围内未归还的泄漏守卫数量（仅用于诊断）
+static LEAKED_PINS: StdAtomicUsize = StdAtomicUsize::new(0);
+
+/// Number of guards currently leaked via [`Ref::leak`] across all cells
+///
+/// 所有单元中当前通过 [`Ref::leak`] 泄漏的守卫数量
+#[inline]
+pub fn leaked_pins() -> usize {
+    LEAKED_PINS.load(StdOrdering::Relaxed)
+}
+
+impl<'a, T> Ref<'a, T> {
+    /// Wrap a retained and validated node
+    ///
+    /// 包装一个已持有且已验证的节点
+    #[inline(always)]
+    pub(crate) fn new(shared: &'a SharedState<T>, node: &'a Node<T>) -> Self {
+        #[cfg(feature = "checksum")]
+        node.verify(&shared.hooks);
+        Self {
+            node,
+            shared,
+            retro: false,
+        }
+    }
+
+    #[inline(always)]
+    fn into_retro(mut self) -> Self {
+        self.retro = true;
+        self
+    }
+
+    /// Version of the value behind this guard
+    ///
+    /// 此守卫背后值的版本
+    #[inline]
+    pub fn version(this: &Self) -> Version {
+        this.stamp().version
+    }
+
+    /// Whether a newer version has been published since this guard's version.
+    ///
+    /// A single atomic load: long-running computations can poll it to abort
+    /// early and re-read. Guards on retro values are always stale.
+    ///
+    /// 自此守卫的版本以来是否已发布了更新的版本。
+    ///
+    /// 仅需一次原子加载：长时间运行的计算可以轮询它以提前中止并重新读取。
+    /// 回溯值上的守卫始终是过期的。
+    #[inline]
+    pub fn is_stale(this: &Self) -> bool {
+        // The node's version is stamped before the shared version is published,
+        // so a guard may briefly be ahead of it: that is not stale
+        // 节点版本在共享版本发布之前写入，因此守卫可能短暂领先于它：这不算过期
+        this.shared.version.load(Ordering::Acquire) > this.node.version().get()
+    }
+
+    /// Mint another guard on the same version, so one read can be handed to
+    /// several consumers without re-reading (and possibly observing a newer
+    /// version).
+    ///
+    /// This is an associated function, like `std::cell::Ref::clone`, so that
+    /// `guard.clone()` still clones the value.
+    ///
+    /// 在同一版本上创建另一个守卫，使一次读取可交给多个使用者而无需重新读取
+    /// （并可能观察到更新的版本）。
+    ///
+    /// 与 `std::cell::Ref::clone` 相同，这是一个关联函数，因此 `guard.clone()` 仍然克隆值。
+    #[inline]
+    #[allow(clippy::should_implement_trait)]
+    pub fn clone(this: &Self) -> Self {
+        // Already retained by `this`, so no validation is needed
+        // 已由 `this` 持有，因此无需验证
+        this.node.reader_count.retain();
+        let mut guard = Ref::new(this.shared, this.node);
+        guard.retro = this.retro;
+        guard
+    }
+
+    /// Clone the value and release the guard
+    ///
+    /// 克隆值并释放守卫
+    #[inline]
+    pub fn to_owned(this: Self) -> T
+    where
+        T: Clone,
+    {
+        T::clone(&this)
+    }
+
+    /// Borrow the value as a [`Cow`], for APIs that sometimes need owned data
+    ///
+    /// 以 [`Cow`] 借用值，供有时需要拥有所有权数据的 API 使用
+    #[inline]
+    pub fn as_cow(this: &Self) -> Cow<'_, T>
+    where
+        T: Clone,
+    {
+        Cow::Borrowed(&**this)
+    }
+
+    /// Whether both guards hold the same node, i.e. the same version of the
+    /// same cell (a node held by a guard can't be rewritten or reused), without
+    /// comparing the values
+    ///
+    /// 两个守卫是否持有同一节点，即同一单元的同一版本（被守卫持有的节点不会被改写或复用），
+    /// 无需比较值
+    #[inline]
+    pub fn ptr_eq(a: &Self, b: &Self) -> bool {
+        ptr::eq(a.node, b.node)
+    }
+
+    /// Address of the node behind this guard.
+    ///
+    /// Unique among nodes held at the same time, but nodes are reused once
+    /// released: to identify a version over time, pair it with
+    /// [`version`](Self::version).
+    ///
+    /// 此守卫背后节点的地址。
+    ///
+    /// 在同时被持有的节点之间唯一，但节点释放后会被复用：若要跨时间标识版本，
+    /// 请与 [`version`](Self::version) 配合使用。
+    #[inline]
+    pub fn node_id(this: &Self) -> usize {
+        this.node as *const Node<T> as usize
+    }
+
+    /// Whether this guard was obtained from the retro version (via
+    /// `read_retro` or a blocked-read fallback) rather than as the current one
+    ///
+    /// 此守卫是否取自回溯版本（通过 `read_retro` 或被阻塞读取的回退），而非作为当前版本获得
+    #[inline]
+    pub fn is_retro(this: &Self) -> bool {
+        this.retro
+    }
+
+    /// Whether this guard belongs to `reader`'s cell and still holds its
+    /// latest published version
+    ///
+    /// 此守卫是否属于 `reader` 的单元，且仍持有其最新已发布的版本
+    #[inline]
+    pub fn is_current(this: &Self, reader: &Reader<T>) -> bool {
+        ptr::eq(this.shared, &*reader.shared) && !Ref::is_stale(this)
+    }
+
+    #[inline]
+    pub(crate) fn stamp(&self) -> Stamp {
+        self.node.stamp()
+    }
+
+    /// Permanently pin the version behind this guard and return a `'static`
+    /// reference to it.
+    ///
+    /// The node is exempt from reclamation for the rest of the program, even
+    /// after the cell and all readers are dropped. It keeps counting as an
+    /// active reader, so in-place writes while it is the current version block
+    /// forever: publish with COW instead.
+    ///
+    /// In [double-buffer](crate::RetroCellBuilder::double_buffer) mode the same
+    /// holds once the leaked version becomes the retro one: every later COW
+    /// write waits for it to drain before reusing it, and so blocks forever.
+    /// Don't leak guards of a double-buffered cell.
+    ///
+    /// 永久固定此守卫背后的版本，并返回其 `'static` 引用。
+    ///
+    /// 该节点在程序剩余生命周期内免于回收，即使单元和所有读者都已析构。
+    /// 它始终被视为活跃读者，因此当其为当前版本时原地写入将永远阻塞：请改用 COW 发布。
+    ///
+    /// 在[双缓冲](crate::RetroCellBuilder::double_buffer)模式下，一旦被泄漏的版本成为回溯版本，
+    /// 情况相同：之后的每次 COW 写入都会等待其排空后再复用，因而永远阻塞。
+    /// 不要泄漏双缓冲单元的守卫。
+    #[inline]
+    pub fn leak(this: Self) -> &'static T
+    where
+        T: 'static,
+    {
+        LEAKED_PINS.fetch_add(1, StdOrdering::Relaxed);
+        let data = this.node.data.get();
+        mem::forget(this);
+        unsafe { &*data }
+    }
+}
+
 impl<'a, T> Drop for Ref<'a, T> {
     #[inline(always)]
     fn drop(&mut self) {
-        self.node.reader_count.release();
+        hot_path! { self.node.reader_count.release() }
+    }
+}
+
+impl<'a, T> Ref<'a, T> {
+    /// Narrow the guard to a part of the value, e.g. one field of a large
+    /// struct. The version stays retained until the returned guard is dropped.
+    ///
+    /// 将守卫收窄为值的一部分，例如大型结构体的某个字段。在返回的守卫析构前，该版本一直被持有。
+    #[inline]
+    pub fn map<U: ?Sized, F>(this: Self, f: F) -> MappedRef<'a, U>
+    where
+        F: FnOnce(&T) -> &U,
+    {
+        let node = this.node;
+        let version = Ref::version(&this);
+        // Project first: if `f` panics, `this` still releases the version
+        // 先投影：若 `f` panic，`this` 仍会释放该版本
+        let value = f(unsafe { &*node.data.get() });
+        mem::forget(this);
+        MappedRef {
+            value,
+            count: &node.reader_count,
+            version,
+        }
+    }
+}
+
+/// Read guard on a part of a value, returned by [`Ref::map`]
+///
+/// 值的一部分上的读取守卫，由 [`Ref::map`] 返回
+pub struct MappedRef<'a, U: ?Sized> {
+    value: &'a U,
+    count: &'a RefCount,
+    version: Version,
+}
+
+impl<'a, U: ?Sized> MappedRef<'a, U> {
+    /// Narrow the guard further
+    ///
+    /// 进一步收窄守卫
+    #[inline]
+    pub fn map<V: ?Sized, F>(this: Self, f: F) -> MappedRef<'a, V>
+    where
+        F: FnOnce(&U) -> &V,
+    {
+        let value = f(this.value);
+        let mapped = MappedRef {
+            value,
+            count: this.count,
+            version: this.version,
+        };
+        mem::forget(this);
+        mapped
+    }
+
+    /// Version of the value this part was projected from
+    ///
+    /// 此部分所投影自的值的版本
+    #[inline]
+    pub fn version(this: &Self) -> Version {
+        this.version
+    }
+}
+
+impl<'a, U: ?Sized> Deref for MappedRef<'a, U> {
+    type Target = U;
+    #[inline(always)]
+    fn deref(&self) -> &U {
+        self.value
+    }
+}
+
+impl<'a, U: ?Sized> Drop for MappedRef<'a, U> {
+    #[inline(always)]
+    fn drop(&mut self) {
+        hot_path! { self.count.release() }
+    }
+}
+
+impl<'a, U: ?Sized + fmt::Debug> fmt::Debug for MappedRef<'a, U> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MappedRef")
+            .field("value", &self.value)
+            .field("version", &self.version)
+            .finish()
     }
 }
 
+/// Metadata of a value obtained by [`Reader::read_versioned`]
+///
+/// 通过 [`Reader::read_versioned`] 获得的值的元数据
+#[derive(Clone, Copy, Debug)]
+#[non_exhaustive]
+pub struct ReadMeta {
+    /// Version of the value
+    ///
+    /// 值的版本
+    pub version: Version,
+    /// The current value was being written in place, so the retro value was
+    /// returned instead of waiting
+    ///
+    /// 当前值正在被原地写入，因此返回了回溯值而非等待
+    pub was_retro: bool,
+    /// When the value was published
+    ///
+    /// 值的发布时间
+    pub publish_time: Instant,
+}
+
+// Lock polls of a timed wait before it sleeps on the notifier
+// 带超时的等待在通知器上睡眠前轮询锁的次数
+const SPINS_BEFORE_PARK: u32 = 10;
+
 /// Result of a non-blocking read attempt
 ///
 /// 非阻塞读取尝试的结果
@@ -34,11 +361,92 @@ pub enum ReadResult<'a, T> {
     Blocked(BlockedReader<'a, T>),
 }
 
+impl<'a, T> ReadResult<'a, T> {
+    /// Convert into the guard, discarding the blocked state
+    ///
+    /// 转换为守卫，丢弃阻塞状态
+    #[inline]
+    pub fn ok(self) -> Option<Ref<'a, T>> {
+        match self {
+            ReadResult::Success(r) => Some(r),
+            ReadResult::Blocked(_) => None,
+        }
+    }
+
+    /// The guard, waiting for the in-place write if blocked
+    ///
+    /// 获取守卫，若被阻塞则等待原地写入完成
+    #[inline]
+    pub fn resolve(self) -> Ref<'a, T> {
+        match self {
+            ReadResult::Success(r) => r,
+            ReadResult::Blocked(blocked) => blocked.wait(),
+        }
+    }
+
+    /// The guard, or the retro value without waiting if blocked (`None` if
+    /// there is no retro value)
+    ///
+    /// 获取守卫；若被阻塞则不等待而取回溯值（没有回溯值时为 `None`）
+    #[inline]
+    pub fn resolve_or_retro(self) -> Option<Ref<'a, T>> {
+        match self {
+            ReadResult::Success(r) => Some(r),
+            ReadResult::Blocked(blocked) => blocked.read_retro(),
+        }
+    }
+
+    /// Convert into the blocked reader, discarding a successful guard
+    ///
+    /// 转换为被阻塞的读取者，丢弃成功获得的守卫
+    #[inline]
+    pub fn err(self) -> Option<BlockedReader<'a, T>> {
+        match self {
+            ReadResult::Success(_) => None,
+            ReadResult::Blocked(blocked) => Some(blocked),
+        }
+    }
+}
+
+impl<'a, T> From<ReadResult<'a, T>> for Result<Ref<'a, T>, BlockedReader<'a, T>> {
+    #[inline]
+    fn from(result: ReadResult<'a, T>) -> Self {
+        match result {
+            ReadResult::Success(r) => Ok(r),
+            ReadResult::Blocked(blocked) => Err(blocked),
+        }
+    }
+}
+
+/// What [`Reader::read_with_policy`] does when an in-place write blocks the
+/// current value
+///
+/// 当原地写入阻塞当前值时 [`Reader::read_with_policy`] 的行为
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ReadPolicy {
+    /// Wait for the write to finish and read the latest value
+    ///
+    /// 等待写入完成并读取最新值
+    #[default]
+    Latest,
+    /// Read the retro value instead, waiting only if there is none
+    ///
+    /// 改为读取回溯值，仅在没有回溯值时等待
+    RetroOk,
+    /// Never wait: hand the blocked reader back as the error
+    ///
+    /// 从不等待：将被阻塞的读取者作为错误返回
+    NonBlocking,
+}
+
 /// A reader that is blocked by a writer
 ///
 /// 被写入者阻塞的读取者
 pub struct BlockedReader<'a, T> {
     pub(crate) shared: &'a SharedState<T>,
+    #[cfg(feature = "stats")]
+    pub(crate) stats: &'a Counters,
+    pub(crate) observer: Option<&'a Arc<dyn WaitObserver>>,
 }
 
 impl<'a, T> BlockedReader<'a, T> {
@@ -46,87 +454,317 @@ impl<'a, T> BlockedReader<'a, T> {
     // Mark as cold path to optimize branch prediction
     // 标记为冷路径，优化分支预测
     pub fn wait(self) -> Ref<'a, T> {
-        let mut backoff = Backoff::new();
-        loop {
-            let mut val = self.shared.current.load(Ordering::Acquire);
+        #[cfg(feature = "stats")]
+        let start = Instant::now();
+        let guard = self.wait_unlocked();
+        #[cfg(feature = "stats")]
+        {
+            self.stats.waited(start.elapsed());
+            self.stats.read();
+        }
+        guard
+    }
+
+    /// Like [`wait`](Self::wait), but give up after `timeout` and hand the
+    /// blocked reader back, so the caller can retry, read the retro value or
+    /// report an error.
+    ///
+    /// After a short spin the reader sleeps until the writer unlocks or the
+    /// timeout expires.
+    ///
+    /// 与 [`wait`](Self::wait) 相同，但在 `timeout` 后放弃并交还被阻塞的读取者，
+    /// 使调用方可以重试、读取回溯值或报告错误。
+    ///
+    /// 短暂自旋后，读者会睡眠直到写入者解锁或超时。
+    ///
+    /// A timeout is reported through the cell's
+    /// [`FailurePolicy`](crate::FailurePolicy) as [`Error::TimedOut`].
+    ///
+    /// 超时会作为 [`Error::TimedOut`] 通过单元的
+    /// [`FailurePolicy`](crate::FailurePolicy) 报告。
+    #[cold]
+    pub fn wait_timeout(self, timeout: Duration) -> Result<Ref<'a, T>, Self> {
+        self.wait_for(timeout).map_err(Self::timed_out)
+    }
 
+    /// Wait up to `timeout` without reporting a timeout
+    ///
+    /// 最多等待 `timeout` 时长，不报告超时
+    fn wait_for(self, timeout: Duration) -> Result<Ref<'a, T>, Self> {
+        let start = Instant::now();
+        let mut backoff = Backoff::new();
+        let mut spins = 0;
+        observe(self.observer, WaitPhase::Spin);
+        let result = loop {
+            let val = self.shared.current.load(Ordering::Acquire);
             if (val & TAG_MASK) == 0 {
-                let ptr = (val & PTR_MASK) as *mut Node<T>;
-                let node = unsafe { &*ptr };
+                let node = unsafe { &*((val & PTR_MASK) as *mut Node<T>) };
                 node.reader_count.retain();
 
                 // Validate consistency
                 // 验证一致性
                 if self.shared.current.load(Ordering::Acquire) == val {
-                    return Ref { node };
+                    break Some(Ref::new(self.shared, node));
                 }
                 node.reader_count.release();
                 backoff.snooze();
                 continue;
             }
 
-            let ticket = self.shared.notifier.ticket();
-            val = self.shared.current.load(Ordering::Acquire);
+            let elapsed = start.elapsed();
+            if elapsed >= timeout {
+                break None;
+            }
+            // Spin briefly before sleeping
+            // 睡眠前短暂自旋
+            if spins < SPINS_BEFORE_PARK {
+                spins += 1;
+                backoff.snooze();
+                continue;
+            }
 
+            let ticket = self.shared.notifier.ticket();
             // If lock is released after getting ticket, retry immediately
             // 获取 ticket 后若锁释放，立即重试
-            if (val & TAG_MASK) == 0 {
+            if (self.shared.current.load(Ordering::Acquire) & TAG_MASK) == 0 {
                 continue;
             }
+            observe(self.observer, WaitPhase::Park);
+            self.shared.notifier.wait_ticket_timeout(ticket, timeout - elapsed);
+            observe(self.observer, WaitPhase::Wake);
+        };
+        #[cfg(feature = "stats")]
+        {
+            self.stats.waited(start.elapsed());
+            if result.is_some() {
+                self.stats.read();
+            }
+        }
+        result.ok_or(self)
+    }
+
+    /// Wait for the lock for at most `budget`, then fall back to the retro
+    /// value; the blocked reader is handed back (and the timeout reported
+    /// through the [`FailurePolicy`](crate::FailurePolicy)) only if there is
+    /// none
+    ///
+    /// 最多等待锁 `budget` 时长，然后回退到回溯值；仅在没有回溯值时交还被阻塞的读取者
+    /// （并通过 [`FailurePolicy`](crate::FailurePolicy) 报告超时）
+    #[cold]
+    pub fn wait_or_retro(self, budget: Duration) -> Result<Ref<'a, T>, Self> {
+        match self.wait_for(budget) {
+            Ok(guard) => Ok(guard),
+            Err(blocked) => match blocked.read_retro() {
+                Some(guard) => Ok(guard),
+                None => Err(blocked.timed_out()),
+            },
+        }
+    }
+
+    /// Report a timeout through the cell's failure policy
+    ///
+    /// 通过单元的失败策略报告超时
+    #[cold]
+    fn timed_out(self) -> Self {
+        self.shared.hooks.failure.apply(Error::TimedOut);
+        self
+    }
+
+    #[inline(always)]
+    fn wait_unlocked(&self) -> Ref<'a, T> {
+        hot_path! {
+            let mut backoff = Backoff::new();
+            observe(self.observer, WaitPhase::Spin);
+            loop {
+                let mut val = self.shared.current.load(Ordering::Acquire);
+
+                if (val & TAG_MASK) == 0 {
+                    let ptr = (val & PTR_MASK) as *mut Node<T>;
+                    let node = unsafe { &*ptr };
+                    node.reader_count.retain();
 
-            self.shared.notifier.wait_ticket(ticket);
+                    // Validate consistency
+                    // 验证一致性
+                    if self.shared.current.load(Ordering::Acquire) == val {
+                        return Ref::new(self.shared, node);
+                    }
+                    node.reader_count.release();
+                    observe(self.observer, WaitPhase::Spin);
+                    backoff.snooze();
+                    continue;
+                }
+
+                let ticket = self.shared.notifier.ticket();
+                val = self.shared.current.load(Ordering::Acquire);
+
+                // If lock is released after getting ticket, retry immediately
+                // 获取 ticket 后若锁释放，立即重试
+                if (val & TAG_MASK) == 0 {
+                    continue;
+                }
+
+                observe(self.observer, WaitPhase::Park);
+                self.shared.notifier.wait_ticket(ticket);
+                observe(self.observer, WaitPhase::Wake);
+            }
         }
     }
 
     #[inline]
     pub fn read_retro(&self) -> Option<Ref<'a, T>> {
-        let prev_ptr = self.shared.previous.load(Ordering::Acquire);
-        if prev_ptr.is_null() {
-            return None;
+        let retro = acquire_retro(self.shared);
+        #[cfg(feature = "stats")]
+        if retro.is_some() {
+            self.stats.retro_fallback();
+        }
+        retro
+    }
+}
+
+/// Retain the retro node, validating that it is still published
+///
+/// 持有回溯节点，并验证其仍处于发布状态
+#[inline]
+fn acquire_retro<T>(shared: &SharedState<T>) -> Option<Ref<'_, T>> {
+    hot_path! {
+        let mut backoff = Backoff::new();
+        loop {
+            let prev_ptr = shared.previous.load(Ordering::Acquire);
+            if prev_ptr.is_null() {
+                return None;
+            }
+            let node = unsafe { &*prev_ptr };
+            node.reader_count.retain();
+
+            // The writer may reclaim the retro node (double-buffer mode)
+            // 写入者可能会回收回溯节点（双缓冲模式）
+            if shared.previous.load(Ordering::SeqCst) == prev_ptr {
+                return Some(Ref::new(shared, node).into_retro());
+            }
+            node.reader_count.release();
+            backoff.snooze();
         }
-        let node = unsafe { &*prev_ptr };
-        node.reader_count.retain();
-        Some(Ref { node })
     }
 }
 
 /// Reader for accessing the data
 ///
 /// 用于访问数据的读取者
-#[derive(Clone)]
 pub struct Reader<T> {
-    pub(crate) shared: Arc<SharedState<T>>,
+    pub(crate) shared: Shared<T>,
+    // Statistics of this handle; clones start from zero
+    // 此句柄的统计信息；克隆从零开始
+    #[cfg(feature = "stats")]
+    stats: Counters,
+    // Shared with clones, like the cell itself
+    // 与克隆共享，与单元本身相同
+    observer: Option<Arc<dyn WaitObserver>>,
+}
+
+// Manual impl: cloning a reader never needs `T: Clone`
+// 手动实现：克隆读取者从不需要 `T: Clone`
+impl<T> Clone for Reader<T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        let mut reader = Self::new(self.shared.clone());
+        reader.observer = self.observer.clone();
+        reader
+    }
+}
+
+impl<T> Drop for Reader<T> {
+    #[inline]
+    fn drop(&mut self) {
+        self.shared.readers.fetch_sub(1, Ordering::Release);
+    }
 }
 
 impl<T> Reader<T> {
+    #[inline]
+    pub(crate) fn new(shared: Shared<T>) -> Self {
+        shared.readers.fetch_add(1, Ordering::Relaxed);
+        Self {
+            shared,
+            #[cfg(feature = "stats")]
+            stats: Counters::default(),
+            observer: None,
+        }
+    }
+
+    /// Create a fresh reader of the same cell. Unlike [`clone`](Clone::clone),
+    /// it has no wait observer.
+    ///
+    /// 创建同一单元的全新读取者。与 [`clone`](Clone::clone) 不同，它没有等待观察者。
+    #[inline]
+    pub fn new_reader(&self) -> Self {
+        Self::new(self.shared.clone())
+    }
+
+    /// Report the phases of this handle's blocked reads (spinning, parking,
+    /// waking) to `observer`. Clones made afterwards share it.
+    ///
+    /// 将此句柄被阻塞读取的各阶段（自旋、睡眠、唤醒）报告给 `observer`。之后创建的克隆共享该观察者。
+    #[inline]
+    pub fn set_wait_observer(&mut self, observer: impl WaitObserver + 'static) {
+        self.observer = Some(Arc::new(observer));
+    }
+
+    /// Stop reporting blocked reads
+    ///
+    /// 停止报告被阻塞的读取
+    #[inline]
+    pub fn clear_wait_observer(&mut self) {
+        self.observer = None;
+    }
+
+    /// Statistics of this reader handle (`stats` feature): successful and
+    /// blocked reads, retro fallbacks and time spent waiting
+    ///
+    /// 此读取句柄的统计信息（`stats` 特性）：成功与被阻塞的读取次数、回溯回退次数及等待时间
+    #[cfg(feature = "stats")]
+    #[inline]
+    pub fn local_stats(&self) -> ReaderStats {
+        self.stats.snapshot()
+    }
+
     /// Try to read the current value without blocking
     ///
     /// 尝试非阻塞地读取当前值
     pub fn try_read(&self) -> ReadResult<'_, T> {
-        let mut backoff = Backoff::new();
-        loop {
-            let curr_val = self.shared.current.load(Ordering::Acquire);
-            if (curr_val & TAG_MASK) == LOCKED {
-                return ReadResult::Blocked(BlockedReader {
-                    shared: &self.shared,
-                });
-            }
-            let ptr = (curr_val & PTR_MASK) as *mut Node<T>;
-            let node = unsafe { &*ptr };
+        hot_path! {
+            let mut backoff = Backoff::new();
+            loop {
+                let curr_val = self.shared.current.load(Ordering::Acquire);
 
-            // Optimistically increment reader count
-            // 乐观增加读者计数
-            node.reader_count.retain();
+                if (curr_val & TAG_MASK) == LOCKED {
+                    #[cfg(feature = "stats")]
+                    self.stats.blocked();
+                    return ReadResult::Blocked(BlockedReader {
+                        shared: &self.shared,
+                        #[cfg(feature = "stats")]
+                        stats: &self.stats,
+                        observer: self.observer.as_ref(),
+                    });
+                }
+                let ptr = (curr_val & PTR_MASK) as *mut Node<T>;
+                let node = unsafe { &*ptr };
 
-            // Verify if the pointer changed during the process
-            // 验证过程中指针是否发生变化
-            let val_now = self.shared.current.load(Ordering::Acquire);
-            if curr_val != val_now {
-                node.reader_count.release();
-                backoff.snooze();
-                continue;
+                // Optimistically increment reader count
+                // 乐观增加读者计数
+                node.reader_count.retain();
+
+                // Verify if the pointer changed during the process
+                // 验证过程中指针是否发生变化
+                let val_now = self.shared.current.load(Ordering::Acquire);
+                if curr_val != val_now {
+                    node.reader_count.release();
+                    backoff.snooze();
+                    continue;
+                }
+                #[cfg(feature = "stats")]
+                self.stats.read();
+                return ReadResult::Success(Ref::new(&self.shared, node));
             }
-            return ReadResult::Success(Ref { node });
         }
     }
 
@@ -141,17 +779,340 @@ impl<T> Reader<T> {
         }
     }
 
+    /// Read the latest data like [`read`](Self::read), sharing the retain of
+    /// any [`NestedRef`] this thread already holds on the same version
+    /// (`coalesce` feature).
+    ///
+    /// A nested read of a held version is free and never blocks, even while
+    /// the writer waits to lock it. The returned guard is `!Send`.
+    ///
+    /// 与 [`read`](Self::read) 相同地读取最新数据，但会共享当前线程在同一版本上已持有的
+    /// [`NestedRef`] 的计数（`coalesce` 特性）。
+    ///
+    /// 对已持有版本的嵌套读取无需开销且从不阻塞，即使写入者正在等待锁定它。返回的守卫不是 `Send`。
+    #[cfg(feature = "coalesce")]
+    #[inline]
+    pub fn read_nested(&self) -> NestedRef<'_, T> {
+        let curr_val = self.shared.current.load(Ordering::Acquire);
+        let node_ptr = (curr_val & PTR_MASK) as *const Node<T>;
+        // The shared retain keeps the node alive and unlocked
+        // 被共享的计数使节点保持存活且不会被锁定
+        if coalesce::reuse(node_ptr as usize) {
+            let node = unsafe { &*node_ptr };
+            #[cfg(feature = "checksum")]
+            node.verify(&self.shared.hooks);
+            #[cfg(feature = "stats")]
+            self.stats.read();
+            return NestedRef::new(node);
+        }
+        let guard = self.read();
+        let node = guard.node;
+        mem::forget(guard);
+        // The version changed to one this thread already holds: share that retain
+        // 版本变为当前线程已持有的版本：共享该计数
+        if coalesce::register(node as *const Node<T> as usize) {
+            node.reader_count.release();
+        }
+        NestedRef::new(node)
+    }
+
+    /// Run `f` on the latest value (block until available), releasing the
+    /// guard as soon as it returns, so it cannot outlive the call and keep
+    /// forcing the writer to copy
+    ///
+    /// 对最新值运行 `f`（阻塞直到可用），并在其返回后立即释放守卫，
+    /// 使守卫无法超出调用范围而持续迫使写入者复制
+    #[inline]
+    pub fn with<F, R>(&self, f: F) -> R
+    where
+        F: FnOnce(&T) -> R,
+    {
+        f(&self.read())
+    }
+
+    /// Copy the latest value out (block until available)
+    ///
+    /// 复制出最新值（阻塞直到可用）
+    #[inline]
+    pub fn get(&self) -> T
+    where
+        T: Copy,
+    {
+        *self.read()
+    }
+
+    /// Clone the latest data (block until available). The guard is held only
+    /// for the duration of the clone.
+    ///
+    /// 克隆最新数据（阻塞直到可用）。守卫仅在克隆期间持有。
+    #[inline]
+    pub fn read_cloned(&self) -> T
+    where
+        T: Clone,
+    {
+        T::clone(&self.read())
+    }
+
+    /// Like [`try_read`](Self::try_read), but wait up to `timeout` for an
+    /// in-place write before reporting [`ReadResult::Blocked`]
+    ///
+    /// 与 [`try_read`](Self::try_read) 相同，但在报告 [`ReadResult::Blocked`] 前
+    /// 最多等待原地写入 `timeout` 时长
+    ///
+    /// The timeout is reported as by [`BlockedReader::wait_timeout`].
+    ///
+    /// 超时的报告方式与 [`BlockedReader::wait_timeout`] 相同。
+    #[inline]
+    pub fn try_read_for(&self, timeout: Duration) -> ReadResult<'_, T> {
+        match self.try_read() {
+            ReadResult::Blocked(blocked) => match blocked.wait_timeout(timeout) {
+                Ok(r) => ReadResult::Success(r),
+                Err(blocked) => ReadResult::Blocked(blocked),
+            },
+            success => success,
+        }
+    }
+
+    /// Read with a hard bound on the number of steps: at most one attempt on
+    /// the current value, then at most one on the retro value.
+    ///
+    /// Never waits, spins or retries, so the latency is bounded regardless of
+    /// the writer (suitable for audio and other real-time threads). Returns
+    /// `None` only if the current value is locked or replaced during the
+    /// attempt and no retro value can be retained; reading a locked value
+    /// would race with the in-place write.
+    ///
+    /// 以硬性步数上限读取：最多尝试一次当前值，然后最多尝试一次回溯值。
+    ///
+    /// 从不等待、自旋或重试，因此无论写入者如何，延迟都有界（适用于音频等实时线程）。
+    /// 仅当当前值在尝试期间被锁定或替换，且无法持有回溯值时返回 `None`；
+    /// 读取被锁定的值会与原地写入发生数据竞争。
+    #[inline]
+    pub fn read_wait_free(&self) -> Option<Ref<'_, T>> {
+        hot_path! {
+            let shared = &*self.shared;
+            let curr_val = shared.current.load(Ordering::Acquire);
+            if (curr_val & TAG_MASK) != LOCKED {
+                let node = unsafe { &*((curr_val & PTR_MASK) as *mut Node<T>) };
+                node.reader_count.retain();
+                if shared.current.load(Ordering::Acquire) == curr_val {
+                    #[cfg(feature = "stats")]
+                    self.stats.read();
+                    return Some(Ref::new(shared, node));
+                }
+                node.reader_count.release();
+            }
+
+            let prev_ptr = shared.previous.load(Ordering::Acquire);
+            if prev_ptr.is_null() {
+                return None;
+            }
+            let node = unsafe { &*prev_ptr };
+            node.reader_count.retain();
+
+            // The writer may reclaim the retro node (double-buffer mode)
+            // 写入者可能会回收回溯节点（双缓冲模式）
+            if shared.previous.load(Ordering::SeqCst) != prev_ptr {
+                node.reader_count.release();
+                return None;
+            }
+            #[cfg(feature = "stats")]
+            self.stats.retro_fallback();
+            Some(Ref::new(shared, node).into_retro())
+        }
+    }
+
+    /// Clone the current value without blocking; `None` while a writer holds
+    /// the in-place lock
+    ///
+    /// 非阻塞地克隆当前值；写入者持有原地锁时返回 `None`
+    #[inline]
+    pub fn try_read_cloned(&self) -> Option<T>
+    where
+        T: Clone,
+    {
+        self.try_read().ok().map(|r| T::clone(&r))
+    }
+    /// Latest published version (COW writes and committed in-place writes),
+    /// without taking a guard
+    ///
+    /// 最新已发布的版本（COW 写入及已提交的原地写入），无需获取守卫
+    #[inline]
+    pub fn current_version(&self) -> Version {
+        Version::new(self.shared.version.load(Ordering::Acquire))
+    }
+
+    /// Whether a version was published since `since`, e.g. the version of the
+    /// last guard taken. A single atomic load: polling loops can skip taking
+    /// a guard (and touching the reader count) while nothing changed.
+    ///
+    /// 自 `since`（例如上次获取的守卫的版本）以来是否发布了新版本。仅需一次原子加载：
+    /// 轮询循环可在无变化时跳过获取守卫（以及操作读者计数）。
+    #[inline]
+    pub fn has_changed(&self, since: Version) -> bool {
+        self.current_version() != since
+    }
+
+    /// Whether an in-place write currently holds the lock, i.e. whether
+    /// [`try_read`](Self::try_read) would report [`ReadResult::Blocked`].
+    /// The answer may be outdated by the time it is used.
+    ///
+    /// 当前是否有原地写入持有锁，即 [`try_read`](Self::try_read) 是否会报告
+    /// [`ReadResult::Blocked`]。使用时该结果可能已经过时。
+    #[inline]
+    pub fn is_locked(&self) -> bool {
+        (self.shared.current.load(Ordering::Relaxed) & TAG_MASK) == LOCKED
+    }
+
+    /// Whether a retro version is published, i.e. whether
+    /// [`read_retro`](Self::read_retro) would currently find one
+    ///
+    /// 是否已发布回溯版本，即 [`read_retro`](Self::read_retro) 当前能否找到
+    #[inline]
+    pub fn has_retro(&self) -> bool {
+        !self.shared.previous.load(Ordering::Relaxed).is_null()
+    }
+
+    /// Backpressure signal maintained by the writer: how many of its last
+    /// 64 publications found the current version held by readers, forcing
+    /// it to copy or wait.
+    ///
+    /// Cooperative readers can shorten guard lifetimes or switch to
+    /// [`read_cloned`](Self::read_cloned) while it is high.
+    ///
+    /// 由写入者维护的背压信号：其最近 64 次发布中有多少次发现当前版本被读者持有，
+    /// 从而被迫复制或等待。
+    ///
+    /// 该值较高时，协作的读者可以缩短守卫的持有时间或改用 [`read_cloned`](Self::read_cloned)。
+    #[inline]
+    pub fn pressure(&self) -> u32 {
+        self.shared.pressure.load(Ordering::Relaxed)
+    }
+
     /// Read historical data (if available)
     ///
     /// 读取历史数据（如果有）
     #[inline]
     pub fn read_retro(&self) -> Option<Ref<'_, T>> {
-        let prev_ptr = self.shared.previous.load(Ordering::Acquire);
-        if prev_ptr.is_null() {
-            return None;
+        acquire_retro(&self.shared)
+    }
+
+    /// Read according to `policy` when an in-place write blocks the current
+    /// value; only [`ReadPolicy::NonBlocking`] can fail
+    ///
+    /// 当原地写入阻塞当前值时按 `policy` 读取；仅 [`ReadPolicy::NonBlocking`] 可能失败
+    #[inline]
+    pub fn read_with_policy(
+        &self,
+        policy: ReadPolicy,
+    ) -> Result<Ref<'_, T>, BlockedReader<'_, T>> {
+        let blocked = match self.try_read() {
+            ReadResult::Success(r) => return Ok(r),
+            ReadResult::Blocked(blocked) => blocked,
+        };
+        match policy {
+            ReadPolicy::Latest => Ok(blocked.wait()),
+            ReadPolicy::RetroOk => match blocked.read_retro() {
+                Some(retro) => Ok(retro),
+                None => Ok(blocked.wait()),
+            },
+            ReadPolicy::NonBlocking => Err(blocked),
+        }
+    }
+
+    /// Upgrade `guard` to the latest version if a newer one was published.
+    ///
+    /// The new version is retained before the old one is released, so there
+    /// is no window in which the value can change twice unnoticed. Returns
+    /// whether the guard was upgraded.
+    ///
+    /// 若已发布更新的版本，则将 `guard` 升级到最新版本。
+    ///
+    /// 先持有新版本再释放旧版本，因此不存在值可能在未察觉的情况下变化两次的窗口。
+    /// 返回守卫是否被升级。
+    pub fn refresh<'a>(&'a self, guard: &mut Ref<'a, T>) -> bool {
+        debug_assert!(
+            ptr::eq(guard.shared, &*self.shared),
+            "retro-cell: guard of cell {:?} refreshed through a reader of cell {:?}",
+            guard.shared.id,
+            self.shared.id,
+        );
+        if !Ref::is_stale(guard) {
+            return false;
+        }
+        // Never waits on the writer for our own node: an in-place write
+        // cannot publish over a node we still hold
+        // 不会因自身持有的节点而等待写入者：原地写入无法覆盖仍被持有的节点
+        *guard = self.read();
+        true
+    }
+
+    /// By-value form of [`refresh`](Self::refresh): return `guard` unchanged
+    /// if it still holds the latest version (a single atomic load), otherwise
+    /// a guard on the latest version
+    ///
+    /// [`refresh`](Self::refresh) 的按值形式：若 `guard` 仍持有最新版本（仅需一次原子加载）
+    /// 则原样返回，否则返回最新版本上的守卫
+    #[inline]
+    pub fn refreshed<'a>(&'a self, mut guard: Ref<'a, T>) -> Ref<'a, T> {
+        self.refresh(&mut guard);
+        guard
+    }
+
+    /// Read without waiting for in-place writes when a retro value exists,
+    /// together with the version, origin and publication time of the value.
+    ///
+    /// 在存在回溯值时无需等待原地写入地读取，并同时返回值的版本、来源及发布时间。
+    pub fn read_versioned(&self) -> (Ref<'_, T>, ReadMeta) {
+        let (guard, was_retro) = match self.try_read() {
+            ReadResult::Success(r) => (r, false),
+            ReadResult::Blocked(blocked) => match blocked.read_retro() {
+                Some(retro) => (retro, true),
+                None => (blocked.wait(), false),
+            },
+        };
+        let stamp = guard.stamp();
+        let meta = ReadMeta {
+            version: stamp.version,
+            was_retro,
+            publish_time: stamp.time,
+        };
+        (guard, meta)
+    }
+
+    /// Read the current value together with the version it replaced.
+    ///
+    /// Both guards belong to the same publication boundary: the retro value is
+    /// always the direct predecessor of the current one, so deltas computed
+    /// between them never straddle a concurrent write.
+    ///
+    /// 同时读取当前值及其所替换的版本。
+    ///
+    /// 两个守卫属于同一发布边界：回溯值始终是当前值的直接前驱，
+    /// 因此两者之间计算的差值不会跨越并发写入。
+    pub fn read_pair(&self) -> (Ref<'_, T>, Option<Ref<'_, T>>) {
+        let mut backoff = Backoff::new();
+        loop {
+            let current = self.read();
+            let retro = acquire_retro(&self.shared);
+
+            // The writer publishes 'previous' before 'current', and the held
+            // current node cannot be recycled. If 'current' is unchanged and
+            // differs from 'previous', no publication happened in between.
+            // 写入者先发布 'previous' 再发布 'current'，且持有的当前节点不会被回收。
+            // 若 'current' 未变且不同于 'previous'，则期间没有发生发布。
+            let curr_val = self.shared.current.load(Ordering::Acquire);
+            let curr_ptr = current.node as *const Node<T>;
+            let stable = (curr_val & PTR_MASK) == curr_ptr as usize
+                && retro
+                    .as_ref()
+                    .is_none_or(|r| !ptr::eq(r.node, current.node));
+            if stable {
+                return (current, retro);
+            }
+            drop(retro);
+            drop(current);
+            backoff.snooze();
         }
-        let node = unsafe { &*prev_ptr };
-        node.reader_count.retain();
-        Some(Ref { node })
     }
 }
@@ -0,0 +1,35 @@
+//! Refreshing retired values in place instead of cloning.
+//!
+//! 原地刷新已退役的值而非克隆。
+
+/// Types that can overwrite themselves with a copy of another value while
+/// reusing their own resources (allocations, arenas, buffers).
+///
+/// [`RetroCell::write_cow_recycled`](crate::RetroCell::write_cow_recycled)
+/// uses it to bring a pooled node up to date with the current value, instead
+/// of cloning into a fresh value and dropping the stale one.
+///
+/// 能够在复用自身资源（分配、arena、缓冲区）的同时用另一个值的副本覆盖自身的类型。
+///
+/// [`RetroCell::write_cow_recycled`](crate::RetroCell::write_cow_recycled)
+/// 使用它将池中的节点更新为当前值，而不是克隆出新值并丢弃过期值。
+pub trait Recycle {
+    /// Make `self` equal to `src`
+    ///
+    /// 使 `self` 等于 `src`
+    fn recycle(&mut self, src: &Self);
+}
+
+impl<T: Clone> Recycle for Vec<T> {
+    #[inline]
+    fn recycle(&mut self, src: &Self) {
+        self.clone_from(src);
+    }
+}
+
+impl Recycle for String {
+    #[inline]
+    fn recycle(&mut self, src: &Self) {
+        self.clone_from(src);
+    }
+}
@@ -0,0 +1,126 @@
+//! Fixed-capacity ring used as the writer's garbage queue.
+//!
+//! 用作写入者垃圾队列的固定容量环形缓冲区。
+
+use std::mem::MaybeUninit;
+
+/// Ring buffer of `Copy` items whose capacity is a power of two.
+///
+/// Storage is allocated once at construction; pushing only reallocates (on a
+/// cold path) when readers pin more retired nodes than the budget allows.
+///
+/// 容量为 2 的幂的 `Copy` 元素环形缓冲区。
+///
+/// 存储在构造时一次性分配；仅当读者占用的已退役节点超出预算时，
+/// 推入操作才会（在冷路径上）重新分配。
+pub(crate) struct Ring<P: Copy> {
+    slots: Box<[MaybeUninit<P>]>,
+    head: usize,
+    len: usize,
+}
+
+impl<P: Copy> Ring<P> {
+    /// Create a ring holding at least `capacity` items without reallocating
+    ///
+    /// 创建一个无需重新分配即可容纳至少 `capacity` 个元素的环
+    pub(crate) fn with_capacity(capacity: usize) -> Self {
+        let capacity = capacity.max(1).next_power_of_two();
+        Self {
+            slots: Box::new_uninit_slice(capacity),
+            head: 0,
+            len: 0,
+        }
+    }
+
+    #[inline(always)]
+    fn mask(&self) -> usize {
+        self.slots.len() - 1
+    }
+
+    #[inline(always)]
+    fn get(&self, offset: usize) -> P {
+        // Every offset below `len` has been written
+        // `len` 以下的每个偏移都已写入
+        unsafe { self.slots[(self.head + offset) & self.mask()].assume_init() }
+    }
+
+    #[inline(always)]
+    pub(crate) fn len(&self) -> usize {
+        self.len
+    }
+
+    #[inline(always)]
+    pub(crate) fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    #[inline]
+    pub(crate) fn back(&self) -> Option<P> {
+        (!self.is_empty()).then(|| self.get(self.len - 1))
+    }
+
+    #[inline]
+    pub(crate) fn push_back(&mut self, item: P) {
+        if self.len == self.slots.len() {
+            self.grow();
+        }
+        let index = (self.head + self.len) & self.mask();
+        self.slots[index] = MaybeUninit::new(item);
+        self.len += 1;
+    }
+
+    #[inline]
+    pub(crate) fn pop_back(&mut self) -> Option<P> {
+        let item = self.back()?;
+        self.len -= 1;
+        Some(item)
+    }
+
+    /// Keep only the items for which `keep` returns `true`, in order
+    ///
+    /// 按顺序仅保留 `keep` 返回 `true` 的元素
+    pub(crate) fn retain(&mut self, mut keep: impl FnMut(P) -> bool) {
+        let mask = self.mask();
+        let mut kept = 0;
+        for offset in 0..self.len {
+            let item = self.get(offset);
+            if keep(item) {
+                self.slots[(self.head + kept) & mask] = MaybeUninit::new(item);
+                kept += 1;
+            }
+        }
+        self.len = kept;
+    }
+
+    /// Iterate from oldest to newest
+    ///
+    /// 从最旧到最新迭代
+    pub(crate) fn iter(&self) -> impl Iterator<Item = P> + '_ {
+        (0..self.len).map(|offset| self.get(offset))
+    }
+
+    /// Remove every item, oldest first
+    ///
+    /// 移除所有元素，最旧的优先
+    pub(crate) fn drain(&mut self) -> impl Iterator<Item = P> + '_ {
+        let len = std::mem::take(&mut self.len);
+        let head = std::mem::take(&mut self.head);
+        let mask = self.mask();
+        let slots = &self.slots;
+        (0..len).map(move |offset| unsafe { slots[(head + offset) & mask].assume_init() })
+    }
+
+    /// Double the capacity, unrolling the items to the start
+    ///
+    /// 将容量加倍，并把元素展开到起始位置
+    #[cold]
+    #[inline(never)]
+    fn grow(&mut self) {
+        let mut slots = Box::new_uninit_slice(self.slots.len() * 2);
+        for (offset, slot) in slots.iter_mut().take(self.len).enumerate() {
+            *slot = MaybeUninit::new(self.get(offset));
+        }
+        self.slots = slots;
+        self.head = 0;
+    }
+}
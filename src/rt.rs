@@ -2,15 +2,13 @@
 pub(crate) use std::hint;
 #[cfg(not(feature = "loom"))]
 pub(crate) use std::sync;
-#[cfg(not(feature = "loom"))]
-pub(crate) use std::thread;
 
 #[cfg(feature = "loom")]
 pub(crate) use loom::hint;
 #[cfg(feature = "loom")]
 pub(crate) use loom::sync;
-#[cfg(feature = "loom")]
-pub(crate) use loom::thread;
+
+use std::time::Duration;
 
 #[cfg(not(feature = "loom"))]
 #[inline(always)]
@@ -18,6 +16,34 @@ pub(crate) fn wait(atomic: &sync::atomic::AtomicU32, expected: u32) {
     atomic_wait::wait(atomic, expected);
 }
 
+// Timed futex wait; spurious and early returns are allowed
+// 带超时的 futex 等待；允许虚假返回和提前返回
+#[cfg(all(not(feature = "loom"), target_os = "linux"))]
+pub(crate) fn wait_timeout(atomic: &sync::atomic::AtomicU32, expected: u32, timeout: Duration) {
+    let timeout = libc::timespec {
+        tv_sec: timeout.as_secs().min(libc::time_t::MAX as u64) as libc::time_t,
+        tv_nsec: timeout.subsec_nanos() as _,
+    };
+    unsafe {
+        libc::syscall(
+            libc::SYS_futex,
+            atomic,
+            libc::FUTEX_WAIT | libc::FUTEX_PRIVATE_FLAG,
+            expected,
+            &timeout,
+        );
+    }
+}
+
+// Without a portable timed futex, sleep in short slices instead
+// 没有可移植的带超时 futex 时，改为分段短暂睡眠
+#[cfg(all(not(feature = "loom"), not(target_os = "linux")))]
+pub(crate) fn wait_timeout(atomic: &sync::atomic::AtomicU32, expected: u32, timeout: Duration) {
+    if atomic.load(sync::atomic::Ordering::Acquire) == expected {
+        std::thread::sleep(timeout.min(Duration::from_millis(1)));
+    }
+}
+
 #[cfg(not(feature = "loom"))]
 #[inline(always)]
 pub(crate) fn wake_one(atomic: &sync::atomic::AtomicU32) {
@@ -30,10 +56,35 @@ pub(crate) fn wake_all(atomic: &sync::atomic::AtomicU32) {
     atomic_wait::wake_all(atomic);
 }
 
+#[cfg(not(feature = "loom"))]
+#[inline(always)]
+pub(crate) fn yield_now() {
+    // `std::thread::yield_now` never panics but is opaque to the optimizer;
+    // unwinding out of an `extern "C"` fn aborts, so this call is nounwind
+    // `std::thread::yield_now` 不会 panic，但对优化器不透明；
+    // 从 `extern "C"` 函数展开会中止，因此此调用不会展开
+    extern "C" fn yield_thread() {
+        std::thread::yield_now();
+    }
+    yield_thread();
+}
+
+#[cfg(feature = "loom")]
+#[inline(always)]
+pub(crate) fn yield_now() {
+    loom::thread::yield_now();
+}
+
 #[cfg(feature = "loom")]
 #[inline(always)]
 pub(crate) fn wait(_atomic: &sync::atomic::AtomicU32, _expected: u32) {
-    crate::rt::thread::yield_now();
+    yield_now();
+}
+
+#[cfg(feature = "loom")]
+#[inline(always)]
+pub(crate) fn wait_timeout(_atomic: &sync::atomic::AtomicU32, _expected: u32, _timeout: Duration) {
+    yield_now();
 }
 
 #[cfg(feature = "loom")]
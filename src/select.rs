@@ -0,0 +1,238 @@
+//! Waiting for the first of several cells to publish.
+//!
+//! 等待多个单元中第一个发布的单元。
+
+use crate::atomic::AtomicCell;
+use crate::reader::{Reader, Ref};
+use crate::version::Version;
+use std::future::poll_fn;
+use std::sync::Arc;
+use std::task::{Poll, Wake, Waker};
+use std::thread::{self, Thread};
+
+/// A cell handle that [`select`] can watch for publications
+///
+/// 可由 [`select`] 监视发布的单元句柄
+pub trait Waitable {
+    /// Latest published version
+    ///
+    /// 最新已发布的版本
+    fn published(&self) -> Version;
+
+    /// Wake `waker` on the next publication (spurious wakeups are allowed)
+    ///
+    /// 在下一次发布时唤醒 `waker`（允许虚假唤醒）
+    fn register(&self, waker: &Waker);
+
+    /// Stop waking `waker`, registered earlier with
+    /// [`register`](Self::register). The default keeps it registered until
+    /// the next publication.
+    ///
+    /// 停止唤醒之前通过 [`register`](Self::register) 注册的 `waker`。
+    /// 默认实现会保持其注册直到下一次发布。
+    #[inline]
+    fn deregister(&self, waker: &Waker) {
+        let _ = waker;
+    }
+}
+
+impl<T> Waitable for Reader<T> {
+    #[inline]
+    fn published(&self) -> Version {
+        self.current_version()
+    }
+
+    #[inline]
+    fn register(&self, waker: &Waker) {
+        self.shared.register_waker(waker);
+    }
+
+    #[inline]
+    fn deregister(&self, waker: &Waker) {
+        self.shared.deregister_waker(waker);
+    }
+}
+
+impl<T> Waitable for AtomicCell<T> {
+    #[inline]
+    fn published(&self) -> Version {
+        self.reader.published()
+    }
+
+    #[inline]
+    fn register(&self, waker: &Waker) {
+        self.reader.register(waker);
+    }
+
+    #[inline]
+    fn deregister(&self, waker: &Waker) {
+        self.reader.deregister(waker);
+    }
+}
+
+/// Block until one of `cells` publishes a new version, and return its index.
+///
+/// Versions are compared with those seen when the call starts; if several
+/// cells changed, the lowest index wins. Never returns if `cells` is empty.
+///
+/// 阻塞直到 `cells` 中的某个单元发布新版本，并返回其索引。
+///
+/// 版本与调用开始时所见的版本比较；若多个单元发生变化，返回最小的索引。
+/// 若 `cells` 为空则永不返回。
+pub fn select(cells: &[&dyn Waitable]) -> usize {
+    let seen = snapshot(cells);
+    with_thread_waker(|waker| {
+        loop {
+            if let Some(index) = changed(cells, &seen, waker) {
+                return index;
+            }
+            thread::park();
+        }
+    })
+}
+
+impl<T> Reader<T> {
+    /// Block until a version newer than `since` is published, then read it.
+    ///
+    /// Returns immediately if that already happened, so a loop passing the
+    /// version of its last guard never misses a publication. Returns `None`
+    /// once the cell is [frozen](Self::is_frozen) without a newer version.
+    ///
+    /// 阻塞直到发布了比 `since` 更新的版本，然后读取它。
+    ///
+    /// 若已发生则立即返回，因此传入上一个守卫版本的循环不会错过任何发布。
+    /// 若单元已[封存](Self::is_frozen)且没有更新的版本，则返回 `None`。
+    pub fn wait_for_change(&self, since: Version) -> Option<Ref<'_, T>> {
+        let ready = || {
+            if self.has_changed(since) {
+                Some(Some(self.read()))
+            } else if self.is_frozen() {
+                Some(None)
+            } else {
+                None
+            }
+        };
+        with_thread_waker(|waker| {
+            loop {
+                if let Some(result) = ready() {
+                    return result;
+                }
+                self.shared.register_waker(waker);
+                // A publication may have raced with the registration
+                // 发布可能与注册发生竞争
+                if let Some(result) = ready() {
+                    return result;
+                }
+                thread::park();
+            }
+        })
+    }
+}
+
+/// Run `f` with a waker that unparks the calling thread
+///
+/// 使用可唤醒调用线程的唤醒器运行 `f`
+fn with_thread_waker<R>(f: impl FnOnce(&Waker) -> R) -> R {
+    thread_local! {
+        // One waker per thread, so repeated waits on quiet cells register it
+        // only once
+        // 每个线程一个唤醒器，使对安静单元的重复等待只注册一次
+        static WAKER: Waker = Waker::from(Arc::new(Unpark(thread::current())));
+    }
+    WAKER.with(f)
+}
+
+/// Async version of [`select`]: complete when one of `cells` publishes a
+/// new version, with its index
+///
+/// [`select`] 的异步版本：当 `cells` 中的某个单元发布新版本时完成，并给出其索引
+pub async fn select_async(cells: &[&dyn Waitable]) -> usize {
+    let seen = snapshot(cells);
+    let mut registered = Registered { cells, waker: None };
+    poll_fn(|cx| match changed(cells, &seen, cx.waker()) {
+        Some(index) => Poll::Ready(index),
+        None => {
+            registered.set(cx.waker());
+            Poll::Pending
+        }
+    })
+    .await
+}
+
+/// The waker a [`select_async`] future left registered with its cells,
+/// deregistered when the future completes or is dropped
+///
+/// [`select_async`] future 在其单元上留下注册的唤醒器，在 future 完成或被丢弃时注销
+struct Registered<'a> {
+    cells: &'a [&'a dyn Waitable],
+    waker: Option<Waker>,
+}
+
+impl Registered<'_> {
+    /// Track `waker`, deregistering a previous one it replaces
+    ///
+    /// 跟踪 `waker`，并注销被其取代的旧唤醒器
+    fn set(&mut self, waker: &Waker) {
+        if let Some(previous) = &self.waker {
+            if previous.will_wake(waker) {
+                return;
+            }
+            self.deregister(previous);
+        }
+        self.waker = Some(waker.clone());
+    }
+
+    fn deregister(&self, waker: &Waker) {
+        for cell in self.cells {
+            cell.deregister(waker);
+        }
+    }
+}
+
+impl Drop for Registered<'_> {
+    fn drop(&mut self) {
+        if let Some(waker) = &self.waker {
+            self.deregister(waker);
+        }
+    }
+}
+
+fn snapshot(cells: &[&dyn Waitable]) -> Vec<Version> {
+    cells.iter().map(|cell| cell.published()).collect()
+}
+
+/// Index of the first cell past its `seen` version; otherwise register
+/// `waker` with every cell and check again
+///
+/// 第一个超过 `seen` 版本的单元的索引；否则向所有单元注册 `waker` 并再次检查
+fn changed(cells: &[&dyn Waitable], seen: &[Version], waker: &Waker) -> Option<usize> {
+    let first = || {
+        cells
+            .iter()
+            .zip(seen)
+            .position(|(cell, &version)| cell.published() != version)
+    };
+    if let Some(index) = first() {
+        return Some(index);
+    }
+    for cell in cells {
+        cell.register(waker);
+    }
+    // A publication may have raced with the registration
+    // 发布可能与注册发生竞争
+    first()
+}
+
+struct Unpark(Thread);
+
+impl Wake for Unpark {
+    #[inline]
+    fn wake(self: Arc<Self>) {
+        self.0.unpark();
+    }
+
+    #[inline]
+    fn wake_by_ref(self: &Arc<Self>) {
+        self.0.unpark();
+    }
+}
@@ -0,0 +1,41 @@
+//! Copy-on-write through a serde round-trip (`serde` feature).
+//!
+//! 通过 serde 往返实现的写时复制（`serde` 特性）。
+
+use crate::error::Error;
+use crate::writer::RetroCell;
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+
+impl<T: Serialize + DeserializeOwned> RetroCell<T> {
+    /// Perform a COW update on a copy produced by serializing the current
+    /// value and deserializing it again, for types that are not `Clone`
+    /// (e.g. holding handles rebuilt on deserialize).
+    ///
+    /// Nothing is published if the round-trip fails; the error goes through
+    /// the cell's [`FailurePolicy`](crate::FailurePolicy).
+    ///
+    /// 对通过序列化当前值再反序列化得到的副本执行 COW 更新，适用于未实现 `Clone` 的类型
+    /// （例如持有在反序列化时重建的句柄）。
+    ///
+    /// 往返失败时不会发布任何内容；错误经由单元的 [`FailurePolicy`](crate::FailurePolicy) 处理。
+    pub fn write_cow_serde<F, R>(&mut self, f: F) -> Result<R, Error>
+    where
+        F: FnOnce(&mut T) -> R,
+    {
+        let mut staged = match round_trip(self.current()) {
+            Ok(staged) => staged,
+            Err(err) => return Err(self.shared.hooks.failure.apply(err)),
+        };
+        let result = f(&mut staged);
+        self.collect_garbage();
+        self.congested = false;
+        self.install(staged);
+        Ok(result)
+    }
+}
+
+fn round_trip<T: Serialize + DeserializeOwned>(value: &T) -> Result<T, Error> {
+    let bytes = bincode::serialize(value).map_err(|err| Error::Serde(err.to_string()))?;
+    bincode::deserialize(&bytes).map_err(|err| Error::Serde(err.to_string()))
+}
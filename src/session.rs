@@ -0,0 +1,82 @@
+//! Read sessions amortizing the reader count over many accesses.
+//!
+//! 在多次访问间分摊读者计数开销的读取会话。
+
+use crate::reader::{Reader, Ref};
+use crate::version::Version;
+use std::fmt;
+
+impl<T> Reader<T> {
+    /// Start a session that retains one version and reuses it across
+    /// accesses until a newer one is published
+    ///
+    /// 开始一个会话：持有一个版本并在多次访问间复用，直到发布了更新的版本
+    #[inline]
+    pub fn session(&self) -> ReadSession<'_, T> {
+        ReadSession {
+            reader: self,
+            guard: self.read(),
+        }
+    }
+}
+
+/// Session returned by [`Reader::session`].
+///
+/// Each [`get`](Self::get) costs a single atomic load while the value is
+/// unchanged; the reader count is touched only when a newer version is
+/// picked up. The retained version forces in-place writes to copy, so end
+/// sessions once the hot loop is done.
+///
+/// 由 [`Reader::session`] 返回的会话。
+///
+/// 值未变化时每次 [`get`](Self::get) 仅需一次原子加载；仅在获取更新版本时才操作读者计数。
+/// 被持有的版本会迫使原地写入改为复制，因此热循环结束后应结束会话。
+pub struct ReadSession<'a, T> {
+    reader: &'a Reader<T>,
+    guard: Ref<'a, T>,
+}
+
+impl<'a, T> ReadSession<'a, T> {
+    /// The latest value, switching to a newer version only if one was
+    /// published since the previous access
+    ///
+    /// 最新值；仅当自上次访问以来发布了新版本时才切换到更新的版本
+    #[inline]
+    pub fn get(&mut self) -> &T {
+        self.reader.refresh(&mut self.guard);
+        &self.guard
+    }
+
+    /// The value of the previous access, without checking for a newer version
+    ///
+    /// 上次访问的值，不检查是否有更新的版本
+    #[inline]
+    pub fn cached(&self) -> &T {
+        &self.guard
+    }
+
+    /// Version of the value of the previous access
+    ///
+    /// 上次访问的值的版本
+    #[inline]
+    pub fn version(&self) -> Version {
+        Ref::version(&self.guard)
+    }
+
+    /// End the session, keeping the guard on the last version accessed
+    ///
+    /// 结束会话，保留最后访问版本上的守卫
+    #[inline]
+    pub fn into_ref(self) -> Ref<'a, T> {
+        self.guard
+    }
+}
+
+impl<'a, T: fmt::Debug> fmt::Debug for ReadSession<'a, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ReadSession")
+            .field("value", self.cached())
+            .field("version", &self.version())
+            .finish()
+    }
+}
@@ -1,31 +1,277 @@
-use crate::rt::sync::atomic::{AtomicPtr, AtomicUsize, Ordering};
-use crate::sync::{Notifier, RefCount};
+use crate::builder::FailurePolicy;
+use crate::clock::Instant;
+use crate::error::Error;
+use crate::hazard::Registry;
+use crate::rt::sync::Arc;
+use crate::rt::sync::atomic::{
+    AtomicBool, AtomicPtr, AtomicU32, AtomicU64, AtomicUsize, Ordering, fence,
+};
+use crate::sync::{Notifier, Notify, RefCount};
 use crate::utils::CachePadded;
+use crate::version::Version;
+use std::alloc::{self, Layout};
 use std::cell::UnsafeCell;
+use std::fmt;
+use std::marker::PhantomData;
+use std::mem;
+use std::ops::Deref;
+use std::ptr::NonNull;
+use std::sync::{OnceLock, PoisonError};
+use std::task::Waker;
 
 // === Constants ===
 pub(crate) const TAG_MASK: usize = 0b1;
 pub(crate) const PTR_MASK: usize = !TAG_MASK;
 pub(crate) const LOCKED: usize = 0b1;
 
+/// Publication metadata of a node, written only by the writer while no
+/// reader can observe the node
+///
+/// 节点的发布元数据，仅由写入者在没有读者能观察到该节点时写入
+#[derive(Clone, Copy)]
+pub(crate) struct Stamp {
+    pub(crate) version: Version,
+    pub(crate) time: Instant,
+}
+
+impl Stamp {
+    #[inline]
+    pub(crate) fn initial() -> Self {
+        Self {
+            version: Version::default(),
+            time: Instant::now(),
+        }
+    }
+}
+
 pub(crate) struct Node<T> {
     pub(crate) data: UnsafeCell<T>,
+    // Version of 'data', atomic so a dirty in-place write can advance it
+    // while readers hold the node
+    // 'data' 的版本，为原子类型，使脏原地写入可以在读者持有节点时推进它
+    version: AtomicU64,
+    // Publication time, written only while no reader can hold the node
+    // 发布时间，仅在没有读者能持有该节点时写入
+    time: UnsafeCell<Instant>,
 
+    // Padded so reader traffic doesn't false-share with the data; the
+    // `compact` feature drops the padding to shrink small nodes
+    // 填充以避免读者流量与数据发生伪共享；`compact` 特性去掉填充以缩小小节点
+    #[cfg(not(feature = "compact"))]
     pub(crate) reader_count: CachePadded<RefCount>,
+    #[cfg(feature = "compact")]
+    pub(crate) reader_count: RefCount,
+
+    // Checksum of 'data' as last published, written only by the writer
+    // (atomic for the same reason as 'version')
+    // 'data' 最近一次发布时的校验和，仅由写入者写入（与 'version' 同理为原子类型）
+    #[cfg(feature = "checksum")]
+    checksum: AtomicU64,
 }
 
 impl<T> Node<T> {
+    // The LOCKED tag lives in the low bit of node pointers
+    // LOCKED 标记位于节点指针的最低位
+    const TAGGABLE: () = assert!(align_of::<Node<T>>() > TAG_MASK);
+
     #[inline(always)]
-    pub(crate) fn new(data: T) -> Self {
+    pub(crate) fn new(data: T, stamp: Stamp) -> Self {
+        #[allow(clippy::let_unit_value)]
+        let () = Self::TAGGABLE;
         Self {
             data: UnsafeCell::new(data),
+            version: AtomicU64::new(stamp.version.get()),
+            time: UnsafeCell::new(stamp.time),
+            #[cfg(not(feature = "compact"))]
             reader_count: CachePadded {
                 value: RefCount::new(),
             },
+            #[cfg(feature = "compact")]
+            reader_count: RefCount::new(),
+            #[cfg(feature = "checksum")]
+            checksum: AtomicU64::new(0),
+        }
+    }
+
+    /// Publication metadata of the data
+    ///
+    /// 数据的发布元数据
+    #[inline(always)]
+    pub(crate) fn stamp(&self) -> Stamp {
+        Stamp {
+            version: self.version(),
+            time: unsafe { *self.time.get() },
+        }
+    }
+
+    /// Version of the data
+    ///
+    /// 数据的版本
+    #[inline(always)]
+    pub(crate) fn version(&self) -> Version {
+        Version::new(self.version.load(Ordering::Relaxed))
+    }
+
+    /// Writer only: restamp the node before publishing it.
+    ///
+    /// # Safety
+    ///
+    /// No reader may hold the node (drained, locked or unpublished).
+    ///
+    /// 仅供 Writer 使用：在发布节点前重新标记它。
+    ///
+    /// # 安全性
+    ///
+    /// 不得有读者持有该节点（已排空、已锁定或未发布）。
+    #[inline]
+    pub(crate) unsafe fn set_stamp(&self, stamp: Stamp) {
+        unsafe { *self.time.get() = stamp.time };
+        self.set_version(stamp.version);
+    }
+
+    /// Writer only: advance the version of a node that readers may hold,
+    /// keeping its publication time
+    ///
+    /// 仅供 Writer 使用：推进读者可能持有的节点的版本，保留其发布时间
+    #[inline]
+    pub(crate) fn set_version(&self, version: Version) {
+        // The lock release (or publication) orders it for new readers
+        // 锁的释放（或发布）为新读者保证其顺序
+        self.version.store(version.get(), Ordering::Relaxed);
+    }
+
+    /// Allocate a boxed node, reporting allocation failure instead of aborting
+    ///
+    /// 分配一个装箱节点，分配失败时返回错误而非中止
+    pub(crate) fn try_boxed(data: T, stamp: Stamp) -> Result<Box<Self>, Error> {
+        let layout = Layout::new::<Self>();
+        let ptr = unsafe { alloc::alloc(layout) } as *mut Self;
+        if ptr.is_null() {
+            return Err(Error::AllocFailed {
+                size: layout.size(),
+            });
+        }
+        unsafe {
+            ptr.write(Self::new(data, stamp));
+            Ok(Box::from_raw(ptr))
+        }
+    }
+
+    /// Record the checksum of the data about to be published (debug builds
+    /// only, like [`verify`](Self::verify))
+    ///
+    /// 记录即将发布的数据的校验和（与 [`verify`](Self::verify) 相同，仅限调试构建）
+    #[cfg(feature = "checksum")]
+    #[inline]
+    pub(crate) fn seal(&self, hooks: &Hooks<T>) {
+        if cfg!(debug_assertions)
+            && let Some(checksum) = hooks.checksum
+        {
+            let sum = checksum(unsafe { &*self.data.get() });
+            self.checksum.store(sum, Ordering::Relaxed);
+        }
+    }
+
+    /// Check the data against the checksum recorded at publication.
+    ///
+    /// Compiled out of release builds: the hasher is user code that would
+    /// otherwise run (and may panic) inside the `no-panic` hot paths.
+    ///
+    /// 将数据与发布时记录的校验和进行比对。
+    ///
+    /// 在 release 构建中被编译移除：哈希函数是用户代码，否则会在 `no-panic` 热路径中运行（且可能 panic）。
+    #[cfg(feature = "checksum")]
+    #[inline]
+    pub(crate) fn verify(&self, hooks: &Hooks<T>) {
+        if cfg!(debug_assertions)
+            && let Some(checksum) = hooks.checksum
+        {
+            let expected = self.checksum.load(Ordering::Relaxed);
+            let actual = checksum(unsafe { &*self.data.get() });
+            if actual != expected {
+                corrupted(hooks.failure, expected, actual);
+            }
+        }
+    }
+}
+
+/// Report a checksum mismatch. Reads have no error to return, so only a
+/// [`FailurePolicy::Hook`] lets the read go on; the other policies panic.
+///
+/// 报告校验和不匹配。读取没有可返回的错误，因此只有 [`FailurePolicy::Hook`]
+/// 允许读取继续；其他策略均 panic。
+#[cfg(feature = "checksum")]
+#[cold]
+#[inline(never)]
+fn corrupted(policy: FailurePolicy, expected: u64, actual: u64) {
+    let err = Error::Corrupted { expected, actual };
+    match policy {
+        FailurePolicy::Hook(hook) => hook(&err),
+        FailurePolicy::Panic | FailurePolicy::Error => panic!("retro-cell: {err}"),
+    }
+}
+
+/// Per-cell callbacks and failure policy, fixed at construction
+///
+/// 单元级回调与失败策略，在构造时确定
+pub(crate) struct Hooks<T> {
+    // Checksum of each published value, verified when a guard is created
+    // 每个已发布值的校验和，在创建守卫时验证
+    #[cfg(feature = "checksum")]
+    pub(crate) checksum: Option<fn(&T) -> u64>,
+    // Wake mechanism for blocked readers, moved into the notifier on construction
+    // 被阻塞读者的唤醒机制，构造时移入通知器
+    pub(crate) notify: Option<Box<dyn Notify>>,
+    // How failures (including timeouts and corruption) are reported
+    // 失败（包括超时与数据损坏）的报告方式
+    pub(crate) failure: FailurePolicy,
+    pub(crate) _marker: PhantomData<fn(&T)>,
+}
+
+impl<T> Default for Hooks<T> {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            #[cfg(feature = "checksum")]
+            checksum: None,
+            notify: None,
+            failure: FailurePolicy::default(),
+            _marker: PhantomData,
         }
     }
 }
 
+/// Identity of a cell, stamped on guards to diagnose misuse (debug builds
+/// only, zero-sized otherwise)
+///
+/// 单元的标识，标记在守卫上以诊断误用（仅调试构建，否则为零大小）
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub(crate) struct CellId {
+    #[cfg(debug_assertions)]
+    id: u64,
+}
+
+impl CellId {
+    #[inline]
+    pub(crate) fn next() -> Self {
+        #[cfg(debug_assertions)]
+        static NEXT: std::sync::atomic::AtomicU64 = std::sync::atomic::AtomicU64::new(1);
+        Self {
+            #[cfg(debug_assertions)]
+            id: NEXT.fetch_add(1, std::sync::atomic::Ordering::Relaxed),
+        }
+    }
+}
+
+impl fmt::Debug for CellId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        #[cfg(debug_assertions)]
+        return write!(f, "#{}", self.id);
+        #[cfg(not(debug_assertions))]
+        f.write_str("_")
+    }
+}
+
 /// Optimization: Separate 'current' and 'notifier' to prevent cache line thrashing
 ///
 /// 优化：分离 'current' 和 'notifier' 以防止缓存行抖动
@@ -39,20 +285,247 @@ pub(crate) struct SharedState<T> {
     // Cold: Accessed only by Retro Reader and Writer
     // Cold: 只有 Retro Reader 和 Writer 访问
     pub(crate) previous: AtomicPtr<Node<T>>,
+    // Cold: latest published version, read by staleness checks
+    // Cold: 最新已发布版本，供过期检查读取
+    pub(crate) version: AtomicU64,
+    // Cold: congested publications among the writer's last 64
+    // Cold: 写入者最近 64 次发布中遇到拥塞的次数
+    pub(crate) pressure: AtomicU32,
+    // Cold: set once the writer is gone (dropped or frozen)
+    // Cold: 写入者消失（析构或封存）后置位
+    pub(crate) frozen: AtomicBool,
+    // Cold: set once the writer was dropped without being frozen
+    // Cold: 写入者未经封存即被析构后置位
+    pub(crate) closed: AtomicBool,
+    // Cold: live `Reader` handles, for the writer's diagnostics
+    // Cold: 存活的 `Reader` 句柄数，供写入者诊断使用
+    pub(crate) readers: AtomicUsize,
+    // Cold: tasks and threads waiting for the next publication (signals,
+    // `select`), woken and cleared on every publication
+    // Cold: 等待下一次发布的任务与线程（信号、`select`），每次发布时唤醒并清空
+    pub(crate) wakers: std::sync::Mutex<Vec<std::task::Waker>>,
+    // Cold: whether `wakers` may be non-empty, so publications skip the mutex
+    // Cold: `wakers` 是否可能非空，使发布可以跳过互斥锁
+    pub(crate) has_wakers: AtomicBool,
+    // Retired nodes handed over by the writer on drop, freed with the state
+    // 写入者析构时移交的已退役节点，随共享状态一同释放
+    pub(crate) orphans: UnsafeCell<Vec<*mut Node<T>>>,
+    // Per-cell callbacks and failure policy
+    // 单元级回调与失败策略
+    pub(crate) hooks: Hooks<T>,
+    pub(crate) id: CellId,
+    // Cold: announcement slots of registered local readers
+    // Cold: 已注册本地读者的声明槽位
+    pub(crate) slots: Registry<T>,
 }
 
 unsafe impl<T: Send + Sync> Send for SharedState<T> {}
 unsafe impl<T: Send + Sync> Sync for SharedState<T> {}
 
+impl<T> SharedState<T> {
+    /// Writer only: whether no reader (counted or announced) holds `node`
+    ///
+    /// 仅供 Writer 使用：是否没有读者（计数或声明）持有 `node`
+    #[inline]
+    pub(crate) fn is_idle(&self, node: *mut Node<T>) -> bool {
+        unsafe { &*node }.reader_count.count() == 0 && !self.slots.protects(node)
+    }
+
+    /// Number of readers (counted or announced) holding `node`
+    ///
+    /// 持有 `node` 的读者数（计数或声明）
+    pub(crate) fn holders(&self, node: *mut Node<T>) -> usize {
+        unsafe { &*node }.reader_count.count() as usize + self.slots.announcing(node)
+    }
+
+    /// Writer only: wait until no reader holds `node`. New readers must
+    /// already be excluded (node locked or unpublished).
+    ///
+    /// 仅供 Writer 使用：等待直到没有读者持有 `node`。
+    /// 必须已阻止新读者进入（节点已锁定或已取消发布）。
+    #[inline]
+    pub(crate) fn wait_idle(&self, node: *mut Node<T>) {
+        unsafe { &*node }.reader_count.wait_until_zero();
+        self.slots.wait_clear(node);
+    }
+
+    /// Like [`wait_idle`](Self::wait_idle), but give up at `deadline`.
+    /// Returns whether the node drained.
+    ///
+    /// 与 [`wait_idle`](Self::wait_idle) 相同，但在 `deadline` 时放弃。返回节点是否已排空。
+    pub(crate) fn wait_idle_until(&self, node: *mut Node<T>, deadline: Instant) -> bool {
+        unsafe { &*node }
+            .reader_count
+            .wait_until_zero_until(deadline)
+            && self.slots.wait_clear_until(node, deadline)
+    }
+
+    /// Wake `waker` on the next publication. Callers must re-check the
+    /// version afterwards: a publication may have raced with the registration.
+    ///
+    /// 在下一次发布时唤醒 `waker`。调用者之后必须重新检查版本：发布可能与注册发生竞争。
+    pub(crate) fn register_waker(&self, waker: &Waker) {
+        let mut wakers = self.wakers.lock().unwrap_or_else(PoisonError::into_inner);
+        if !wakers.iter().any(|registered| registered.will_wake(waker)) {
+            wakers.push(waker.clone());
+        }
+        self.has_wakers.store(true, Ordering::Relaxed);
+        drop(wakers);
+        // Pairs with the fence in `wake_tasks`: either the writer sees the
+        // flag, or the caller's re-check sees the publication
+        // 与 `wake_tasks` 中的屏障配对：要么写入者看到标志，要么调用者的重新检查看到发布
+        fence(Ordering::SeqCst);
+    }
+
+    /// Stop waking `waker`, e.g. when the future that registered it is
+    /// dropped before completing
+    ///
+    /// 停止唤醒 `waker`，例如注册它的 future 在完成前被丢弃时
+    pub(crate) fn deregister_waker(&self, waker: &Waker) {
+        let mut wakers = self.wakers.lock().unwrap_or_else(PoisonError::into_inner);
+        wakers.retain(|registered| !registered.will_wake(waker));
+        if wakers.is_empty() {
+            self.has_wakers.store(false, Ordering::Relaxed);
+        }
+    }
+
+    /// Writer only: wake every registered task after a publication
+    ///
+    /// 仅供 Writer 使用：发布后唤醒所有已注册的任务
+    #[inline]
+    pub(crate) fn wake_tasks(&self) {
+        fence(Ordering::SeqCst);
+        if !self.has_wakers.load(Ordering::Relaxed) {
+            return;
+        }
+        let wakers = {
+            let mut wakers = self.wakers.lock().unwrap_or_else(PoisonError::into_inner);
+            self.has_wakers.store(false, Ordering::Relaxed);
+            mem::take(&mut *wakers)
+        };
+        for waker in wakers {
+            waker.wake();
+        }
+    }
+}
+
+/// Handle to the shared state: reference-counted, or borrowed from a
+/// [`SharedStorage`] that lives for the rest of the program
+///
+/// 共享状态的句柄：引用计数，或借用自存活至程序结束的 [`SharedStorage`]
+pub(crate) enum Shared<T> {
+    Counted(Arc<SharedState<T>>),
+    // Points into a `&'static SharedStorage<T>`; kept as a pointer so `T`
+    // needs no `'static` bound
+    // 指向 `&'static SharedStorage<T>`；以指针保存，使 `T` 无需 `'static` 约束
+    Static(NonNull<SharedState<T>>),
+}
+
+unsafe impl<T: Send + Sync> Send for Shared<T> {}
+unsafe impl<T: Send + Sync> Sync for Shared<T> {}
+
+impl<T> Shared<T> {
+    #[inline]
+    pub(crate) fn ptr_eq(this: &Self, other: &Self) -> bool {
+        std::ptr::eq(&**this, &**other)
+    }
+}
+
+impl<T> Clone for Shared<T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        match self {
+            Shared::Counted(arc) => Shared::Counted(arc.clone()),
+            Shared::Static(ptr) => Shared::Static(*ptr),
+        }
+    }
+}
+
+impl<T> Deref for Shared<T> {
+    type Target = SharedState<T>;
+    #[inline(always)]
+    fn deref(&self) -> &SharedState<T> {
+        match self {
+            Shared::Counted(arc) => arc,
+            Shared::Static(ptr) => unsafe { ptr.as_ref() },
+        }
+    }
+}
+
+/// Program-lifetime storage for the shared state of one cell, so that cells
+/// with a fixed topology can live in a `static` without any `Arc`.
+///
+/// Cloning a reader of such a cell is a pointer copy. The storage can hold
+/// only one cell, and the state (including the last versions) is never freed.
+///
+/// 单个单元共享状态的程序生命周期存储，使拓扑固定的单元无需任何 `Arc` 即可置于 `static` 中。
+///
+/// 克隆此类单元的读取者只是复制指针。该存储只能容纳一个单元，
+/// 且共享状态（包括最后的若干版本）永不释放。
+pub struct SharedStorage<T> {
+    state: OnceLock<SharedState<T>>,
+}
+
+impl<T> SharedStorage<T> {
+    /// Create empty storage
+    ///
+    /// 创建空存储
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            state: OnceLock::new(),
+        }
+    }
+
+    /// Move `state` into the storage; panics if it already holds a cell
+    ///
+    /// 将 `state` 移入存储；若已容纳单元则 panic
+    pub(crate) fn place(&'static self, state: SharedState<T>) -> Shared<T> {
+        if self.state.set(state).is_err() {
+            storage_in_use();
+        }
+        Shared::Static(NonNull::from(self.state.get().unwrap_or_else(|| storage_in_use())))
+    }
+}
+
+impl<T> Default for SharedStorage<T> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> fmt::Debug for SharedStorage<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SharedStorage")
+            .field("cell", &self.state.get().map(|state| state.id))
+            .finish()
+    }
+}
+
+#[cold]
+fn storage_in_use() -> ! {
+    panic!("retro-cell: SharedStorage already holds a cell")
+}
+
 impl<T> Drop for SharedState<T> {
     #[inline(always)]
     fn drop(&mut self) {
         let curr_val = self.current.load(Ordering::Relaxed);
         let curr_ptr = (curr_val & PTR_MASK) as *mut Node<T>;
-        if !curr_ptr.is_null() {
-            unsafe {
-                let _ = Box::from_raw(curr_ptr);
-            }
+        unsafe { free_unpinned(curr_ptr) };
+        for ptr in self.orphans.get_mut().drain(..) {
+            unsafe { free_unpinned(ptr) };
         }
     }
 }
+
+/// Free a node unless it is still pinned by a leaked guard
+///
+/// 释放节点，除非其仍被泄漏的守卫固定
+#[inline]
+unsafe fn free_unpinned<T>(ptr: *mut Node<T>) {
+    if !ptr.is_null() && unsafe { &*ptr }.reader_count.count() == 0 {
+        unsafe { drop(Box::from_raw(ptr)) };
+    }
+}
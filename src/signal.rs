@@ -0,0 +1,74 @@
+//! `futures_signals::signal::Signal` adapter (`futures-signals` feature).
+//!
+//! `futures_signals::signal::Signal` 适配器（`futures-signals` 特性）。
+
+use crate::reader::{Reader, Ref};
+use crate::rt::sync::atomic::Ordering;
+use futures_signals::signal::Signal;
+use std::fmt;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+impl<T: Clone> Reader<T> {
+    /// A [`Signal`] that emits a clone of the current value, then of the
+    /// latest value after each publication. Intermediate versions published
+    /// between two polls are skipped (latest wins).
+    ///
+    /// 一个 [`Signal`]，先发出当前值的克隆，之后每次发布后发出最新值的克隆。
+    /// 两次轮询之间发布的中间版本会被跳过（最新值优先）。
+    #[inline]
+    pub fn to_signal(&self) -> ReaderSignal<T> {
+        ReaderSignal {
+            reader: self.clone(),
+            emitted: None,
+        }
+    }
+}
+
+/// Signal returned by [`Reader::to_signal`]
+///
+/// [`Reader::to_signal`] 返回的信号
+pub struct ReaderSignal<T> {
+    reader: Reader<T>,
+    // Version of the last emitted value
+    // 最近发出的值的版本
+    emitted: Option<u64>,
+}
+
+impl<T: Clone> Signal for ReaderSignal<T> {
+    type Item = T;
+
+    fn poll_change(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<T>> {
+        let this = self.get_mut();
+        let shared = &*this.reader.shared;
+        if this.emitted != Some(shared.version.load(Ordering::Acquire)) {
+            return Poll::Ready(Some(this.emit()));
+        }
+
+        shared.register_waker(cx.waker());
+
+        // A publication may have raced with the registration
+        // 发布可能与注册发生竞争
+        if this.emitted != Some(shared.version.load(Ordering::Acquire)) {
+            return Poll::Ready(Some(this.emit()));
+        }
+        Poll::Pending
+    }
+}
+
+impl<T: Clone> ReaderSignal<T> {
+    fn emit(&mut self) -> T {
+        let guard = self.reader.read();
+        self.emitted = Some(Ref::version(&guard).get());
+        T::clone(&guard)
+    }
+}
+
+impl<T> fmt::Debug for ReaderSignal<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ReaderSignal")
+            .field("cell", &self.reader.shared.id)
+            .field("emitted", &self.emitted)
+            .finish()
+    }
+}
@@ -0,0 +1,144 @@
+//! Single-value handoff cell built on [`RetroCell<Option<T>>`](RetroCell).
+//!
+//! 基于 [`RetroCell<Option<T>>`](RetroCell) 的单值交接单元。
+
+use crate::reader::{Reader, Ref};
+use crate::rt::sync::{Condvar, Mutex, MutexGuard};
+use crate::writer::RetroCell;
+use std::fmt;
+use std::sync::PoisonError;
+
+/// Producer/consumer handoff slot that keeps the last taken value readable.
+///
+/// [`put`](Self::put) fills the slot and [`take`](Self::take) empties it.
+/// Writes are serialized by a mutex; peeking through
+/// [`reader`](Self::reader)s stays lock-free, and [`last_taken`](Self::last_taken)
+/// (the retro version) returns the value consumed most recently.
+///
+/// 保持最近取走的值可读的生产者/消费者交接槽。
+///
+/// [`put`](Self::put) 填充槽位，[`take`](Self::take) 清空槽位。写入由互斥锁串行化；
+/// 通过 [`reader`](Self::reader) 查看仍然是无锁的，[`last_taken`](Self::last_taken)
+/// （即回溯版本）返回最近被消费的值。
+pub struct RetroSlot<T> {
+    cell: Mutex<RetroCell<Option<T>>>,
+    filled: Condvar,
+    reader: Reader<Option<T>>,
+}
+
+impl<T> RetroSlot<T> {
+    /// Create an empty slot
+    ///
+    /// 创建一个空槽位
+    pub fn new() -> Self {
+        let (cell, reader) = RetroCell::new(None);
+        Self {
+            cell: Mutex::new(cell),
+            filled: Condvar::new(),
+            reader,
+        }
+    }
+
+    /// Fill the slot, returning the value it replaces if it was never taken.
+    ///
+    /// The value is written in place, waiting for readers peeking at the
+    /// current value to drain, so the last taken value stays readable.
+    ///
+    /// 填充槽位；若被替换的值从未被取走则将其返回。
+    ///
+    /// 值以原地方式写入，会等待正在查看当前值的读者排空，因此最近取走的值保持可读。
+    pub fn put(&self, value: T) -> Option<T> {
+        let mut cell = self.lock();
+        let replaced = cell.write_in_place().replace(value);
+        self.filled.notify_one();
+        replaced
+    }
+
+    /// Take the value if the slot is filled, without blocking
+    ///
+    /// 若槽位已填充则取走值，不阻塞
+    pub fn take_if_present(&self) -> Option<T>
+    where
+        T: Clone,
+    {
+        let mut cell = self.lock();
+        if cell.current().is_none() {
+            return None;
+        }
+        Self::empty(&mut cell)
+    }
+
+    /// Take the value, blocking until the slot is filled
+    ///
+    /// 取走值，阻塞直到槽位被填充
+    pub fn take(&self) -> T
+    where
+        T: Clone,
+    {
+        let mut cell = self.lock();
+        loop {
+            if cell.current().is_some()
+                && let Some(value) = Self::empty(&mut cell)
+            {
+                return value;
+            }
+            cell = self
+                .filled
+                .wait(cell)
+                .unwrap_or_else(PoisonError::into_inner);
+        }
+    }
+
+    /// Peek at the value waiting in the slot
+    ///
+    /// 查看槽位中等待的值
+    #[inline]
+    pub fn peek(&self) -> Ref<'_, Option<T>> {
+        self.reader.read()
+    }
+
+    /// The value taken most recently, if any
+    ///
+    /// 最近取走的值（如果有）
+    #[inline]
+    pub fn last_taken(&self) -> Option<Ref<'_, Option<T>>> {
+        self.reader.read_retro()
+    }
+
+    /// A lock-free reader of the slot: `read` peeks at the waiting value,
+    /// `read_retro` yields the last taken one
+    ///
+    /// 槽位的无锁读取者：`read` 查看等待中的值，`read_retro` 返回最近取走的值
+    #[inline]
+    pub fn reader(&self) -> Reader<Option<T>> {
+        self.reader.clone()
+    }
+
+    // Publish the emptied slot by COW, leaving the taken value as the retro version
+    // 通过 COW 发布清空后的槽位，使取走的值成为回溯版本
+    fn empty(cell: &mut RetroCell<Option<T>>) -> Option<T>
+    where
+        T: Clone,
+    {
+        cell.write_cow(Option::take)
+    }
+
+    fn lock(&self) -> MutexGuard<'_, RetroCell<Option<T>>> {
+        self.cell.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl<T> Default for RetroSlot<T> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for RetroSlot<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RetroSlot")
+            .field("value", &*self.peek())
+            .finish()
+    }
+}
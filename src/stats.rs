@@ -0,0 +1,74 @@
+//! Per-reader statistics (`stats` feature).
+//!
+//! 每个读取者的统计信息（`stats` 特性）。
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+/// Snapshot of the statistics of one [`Reader`](crate::Reader) handle,
+/// returned by [`Reader::local_stats`](crate::Reader::local_stats)
+///
+/// 单个 [`Reader`](crate::Reader) 句柄的统计快照，由
+/// [`Reader::local_stats`](crate::Reader::local_stats) 返回
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct ReaderStats {
+    /// Guards obtained on the current value
+    ///
+    /// 在当前值上获得的守卫数
+    pub reads: u64,
+    /// Reads that found the current value locked by an in-place write
+    ///
+    /// 发现当前值被原地写入锁定的读取次数
+    pub blocked: u64,
+    /// Blocked reads served from the retro value instead of waiting
+    ///
+    /// 以回溯值代替等待的被阻塞读取次数
+    pub retro_fallbacks: u64,
+    /// Total time spent waiting for in-place writes
+    ///
+    /// 等待原地写入所花费的总时间
+    pub wait_time: Duration,
+}
+
+// Plain std atomics: statistics are not part of the modeled protocol
+// 使用普通的 std 原子类型：统计信息不属于被建模的协议
+#[derive(Debug, Default)]
+pub(crate) struct Counters {
+    reads: AtomicU64,
+    blocked: AtomicU64,
+    retro_fallbacks: AtomicU64,
+    wait_nanos: AtomicU64,
+}
+
+impl Counters {
+    #[inline(always)]
+    pub(crate) fn read(&self) {
+        self.reads.fetch_add(1, Ordering::Relaxed);
+    }
+
+    #[inline(always)]
+    pub(crate) fn blocked(&self) {
+        self.blocked.fetch_add(1, Ordering::Relaxed);
+    }
+
+    #[inline(always)]
+    pub(crate) fn retro_fallback(&self) {
+        self.retro_fallbacks.fetch_add(1, Ordering::Relaxed);
+    }
+
+    #[inline]
+    pub(crate) fn waited(&self, time: Duration) {
+        let nanos = u64::try_from(time.as_nanos()).unwrap_or(u64::MAX);
+        self.wait_nanos.fetch_add(nanos, Ordering::Relaxed);
+    }
+
+    pub(crate) fn snapshot(&self) -> ReaderStats {
+        ReaderStats {
+            reads: self.reads.load(Ordering::Relaxed),
+            blocked: self.blocked.load(Ordering::Relaxed),
+            retro_fallbacks: self.retro_fallbacks.load(Ordering::Relaxed),
+            wait_time: Duration::from_nanos(self.wait_nanos.load(Ordering::Relaxed)),
+        }
+    }
+}
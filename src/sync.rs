@@ -1,5 +1,9 @@
+use crate::clock::Instant;
 use crate::rt::hint::spin_loop;
 use crate::rt::sync::atomic::{AtomicU32, Ordering};
+use std::sync::atomic::AtomicU32 as StdAtomicU32;
+use crate::utils::hot_path;
+use std::time::Duration;
 
 /// === RefCount ===
 /// Reference counting with writer waiting support.
@@ -39,6 +43,10 @@ impl RefCount {
     #[inline(always)]
     pub(crate) fn release(&self) {
         let prev = self.state.fetch_sub(1, Ordering::Release);
+        debug_assert!(
+            prev & COUNT_MASK != 0,
+            "retro-cell: reader released more often than retained"
+        );
 
         // If this was the last reader and a writer is waiting, wake it up
         // 若这是最后一个读者且有 Writer 在等待，则唤醒它
@@ -51,60 +59,111 @@ impl RefCount {
     // 仅供 Writer 使用：等待所有读者退出
     #[inline(never)]
     pub(crate) fn wait_until_zero(&self) {
+        hot_path! {
+            let mut spin_count = 0;
+            loop {
+                let val = self.state.load(Ordering::Acquire);
+                // Fast path: no readers
+                // 快速路径：无读者
+                if (val & COUNT_MASK) == 0 {
+                    return;
+                }
+
+                // Set WAITING bit if not already set
+                // 若未设置 WAITING 位，则尝试设置
+                if (val & WAITING_BIT) == 0 {
+                    // Try CAS: val -> val | WAITING_BIT
+                    // 尝试 CAS: val -> val | WAITING_BIT
+                    if self
+                        .state
+                        .compare_exchange_weak(
+                            val,
+                            val | WAITING_BIT,
+                            Ordering::Relaxed, // CAS failure is fine, just retry // CAS 失败无妨，重试即可
+                            Ordering::Relaxed,
+                        )
+                        .is_err()
+                    {
+                        continue;
+                    }
+                }
+
+                // Re-check in case readers exited while setting the bit
+                // 二次检查，防止设置位时读者已退出
+                let val_now = self.state.load(Ordering::Acquire);
+                if (val_now & COUNT_MASK) == 0 {
+                    return;
+                }
+
+                // Spin briefly before sleeping
+                // 睡眠前短暂自旋
+                if spin_count < 20 {
+                    spin_loop();
+                    spin_count += 1;
+                    continue;
+                }
+
+                // Sleep and wait for wakeup
+                // 睡眠等待唤醒
+                crate::rt::wait(&self.state, val_now | WAITING_BIT);
+            }
+        }
+    }
+
+    // Writer only: like wait_until_zero, but give up at `deadline`. Returns
+    // whether the readers drained. Not a hot path: reading the clock may panic.
+    // 仅供 Writer 使用：与 wait_until_zero 相同，但在 `deadline` 时放弃。返回读者是否已排空。
+    // 不是热路径：读取时钟可能 panic。
+    pub(crate) fn wait_until_zero_until(&self, deadline: Instant) -> bool {
         let mut spin_count = 0;
         loop {
             let val = self.state.load(Ordering::Acquire);
-            // Fast path: no readers
-            // 快速路径：无读者
             if (val & COUNT_MASK) == 0 {
-                return;
+                return true;
             }
 
-            // Set WAITING bit if not already set
-            // 若未设置 WAITING 位，则尝试设置
-            if (val & WAITING_BIT) == 0 {
-                // Try CAS: val -> val | WAITING_BIT
-                // 尝试 CAS: val -> val | WAITING_BIT
-                if self
+            // Spin briefly before sleeping
+            // 睡眠前短暂自旋
+            if spin_count < 20 {
+                spin_loop();
+                spin_count += 1;
+                continue;
+            }
+
+            // Set WAITING bit so the last reader wakes us
+            // 设置 WAITING 位，使最后一个读者唤醒我们
+            if (val & WAITING_BIT) == 0
+                && self
                     .state
                     .compare_exchange_weak(
                         val,
                         val | WAITING_BIT,
-                        Ordering::Relaxed, // CAS failure is fine, just retry // CAS 失败无妨，重试即可
+                        Ordering::Relaxed,
                         Ordering::Relaxed,
                     )
                     .is_err()
-                {
-                    continue;
-                }
-            }
-
-            // Re-check in case readers exited while setting the bit
-            // 二次检查，防止设置位时读者已退出
-            let val_now = self.state.load(Ordering::Acquire);
-            if (val_now & COUNT_MASK) == 0 {
-                return;
-            }
-
-            // Spin briefly before sleeping
-            // 睡眠前短暂自旋
-            if spin_count < 20 {
-                spin_loop();
-                spin_count += 1;
+            {
                 continue;
             }
 
-            // Sleep and wait for wakeup
-            // 睡眠等待唤醒
-            crate::rt::wait(&self.state, val_now | WAITING_BIT);
+            // Sleep until woken by the last reader or the deadline passes
+            // 睡眠直到被最后一个读者唤醒或截止时间已过
+            let now = Instant::now();
+            if now >= deadline {
+                return false;
+            }
+            crate::rt::wait_timeout(&self.state, val | WAITING_BIT, deadline.duration_since(now));
         }
     }
 
-    // Reset state for node reuse
-    // 重置状态以复用节点
+    // Reset state for node reuse.
+    // Only the WAITING bit is cleared: a racing reader may still hold a
+    // transient retain that it is about to release after failing validation.
+    // 重置状态以复用节点。
+    // 仅清除 WAITING 位：竞争中的读者可能仍持有临时计数，验证失败后会释放。
     #[inline(always)]
     pub(crate) fn reset(&self) {
-        self.state.store(0, Ordering::Relaxed);
+        self.state.fetch_and(COUNT_MASK, Ordering::Relaxed);
     }
 
     #[inline(always)]
@@ -120,23 +179,184 @@ impl RefCount {
     }
 }
 
+/// Wake mechanism used by readers blocked on an in-place write.
+///
+/// By default blocked readers sleep on a futex. A cell built with
+/// [`RetroCellBuilder::notify`](crate::RetroCellBuilder::notify) uses this
+/// trait instead, e.g. to signal a job system or a custom eventcount. Only
+/// blocked readers go through it; the writer's own waits stay on futexes.
+///
+/// Implementations must not panic: they are called through `extern "C"`
+/// shims, so a panic aborts the process.
+///
+/// 被原地写入阻塞的读者所使用的唤醒机制。
+///
+/// 默认情况下被阻塞的读者在 futex 上睡眠。使用
+/// [`RetroCellBuilder::notify`](crate::RetroCellBuilder::notify) 构建的单元改用此 trait，
+/// 例如向作业系统或自定义 eventcount 发出信号。只有被阻塞的读者经过它；写入者自身的等待仍使用 futex。
+///
+/// 实现不得 panic：它们通过 `extern "C"` 垫片调用，因此 panic 会中止进程。
+pub trait Notify: Send + Sync {
+    /// Block while `word` holds `expected`. Spurious returns are allowed.
+    ///
+    /// 当 `word` 的值为 `expected` 时阻塞。允许虚假返回。
+    fn wait(&self, word: &StdAtomicU32, expected: u32);
+
+    /// Wake every thread blocked in [`wait`](Self::wait) on `word`
+    ///
+    /// 唤醒所有在 `word` 上阻塞于 [`wait`](Self::wait) 的线程
+    fn wake_all(&self, word: &StdAtomicU32);
+
+    /// Like [`wait`](Self::wait), but return after at most `timeout`. Used by
+    /// timed reads such as
+    /// [`BlockedReader::wait_timeout`](crate::BlockedReader::wait_timeout).
+    ///
+    /// The default implementation sleeps for at most a millisecond, so timed
+    /// reads re-check the lock at that granularity.
+    ///
+    /// 与 [`wait`](Self::wait) 相同，但最多在 `timeout` 后返回。用于带超时的读取，例如
+    /// [`BlockedReader::wait_timeout`](crate::BlockedReader::wait_timeout)。
+    ///
+    /// 默认实现最多睡眠一毫秒，因此带超时的读取以该粒度重新检查锁。
+    fn wait_timeout(&self, word: &StdAtomicU32, expected: u32, timeout: Duration) {
+        if word.load(Ordering::Acquire) == expected {
+            std::thread::sleep(timeout.min(Duration::from_millis(1)));
+        }
+    }
+}
+
+// Lets the embedder keep a handle to a mechanism shared with other cells
+// 使嵌入方可以保留与其他单元共享的唤醒机制的句柄
+impl<N: Notify + ?Sized> Notify for std::sync::Arc<N> {
+    #[inline]
+    fn wait(&self, word: &StdAtomicU32, expected: u32) {
+        (**self).wait(word, expected);
+    }
+
+    #[inline]
+    fn wake_all(&self, word: &StdAtomicU32) {
+        (**self).wake_all(word);
+    }
+
+    #[inline]
+    fn wait_timeout(&self, word: &StdAtomicU32, expected: u32, timeout: Duration) {
+        (**self).wait_timeout(word, expected, timeout);
+    }
+}
+
+/// Progress callbacks for a reader blocked on an in-place write, set with
+/// [`Reader::set_wait_observer`](crate::Reader::set_wait_observer).
+///
+/// Each method is called on the blocked thread, so tracing tools can
+/// timestamp the phases of one wait. All methods default to no-ops.
+/// Implementations must not panic: they are called through `extern "C"`
+/// shims, so a panic aborts the process.
+///
+/// 被原地写入阻塞的读者的进度回调，通过
+/// [`Reader::set_wait_observer`](crate::Reader::set_wait_observer) 设置。
+///
+/// 每个方法都在被阻塞的线程上调用，因此追踪工具可以为一次等待的各阶段打时间戳。
+/// 所有方法默认为空操作。实现不得 panic：它们通过 `extern "C"` 垫片调用，因此 panic 会中止进程。
+pub trait WaitObserver: Send + Sync {
+    /// The reader starts polling the lock: on entering the wait and each time
+    /// it backs off after losing a race with the writer
+    ///
+    /// 读者开始轮询锁：进入等待时，以及每次与写入者竞争失败而退避时
+    fn on_spin_phase(&self) {}
+
+    /// The reader is about to sleep until the writer publishes
+    ///
+    /// 读者即将睡眠，直到写入者发布
+    fn on_park(&self) {}
+
+    /// The reader returned from sleep and resumes polling
+    ///
+    /// 读者从睡眠中返回并恢复轮询
+    fn on_wake(&self) {}
+}
+
+impl<O: WaitObserver + ?Sized> WaitObserver for std::sync::Arc<O> {
+    #[inline]
+    fn on_spin_phase(&self) {
+        (**self).on_spin_phase();
+    }
+
+    #[inline]
+    fn on_park(&self) {
+        (**self).on_park();
+    }
+
+    #[inline]
+    fn on_wake(&self) {
+        (**self).on_wake();
+    }
+}
+
+/// Phase of a blocked read reported to a [`WaitObserver`]
+///
+/// 报告给 [`WaitObserver`] 的阻塞读取阶段
+#[derive(Clone, Copy)]
+#[repr(u8)]
+pub(crate) enum WaitPhase {
+    Spin,
+    Park,
+    Wake,
+}
+
+/// Report `phase` to the observer, if any
+///
+/// 若存在观察者，则向其报告 `phase`
+#[inline(always)]
+pub(crate) fn observe(observer: Option<&std::sync::Arc<dyn WaitObserver>>, phase: WaitPhase) {
+    // Same nounwind shim as the custom notifier
+    // 与自定义通知器相同的不展开垫片
+    extern "C" fn notify(observer: &std::sync::Arc<dyn WaitObserver>, phase: WaitPhase) {
+        match phase {
+            WaitPhase::Spin => observer.on_spin_phase(),
+            WaitPhase::Park => observer.on_park(),
+            WaitPhase::Wake => observer.on_wake(),
+        }
+    }
+    if let Some(observer) = observer {
+        notify(observer, phase);
+    }
+}
+
 /// === Ticket Notifier ===
 /// Ticket-based notifier for global lock waiting.
 ///
 /// === Ticket Notifier ===
 /// 用于全局锁等待的票据通知器。
-#[derive(Debug)]
 pub(crate) struct Notifier {
     inner: AtomicU32,
+    custom: Option<Box<dyn Notify>>,
+    // Loom's atomics can't be lent to a `Notify`, so it gets a std mirror of
+    // the ticket, stored after each advance
+    // loom 的原子类型无法借给 `Notify`，因此它获得票据的 std 镜像，在每次推进后写入
+    #[cfg(feature = "loom")]
+    word: StdAtomicU32,
 }
 
 impl Notifier {
-    pub fn new() -> Self {
+    pub fn new(custom: Option<Box<dyn Notify>>) -> Self {
         Self {
             inner: AtomicU32::new(0),
+            custom,
+            #[cfg(feature = "loom")]
+            word: StdAtomicU32::new(0),
         }
     }
 
+    // The word a custom `Notify` waits on
+    // 自定义 `Notify` 所等待的字
+    #[inline(always)]
+    fn word(&self) -> &StdAtomicU32 {
+        #[cfg(not(feature = "loom"))]
+        return &self.inner;
+        #[cfg(feature = "loom")]
+        return &self.word;
+    }
+
     #[inline(always)]
     pub fn ticket(&self) -> u32 {
         self.inner.load(Ordering::Acquire)
@@ -144,19 +364,57 @@ impl Notifier {
 
     #[inline(always)]
     pub fn wait_ticket(&self, expected: u32) {
-        crate::rt::wait(&self.inner, expected);
+        // User code is opaque to the optimizer; unwinding out of an
+        // `extern "C"` fn aborts, so these calls are nounwind
+        // 用户代码对优化器不透明；从 `extern "C"` 函数展开会中止，因此这些调用不会展开
+        extern "C" fn custom_wait(notifier: &Notifier, expected: u32) {
+            if let Some(notify) = &notifier.custom {
+                notify.wait(notifier.word(), expected);
+            }
+        }
+        if self.custom.is_some() {
+            custom_wait(self, expected);
+        } else {
+            crate::rt::wait(&self.inner, expected);
+        }
+    }
+
+    // Like wait_ticket, but return after at most `timeout`
+    // 与 wait_ticket 相同，但最多在 `timeout` 后返回
+    pub fn wait_ticket_timeout(&self, expected: u32, timeout: Duration) {
+        extern "C" fn custom_wait(notifier: &Notifier, expected: u32, timeout: &Duration) {
+            if let Some(notify) = &notifier.custom {
+                notify.wait_timeout(notifier.word(), expected, *timeout);
+            }
+        }
+        if self.custom.is_some() {
+            custom_wait(self, expected, &timeout);
+        } else {
+            crate::rt::wait_timeout(&self.inner, expected, timeout);
+        }
     }
 
     #[inline(always)]
     pub fn advance_and_wake(&self) {
         // Release ordering ensures memory visibility to woken threads
         // Release 序确保内存修改对唤醒线程可见
-        self.inner.fetch_add(1, Ordering::Release);
+        let _ticket = self.inner.fetch_add(1, Ordering::Release);
+        #[cfg(feature = "loom")]
+        self.word.store(_ticket.wrapping_add(1), Ordering::Release);
         self.wake_all();
     }
 
     #[inline(always)]
     fn wake_all(&self) {
-        crate::rt::wake_all(&self.inner);
+        extern "C" fn custom_wake_all(notifier: &Notifier) {
+            if let Some(notify) = &notifier.custom {
+                notify.wake_all(notifier.word());
+            }
+        }
+        if self.custom.is_some() {
+            custom_wake_all(self);
+        } else {
+            crate::rt::wake_all(&self.inner);
+        }
     }
 }
@@ -0,0 +1,152 @@
+//! Recoverable ownership of a cell's writer.
+//!
+//! 可恢复的单元写入者所有权。
+
+use crate::error::Error;
+use crate::writer::RetroCell;
+use std::fmt;
+use std::ops::{Deref, DerefMut};
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError, TryLockError};
+
+/// Shared, transferable right to write to a cell.
+///
+/// Every clone of the token can check the writer out, one thread at a time.
+/// If a thread panics while holding it, the writer is not lost: the token
+/// reports [`Error::Poisoned`] until a supervisor inspects the value and calls
+/// [`clear_poison`](Self::clear_poison), after which any thread can take over.
+///
+/// 共享且可转移的单元写入权。
+///
+/// 令牌的每个克隆都可以签出写入者，每次仅限一个线程。若某线程在持有时 panic，
+/// 写入者不会丢失：令牌会报告 [`Error::Poisoned`]，直到监督者检查值并调用
+/// [`clear_poison`](Self::clear_poison)，之后任何线程都可以接管。
+pub struct WriterToken<T> {
+    writer: Arc<Mutex<RetroCell<T>>>,
+}
+
+impl<T> WriterToken<T> {
+    /// Hand the writer over to a new token
+    ///
+    /// 将写入者移交给新令牌
+    #[inline]
+    pub fn new(cell: RetroCell<T>) -> Self {
+        Self {
+            writer: Arc::new(Mutex::new(cell)),
+        }
+    }
+
+    /// Check the writer out, blocking while another thread holds it
+    ///
+    /// 签出写入者，其他线程持有时阻塞
+    pub fn acquire(&self) -> Result<WriterGuard<'_, T>, Error> {
+        Self::checked(self.writer.lock().map_err(|_| Error::Poisoned)?)
+    }
+
+    /// Check the writer out if no other thread holds it
+    ///
+    /// 若没有其他线程持有，则签出写入者
+    pub fn try_acquire(&self) -> Option<Result<WriterGuard<'_, T>, Error>> {
+        match self.writer.try_lock() {
+            Ok(guard) => Some(Self::checked(guard)),
+            Err(TryLockError::Poisoned(_)) => Some(Err(Error::Poisoned)),
+            Err(TryLockError::WouldBlock) => None,
+        }
+    }
+
+    /// Whether a holder panicked and the poison has not been cleared yet
+    /// (`false` while another thread holds the writer)
+    ///
+    /// 是否有持有者 panic 且尚未清除中毒状态（其他线程持有写入者时为 `false`）
+    pub fn is_poisoned(&self) -> bool {
+        match self.writer.try_lock() {
+            Ok(cell) => cell.is_poisoned(),
+            Err(TryLockError::Poisoned(_)) => true,
+            Err(TryLockError::WouldBlock) => false,
+        }
+    }
+
+    /// Check the writer out even if poisoned, to inspect or repair the value
+    /// before clearing the poison
+    ///
+    /// 即使已中毒也签出写入者，以便在清除中毒状态前检查或修复值
+    pub fn recover(&self) -> WriterGuard<'_, T> {
+        WriterGuard {
+            cell: self.lock_any(),
+        }
+    }
+
+    /// Declare the value consistent again, so the writer can be acquired
+    ///
+    /// 声明值已恢复一致，使写入者可再次被签出
+    pub fn clear_poison(&self) {
+        self.lock_any().clear_poison();
+        self.writer.clear_poison();
+    }
+
+    /// Take the writer back if this is the last token
+    ///
+    /// 若这是最后一个令牌，则取回写入者
+    pub fn into_inner(self) -> Option<RetroCell<T>> {
+        let mutex = Arc::into_inner(self.writer)?;
+        Some(mutex.into_inner().unwrap_or_else(PoisonError::into_inner))
+    }
+
+    fn checked(cell: MutexGuard<'_, RetroCell<T>>) -> Result<WriterGuard<'_, T>, Error> {
+        if cell.is_poisoned() {
+            return Err(Error::Poisoned);
+        }
+        Ok(WriterGuard { cell })
+    }
+
+    fn lock_any(&self) -> MutexGuard<'_, RetroCell<T>> {
+        self.writer.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl<T> Clone for WriterToken<T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            writer: self.writer.clone(),
+        }
+    }
+}
+
+impl<T> fmt::Debug for WriterToken<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WriterToken")
+            .field("poisoned", &self.writer.is_poisoned())
+            .finish()
+    }
+}
+
+/// The writer checked out of a [`WriterToken`]
+///
+/// 从 [`WriterToken`] 签出的写入者
+pub struct WriterGuard<'a, T> {
+    cell: MutexGuard<'a, RetroCell<T>>,
+}
+
+impl<'a, T> Deref for WriterGuard<'a, T> {
+    type Target = RetroCell<T>;
+    #[inline]
+    fn deref(&self) -> &RetroCell<T> {
+        &self.cell
+    }
+}
+
+impl<'a, T> DerefMut for WriterGuard<'a, T> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut RetroCell<T> {
+        &mut self.cell
+    }
+}
+
+impl<'a, T> fmt::Debug for WriterGuard<'a, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WriterGuard")
+            .field("cell", &self.cell.shared.id)
+            .field("version", &self.cell.version())
+            .finish()
+    }
+}
@@ -0,0 +1,117 @@
+//! Update queue feeding the writer from many producers.
+//!
+//! 由多个生产者向写入者提供更新的队列。
+
+use crate::error::Error;
+use crate::writer::RetroCell;
+use std::fmt;
+use std::mem;
+use std::sync::{Arc, Mutex, PoisonError, Weak};
+
+type Update<T> = Box<dyn FnOnce(&mut T) + Send>;
+
+/// Updates enqueued by [`Updater`]s, owned by the writer
+///
+/// 由 [`Updater`] 入队、由写入者拥有的更新
+pub(crate) type Pending<T> = Arc<Mutex<Vec<Update<T>>>>;
+
+impl<T> RetroCell<T> {
+    /// Create a handle that other threads can use to enqueue updates, applied
+    /// by the writer with [`apply_pending`](Self::apply_pending)
+    ///
+    /// 创建一个句柄，其他线程可以用它将更新入队，由写入者通过
+    /// [`apply_pending`](Self::apply_pending) 应用
+    pub fn updater(&mut self) -> Updater<T> {
+        let pending = self.pending.get_or_insert_with(Pending::default);
+        Updater {
+            pending: Arc::downgrade(pending),
+        }
+    }
+
+    /// Apply every update enqueued so far, in order, as a single version.
+    /// Returns the number of updates applied; nothing is published if the
+    /// queue was empty.
+    ///
+    /// 按顺序将目前已入队的所有更新作为单个版本应用。返回已应用的更新数；若队列为空则不发布任何内容。
+    pub fn apply_pending(&mut self) -> usize
+    where
+        T: Clone,
+    {
+        let Some(pending) = &self.pending else {
+            return 0;
+        };
+        let updates = mem::take(&mut *pending.lock().unwrap_or_else(PoisonError::into_inner));
+        let count = updates.len();
+        if count > 0 {
+            self.write_batch(updates);
+        }
+        count
+    }
+}
+
+/// Cloneable handle enqueueing updates for a cell's writer, created with
+/// [`RetroCell::updater`].
+///
+/// Enqueueing takes a short mutex and never waits for the writer. Nothing is
+/// visible to readers until the writer calls
+/// [`apply_pending`](RetroCell::apply_pending).
+///
+/// 为单元写入者将更新入队的可克隆句柄，由 [`RetroCell::updater`] 创建。
+///
+/// 入队只需短暂持有互斥锁，从不等待写入者。在写入者调用
+/// [`apply_pending`](RetroCell::apply_pending) 之前，读者看不到任何更新。
+pub struct Updater<T> {
+    pending: Weak<Mutex<Vec<Update<T>>>>,
+}
+
+impl<T> Updater<T> {
+    /// Enqueue `f`, failing with [`Error::Closed`] once the writer was dropped
+    ///
+    /// 将 `f` 入队；若写入者已被丢弃，则以 [`Error::Closed`] 失败
+    pub fn update<F>(&self, f: F) -> Result<(), Error>
+    where
+        F: FnOnce(&mut T) + Send + 'static,
+    {
+        let pending = self.pending.upgrade().ok_or(Error::Closed)?;
+        pending
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .push(Box::new(f));
+        Ok(())
+    }
+
+    /// Enqueue replacing the value with `value`
+    ///
+    /// 将以 `value` 替换值的更新入队
+    pub fn set(&self, value: T) -> Result<(), Error>
+    where
+        T: Send + 'static,
+    {
+        self.update(move |current| *current = value)
+    }
+
+    /// Whether the writer was dropped, so updates can no longer be applied
+    ///
+    /// 写入者是否已被丢弃，从而更新再也无法被应用
+    #[inline]
+    pub fn is_closed(&self) -> bool {
+        self.pending.strong_count() == 0
+    }
+}
+
+impl<T> Clone for Updater<T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            pending: self.pending.clone(),
+        }
+    }
+}
+
+impl<T> fmt::Debug for Updater<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Updater")
+            .field("closed", &self.is_closed())
+            .finish()
+    }
+}
@@ -0,0 +1,91 @@
+//! Blocking iteration over published versions.
+//!
+//! 对已发布版本的阻塞迭代。
+
+use crate::reader::{Reader, Ref};
+use crate::version::Version;
+use std::fmt;
+
+impl<T> Reader<T> {
+    /// Iterate over the versions published from now on, blocking between
+    /// them. The iterator ends once the cell is [frozen](Self::is_frozen).
+    ///
+    /// 迭代从现在起发布的版本，在版本之间阻塞。单元[封存](Self::is_frozen)后迭代结束。
+    #[inline]
+    pub fn iter_updates(&self) -> Updates<'_, T> {
+        Updates {
+            reader: self,
+            seen: self.current_version(),
+        }
+    }
+}
+
+/// Blocking iterator returned by [`Reader::iter_updates`]
+///
+/// 由 [`Reader::iter_updates`] 返回的阻塞迭代器
+pub struct Updates<'a, T> {
+    reader: &'a Reader<T>,
+    seen: Version,
+}
+
+/// A version yielded by [`Updates`]
+///
+/// 由 [`Updates`] 产出的版本
+#[non_exhaustive]
+pub struct Change<'a, T> {
+    /// Guard on the newly published version
+    ///
+    /// 新发布版本上的守卫
+    pub value: Ref<'a, T>,
+    /// Versions published since the previous item (or since the iterator
+    /// was created) that were never yielded
+    ///
+    /// 自上一项（或迭代器创建）以来已发布但未被产出的版本数
+    pub skipped: u64,
+}
+
+impl<'a, T> Updates<'a, T> {
+    /// Start an independent cursor at this one's position, e.g. to fan the
+    /// same versions out to another consumer. Advancing either cursor never
+    /// moves the other.
+    ///
+    /// 在此游标的位置开启一个独立游标，例如将相同的版本分发给另一个消费者。
+    /// 推进任一游标都不会移动另一个。
+    #[inline]
+    pub fn fork(&self) -> Updates<'a, T> {
+        Updates {
+            reader: self.reader,
+            seen: self.seen,
+        }
+    }
+}
+
+impl<'a, T> Iterator for Updates<'a, T> {
+    type Item = Change<'a, T>;
+
+    fn next(&mut self) -> Option<Change<'a, T>> {
+        let value = self.reader.wait_for_change(self.seen)?;
+        let version = Ref::version(&value);
+        let skipped = version.get().wrapping_sub(self.seen.get()).saturating_sub(1);
+        self.seen = version;
+        Some(Change { value, skipped })
+    }
+}
+
+impl<'a, T> fmt::Debug for Updates<'a, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Updates")
+            .field("cell", &self.reader.shared.id)
+            .field("seen", &self.seen)
+            .finish()
+    }
+}
+
+impl<'a, T: fmt::Debug> fmt::Debug for Change<'a, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Change")
+            .field("value", &self.value)
+            .field("skipped", &self.skipped)
+            .finish()
+    }
+}
@@ -17,7 +17,7 @@ impl Backoff {
         if self.step < 10 {
             spin_loop();
         } else {
-            crate::rt::thread::yield_now();
+            crate::rt::yield_now();
         }
         // Saturating increment
         // 饱和递增
@@ -42,3 +42,50 @@ impl<T> Deref for CachePadded<T> {
         &self.value
     }
 }
+
+/// Link-time trap proving a hot path cannot panic (`no-panic` feature).
+///
+/// Its destructor only runs while unwinding out of a `hot_path!` body and
+/// references a symbol that does not exist. Optimized builds therefore fail to
+/// link unless the compiler proves the body never unwinds.
+///
+/// 证明热路径不会 panic 的链接期陷阱（`no-panic` 特性）。
+///
+/// 其析构函数仅在从 `hot_path!` 代码体展开时运行，并引用一个不存在的符号。
+/// 因此除非编译器证明代码体不会展开，否则优化构建将无法链接。
+#[cfg(all(feature = "no-panic", not(debug_assertions)))]
+pub(crate) struct PanicTrap;
+
+#[cfg(all(feature = "no-panic", not(debug_assertions)))]
+impl Drop for PanicTrap {
+    #[inline(always)]
+    fn drop(&mut self) {
+        unsafe extern "C" {
+            #[link_name = "\n\nERROR[retro-cell]: a hot path may panic (no-panic feature)\n\n"]
+            fn retro_cell_hot_path_may_panic() -> !;
+        }
+        unsafe { retro_cell_hot_path_may_panic() }
+    }
+}
+
+/// Mark a function body as a panic-free hot path.
+///
+/// Without the `no-panic` feature this is a plain block. With it, release
+/// builds only link if the body provably cannot panic.
+///
+/// 将函数体标记为无 panic 的热路径。
+///
+/// 未启用 `no-panic` 特性时，这只是普通代码块。启用后，
+/// 仅当代码体可证明不会 panic 时，release 构建才能链接。
+macro_rules! hot_path {
+    ($($body:tt)*) => {{
+        #[cfg(all(feature = "no-panic", not(debug_assertions)))]
+        let trap = $crate::utils::PanicTrap;
+        #[allow(clippy::redundant_closure_call)]
+        let result = (|| { $($body)* })();
+        #[cfg(all(feature = "no-panic", not(debug_assertions)))]
+        ::core::mem::forget(trap);
+        result
+    }};
+}
+pub(crate) use hot_path;
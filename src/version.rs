@@ -0,0 +1,43 @@
+use std::fmt;
+
+/// Monotonic publication counter of a cell.
+///
+/// The initial value is version 0; every publication (COW or in-place)
+/// increments it by one.
+///
+/// 单元的单调发布计数。
+///
+/// 初始值为版本 0；每次发布（COW 或原地）都会加一。
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Version(u64);
+
+impl Version {
+    /// The raw counter value
+    ///
+    /// 原始计数值
+    #[inline]
+    pub const fn get(self) -> u64 {
+        self.0
+    }
+
+    #[inline]
+    pub(crate) const fn new(raw: u64) -> Self {
+        Self(raw)
+    }
+
+    #[inline]
+    pub(crate) const fn next(self) -> Self {
+        Self(self.0.wrapping_add(1))
+    }
+
+    #[inline]
+    pub(crate) const fn prev(self) -> Self {
+        Self(self.0.wrapping_sub(1))
+    }
+}
+
+impl fmt::Display for Version {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "v{}", self.0)
+    }
+}
@@ -0,0 +1,83 @@
+//! Reader handles that don't keep the cell alive.
+//!
+//! 不会使单元保持存活的读取者句柄。
+
+use crate::reader::Reader;
+use crate::shared::{Shared, SharedState};
+use std::fmt;
+use std::ptr::NonNull;
+use std::sync::Weak;
+
+/// Weak counterpart of [`Reader`], created with [`Reader::downgrade`].
+///
+/// It does not keep the shared state alive: once the writer and every
+/// [`Reader`] are gone, the last versions are freed and
+/// [`upgrade`](Self::upgrade) returns `None`. Useful for long-lived
+/// registries that must not pin large payloads.
+///
+/// [`Reader`] 的弱引用版本，由 [`Reader::downgrade`] 创建。
+///
+/// 它不会使共享状态保持存活：一旦写入者和所有 [`Reader`] 都已不存在，最后的版本即被释放，
+/// [`upgrade`](Self::upgrade) 返回 `None`。适用于不得固定大型负载的长期注册表。
+pub struct WeakReader<T> {
+    shared: WeakShared<T>,
+}
+
+enum WeakShared<T> {
+    Counted(Weak<SharedState<T>>),
+    // Static storage is never freed, so the pointer always upgrades
+    // 静态存储永不释放，因此该指针总能升级
+    Static(NonNull<SharedState<T>>),
+}
+
+unsafe impl<T: Send + Sync> Send for WeakReader<T> {}
+unsafe impl<T: Send + Sync> Sync for WeakReader<T> {}
+
+impl<T> Reader<T> {
+    /// Create a [`WeakReader`] for the same cell
+    ///
+    /// 为同一单元创建 [`WeakReader`]
+    #[inline]
+    pub fn downgrade(&self) -> WeakReader<T> {
+        let shared = match &self.shared {
+            Shared::Counted(arc) => WeakShared::Counted(std::sync::Arc::downgrade(arc)),
+            Shared::Static(ptr) => WeakShared::Static(*ptr),
+        };
+        WeakReader { shared }
+    }
+}
+
+impl<T> WeakReader<T> {
+    /// A reader of the cell, or `None` once its shared state was freed
+    ///
+    /// 获取该单元的读取者；若其共享状态已被释放则返回 `None`
+    #[inline]
+    pub fn upgrade(&self) -> Option<Reader<T>> {
+        let shared = match &self.shared {
+            WeakShared::Counted(weak) => Shared::Counted(weak.upgrade()?),
+            WeakShared::Static(ptr) => Shared::Static(*ptr),
+        };
+        Some(Reader::new(shared))
+    }
+}
+
+impl<T> Clone for WeakReader<T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        let shared = match &self.shared {
+            WeakShared::Counted(weak) => WeakShared::Counted(weak.clone()),
+            WeakShared::Static(ptr) => WeakShared::Static(*ptr),
+        };
+        Self { shared }
+    }
+}
+
+impl<T> fmt::Debug for WeakReader<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let alive = match &self.shared {
+            WeakShared::Counted(weak) => weak.strong_count() > 0,
+            WeakShared::Static(_) => true,
+        };
+        f.debug_struct("WeakReader").field("alive", &alive).finish()
+    }
+}
@@ -1,13 +1,28 @@
-use crate::reader::Reader;
+use crate::audit::{AuditLog, AuditRecord};
+use crate::builder::{Config, RetroCellBuilder};
+use crate::clock::Instant;
+use crate::error::Error;
+use crate::hazard::Registry;
+use crate::history::HistorySink;
+use crate::reader::{Reader, Ref};
+use crate::recycle::Recycle;
+use crate::ring::Ring;
 use crate::rt::sync::Arc;
-use crate::rt::sync::atomic::{AtomicPtr, AtomicUsize, Ordering};
-use crate::shared::{LOCKED, Node, PTR_MASK, SharedState};
+use crate::rt::sync::atomic::{AtomicBool, AtomicPtr, AtomicU32, AtomicU64, AtomicUsize, Ordering};
+use crate::shared::{
+    CellId, Hooks, LOCKED, Node, PTR_MASK, Shared, SharedState, SharedStorage, Stamp,
+};
 use crate::sync::Notifier;
-use crate::utils::CachePadded;
-use std::collections::VecDeque;
-use std::mem::align_of;
+use crate::updater::Pending;
+use crate::utils::{CachePadded, hot_path};
+use crate::version::Version;
+use std::borrow::Cow;
+use std::cell::UnsafeCell;
+use std::fmt;
+use std::mem;
 use std::ops::{Deref, DerefMut};
 use std::ptr::{self};
+use std::time::{Duration, SystemTime};
 
 /// Guard for in-place writing
 ///
@@ -15,6 +30,74 @@ use std::ptr::{self};
 pub struct InPlaceGuard<'a, T> {
     pub(crate) cell: &'a mut RetroCell<T>,
     pub(crate) locked_val: usize,
+    // Readers were not drained: only the atomic parts of the node may change
+    // 读者未被排空：只能修改节点的原子部分
+    pub(crate) dirty: bool,
+}
+
+// `&InPlaceGuard` only reaches `&T` and the cell's immutable identity
+// `&InPlaceGuard` 只能访问 `&T` 及单元不可变的标识
+unsafe impl<'a, T: Send + Sync> Sync for InPlaceGuard<'a, T> {}
+
+impl<'a, T> InPlaceGuard<'a, T> {
+    /// Publish the write and keep reading the value just written.
+    ///
+    /// The node is retained before the lock is released, and the returned
+    /// guard keeps the writer borrowed, so no other write can slip in.
+    ///
+    /// 发布写入并继续读取刚写入的值。
+    ///
+    /// 在释放锁之前先持有节点，且返回的守卫保持写入者被借用，因此不会插入其他写入。
+    pub fn downgrade(this: Self) -> Ref<'a, T> {
+        let shared: *const SharedState<T> = &*this.cell.shared;
+        let node = (this.locked_val & PTR_MASK) as *const Node<T>;
+        // Readers are drained and blocked by the lock, so no validation is needed
+        // 读者已排空且被锁阻塞，因此无需验证
+        unsafe { &*node }.reader_count.retain();
+        drop(this);
+        // The shared state outlives the `'a` borrow of the writer
+        // 共享状态的存活期长于写入者的 `'a` 借用
+        Ref::new(unsafe { &*shared }, unsafe { &*node })
+    }
+
+    /// Restore `original` (a copy taken before mutating) and release the
+    /// lock without publishing: the version is unchanged and blocked readers
+    /// read the restored value.
+    ///
+    /// 恢复 `original`（修改前获取的副本）并在不发布的情况下释放锁：
+    /// 版本保持不变，被阻塞的读者将读取恢复后的值。
+    pub fn abort(mut this: Self, original: T) {
+        *this = original;
+        Self::unlock(this);
+    }
+
+    /// Release the lock without publishing
+    ///
+    /// 释放锁而不发布
+    fn unlock(this: Self) {
+        let shared = &this.cell.shared;
+        hot_path! {
+            shared.current.store(this.locked_val & PTR_MASK, Ordering::Release);
+            shared.notifier.advance_and_wake();
+        }
+        // Skip the publication in `Drop`
+        // 跳过 `Drop` 中的发布
+        mem::forget(this);
+    }
+
+    /// Narrow the guard to a part of the value, e.g. one section of a large
+    /// struct. The lock is held and the write published when the returned
+    /// guard is dropped.
+    ///
+    /// 将守卫收窄为值的一部分，例如大型结构体的某个段。在返回的守卫析构时才释放锁并发布写入。
+    #[inline]
+    pub fn map_mut<U: ?Sized, F>(mut this: Self, f: F) -> MappedInPlaceGuard<'a, T, U>
+    where
+        F: FnOnce(&mut T) -> &mut U,
+    {
+        let value: *mut U = f(&mut this);
+        MappedInPlaceGuard { guard: this, value }
+    }
 }
 
 impl<'a, T> Deref for InPlaceGuard<'a, T> {
@@ -37,13 +120,102 @@ impl<'a, T> DerefMut for InPlaceGuard<'a, T> {
 impl<'a, T> Drop for InPlaceGuard<'a, T> {
     #[inline]
     fn drop(&mut self) {
-        self.cell
-            .shared
-            .current
-            .store(self.locked_val & PTR_MASK, Ordering::Release);
-        // Wake up readers blocked by the lock
-        // 唤醒被锁阻塞的读者
-        self.cell.shared.notifier.advance_and_wake();
+        if std::thread::panicking() {
+            self.cell.poisoned = true;
+        }
+        // Outside the hot path: the checksum is user code
+        // 位于热路径之外：校验和计算为用户代码
+        let node = unsafe { &*((self.locked_val & PTR_MASK) as *mut Node<T>) };
+        let stamp = self.cell.next_stamp();
+        if self.dirty {
+            node.set_version(stamp.version);
+        } else {
+            unsafe { node.set_stamp(stamp) };
+        }
+        #[cfg(feature = "checksum")]
+        node.seal(&self.cell.shared.hooks);
+        debug_assert_eq!(
+            self.cell.shared.current.load(Ordering::Relaxed),
+            self.locked_val | LOCKED,
+            "retro-cell: in-place lock of cell {:?} released twice or corrupted",
+            self.cell.shared.id,
+        );
+        hot_path! {
+            self.cell
+                .shared
+                .current
+                .store(self.locked_val & PTR_MASK, Ordering::Release);
+            // Wake up readers blocked by the lock
+            // 唤醒被锁阻塞的读者
+            self.cell.shared.notifier.advance_and_wake();
+        }
+        self.cell.published(WritePath::InPlace);
+    }
+}
+
+impl<'a, T: fmt::Debug> fmt::Debug for InPlaceGuard<'a, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("InPlaceGuard")
+            .field("value", &**self)
+            .field("cell", &self.cell.shared.id)
+            .finish()
+    }
+}
+
+/// In-place guard on a part of a value, returned by [`InPlaceGuard::map_mut`]
+///
+/// 值的一部分上的原地写入守卫，由 [`InPlaceGuard::map_mut`] 返回
+pub struct MappedInPlaceGuard<'a, T, U: ?Sized> {
+    guard: InPlaceGuard<'a, T>,
+    // Points into the locked node, which the guard keeps exclusive
+    // 指向被锁定的节点，守卫保证其独占
+    value: *mut U,
+}
+
+unsafe impl<'a, T, U: ?Sized + Send> Send for MappedInPlaceGuard<'a, T, U> where
+    InPlaceGuard<'a, T>: Send
+{
+}
+unsafe impl<'a, T: Send + Sync, U: ?Sized + Sync> Sync for MappedInPlaceGuard<'a, T, U> {}
+
+impl<'a, T, U: ?Sized> MappedInPlaceGuard<'a, T, U> {
+    /// Narrow the guard further
+    ///
+    /// 进一步收窄守卫
+    #[inline]
+    pub fn map_mut<V: ?Sized, F>(mut this: Self, f: F) -> MappedInPlaceGuard<'a, T, V>
+    where
+        F: FnOnce(&mut U) -> &mut V,
+    {
+        let value: *mut V = f(&mut this);
+        MappedInPlaceGuard {
+            guard: this.guard,
+            value,
+        }
+    }
+}
+
+impl<'a, T, U: ?Sized> Deref for MappedInPlaceGuard<'a, T, U> {
+    type Target = U;
+    #[inline]
+    fn deref(&self) -> &U {
+        unsafe { &*self.value }
+    }
+}
+
+impl<'a, T, U: ?Sized> DerefMut for MappedInPlaceGuard<'a, T, U> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut U {
+        unsafe { &mut *self.value }
+    }
+}
+
+impl<'a, T, U: ?Sized + fmt::Debug> fmt::Debug for MappedInPlaceGuard<'a, T, U> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MappedInPlaceGuard")
+            .field("value", &&**self)
+            .field("cell", &self.guard.cell.shared.id)
+            .finish()
     }
 }
 
@@ -55,26 +227,87 @@ pub struct CongestedWriter<'a, T> {
 }
 
 impl<'a, T> CongestedWriter<'a, T> {
-    pub fn force_in_place(self) -> InPlaceGuard<'a, T> {
-        let shared = &self.cell.shared;
-
-        let curr_val = shared.current.load(Ordering::Acquire);
-        let locked_val = curr_val | LOCKED;
-
-        // Forcefully acquire the lock
-        // 强制获取锁
-        shared.current.swap(locked_val, Ordering::AcqRel);
+    /// Number of readers currently holding the current value, see
+    /// [`RetroCell::active_readers`]
+    ///
+    /// 当前持有当前值的读者数，参见 [`RetroCell::active_readers`]
+    #[inline]
+    pub fn readers(&self) -> usize {
+        self.cell.active_readers()
+    }
 
-        // Wait for active readers to drain
-        // 等待活跃读者排空
+    pub fn force_in_place(self) -> InPlaceGuard<'a, T> {
+        let curr_val = self.cell.lock_in_place();
         let curr_ptr = (curr_val & PTR_MASK) as *mut Node<T>;
-        let curr_node = unsafe { &*curr_ptr };
-
-        curr_node.reader_count.wait_until_zero();
+        self.cell.maybe_snapshot(curr_ptr);
 
         InPlaceGuard {
             cell: self.cell,
             locked_val: curr_val,
+            dirty: false,
+        }
+    }
+
+    /// Like [`force_in_place`](Self::force_in_place), but wait at most
+    /// `timeout` for readers to drain. On timeout the lock is released and
+    /// the writer handed back, e.g. to [`perform_cow`](Self::perform_cow).
+    ///
+    /// The writer sleeps until the last reader leaves or the timeout expires.
+    /// The value stays locked meanwhile, so new reads of it block (or fall
+    /// back to the retro value) for up to `timeout`, as with
+    /// [`force_in_place`](Self::force_in_place).
+    ///
+    /// 与 [`force_in_place`](Self::force_in_place) 相同，但最多等待读者排空 `timeout` 时长。
+    /// 超时后释放锁并交还写入者，例如用于 [`perform_cow`](Self::perform_cow)。
+    ///
+    /// 写入者会睡眠直到最后一个读者离开或超时。期间值保持锁定，因此与
+    /// [`force_in_place`](Self::force_in_place) 相同，对它的新读取最多会阻塞
+    /// （或回退到回溯值）`timeout` 时长。
+    ///
+    /// A timeout is reported through the cell's
+    /// [`FailurePolicy`](crate::FailurePolicy) as [`Error::TimedOut`].
+    ///
+    /// 超时会作为 [`Error::TimedOut`] 通过单元的
+    /// [`FailurePolicy`](crate::FailurePolicy) 报告。
+    ///
+    /// A `timeout` too large to be represented as a deadline waits like
+    /// [`force_in_place`](Self::force_in_place).
+    ///
+    /// 过大而无法表示为截止时间的 `timeout` 会像
+    /// [`force_in_place`](Self::force_in_place) 一样等待。
+    pub fn wait_in_place_timeout(self, timeout: Duration) -> Result<InPlaceGuard<'a, T>, Self> {
+        match Instant::now().checked_add(timeout) {
+            Some(deadline) => self.force_in_place_until(deadline),
+            None => Ok(self.force_in_place()),
+        }
+    }
+
+    /// Like [`wait_in_place_timeout`](Self::wait_in_place_timeout), but give
+    /// up at `deadline`. Returns as soon as the readers drain.
+    ///
+    /// 与 [`wait_in_place_timeout`](Self::wait_in_place_timeout) 相同，但在 `deadline` 时放弃。
+    /// 读者一旦排空即返回。
+    pub fn force_in_place_until(self, deadline: Instant) -> Result<InPlaceGuard<'a, T>, Self> {
+        self.lock_until(deadline).inspect_err(|writer| {
+            writer.cell.shared.hooks.failure.apply(Error::TimedOut);
+        })
+    }
+
+    /// Lock in place once the readers drain before `deadline`, without
+    /// reporting a timeout
+    ///
+    /// 若读者在 `deadline` 前排空则原地加锁，不报告超时
+    fn lock_until(self, deadline: Instant) -> Result<InPlaceGuard<'a, T>, Self> {
+        match self.cell.lock_in_place_until(deadline) {
+            Some(curr_val) => {
+                self.cell.maybe_snapshot((curr_val & PTR_MASK) as *mut Node<T>);
+                Ok(InPlaceGuard {
+                    cell: self.cell,
+                    locked_val: curr_val,
+                    dirty: false,
+                })
+            }
+            None => Err(self),
         }
     }
 
@@ -87,39 +320,77 @@ impl<'a, T> CongestedWriter<'a, T> {
         let curr_ptr = (curr_val & PTR_MASK) as *mut Node<T>;
         let curr_node = unsafe { &*curr_ptr };
 
-        let new_data = unsafe { (*curr_node.data.get()).clone() };
-
-        let mut new_node = if let Some(recycled_node) = self.cell.pool.pop() {
-            unsafe { *recycled_node.data.get() = new_data };
-            // Reset RefCount for reuse
-            // 重置 RefCount 以复用
-            recycled_node.reader_count.reset();
-            recycled_node
-        } else {
-            Box::new(Node::new(new_data))
-        };
-
-        let result = f(new_node.data.get_mut());
-        let new_ptr = Box::into_raw(new_node);
-
-        let old_val_raw = self
-            .cell
-            .shared
-            .current
-            .swap(new_ptr as usize, Ordering::Release);
+        let mut new_data = unsafe { (*curr_node.data.get()).clone() };
+        let result = f(&mut new_data);
+        self.cell.install(new_data);
 
-        let old_ptr = (old_val_raw & PTR_MASK) as *mut Node<T>;
-        self.cell.garbage.push_back(old_ptr);
-        self.cell.shared.previous.store(old_ptr, Ordering::Release);
+        result
+    }
 
-        // COW complete. Wake up blocked readers
-        // COW 完成。唤醒阻塞的读者
-        self.cell.shared.notifier.advance_and_wake();
+    /// Like [`perform_cow`](Self::perform_cow), but refresh a pooled node
+    /// with [`Recycle`] instead of cloning when one is available
+    ///
+    /// 与 [`perform_cow`](Self::perform_cow) 相同，但在有可用的池节点时用 [`Recycle`]
+    /// 刷新它而非克隆
+    pub fn perform_cow_recycled<F, R>(self, f: F) -> R
+    where
+        T: Recycle + Clone,
+        F: FnOnce(&mut T) -> R,
+    {
+        let curr: *const T = self.cell.current();
+        // The writer never retires the current node while building the next one
+        // 构建下一个节点期间，写入者不会退役当前节点
+        let node = match self.cell.spare_node() {
+            Some(node) => {
+                unsafe { (*node.data.get()).recycle(&*curr) };
+                node
+            }
+            None => Box::new(Node::new(unsafe { (*curr).clone() }, Stamp::initial())),
+        };
+        let result = f(unsafe { &mut *node.data.get() });
+        unsafe { node.set_stamp(self.cell.next_stamp()) };
+        self.cell.publish_node(node);
 
         result
     }
 }
 
+/// How a new version was published
+///
+/// 新版本的发布方式
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum WritePath {
+    /// Copy-on-write: a new node replaced the current one
+    ///
+    /// 写时复制：新节点替换了当前节点
+    Cow,
+    /// The current node was modified under the lock
+    ///
+    /// 当前节点在锁定状态下被修改
+    InPlace,
+}
+
+/// Summary of a write performed by [`RetroCell::write`]
+///
+/// 由 [`RetroCell::write`] 执行的写入的摘要
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct WriteReport {
+    /// How the new version was published
+    ///
+    /// 新版本的发布方式
+    pub path: WritePath,
+    /// The published version
+    ///
+    /// 已发布的版本
+    pub version: Version,
+    /// Time spent waiting for readers to drain (reclaiming the retro node in
+    /// double-buffer mode); zero otherwise
+    ///
+    /// 等待读者排空所花费的时间（双缓冲模式下回收回溯节点）；其他情况为零
+    pub drained_wait: Duration,
+}
+
 /// Outcome of a write attempt
 ///
 /// 写入尝试的结果
@@ -132,9 +403,36 @@ pub enum WriteOutcome<'a, T> {
 ///
 /// 支持回溯读取的并发单元
 pub struct RetroCell<T> {
-    pub(crate) shared: Arc<SharedState<T>>,
-    pub(crate) garbage: VecDeque<*mut Node<T>>,
+    pub(crate) shared: Shared<T>,
+    pub(crate) garbage: Ring<*mut Node<T>>,
     pub(crate) pool: Vec<Box<Node<T>>>,
+    pub(crate) config: Config,
+    pub(crate) snapshot: Option<SnapshotPolicy<T>>,
+    pub(crate) gc_paused: u32,
+    pub(crate) version: Version,
+    pub(crate) tag: Option<Cow<'static, str>>,
+    pub(crate) audit: AuditLog,
+    pub(crate) reclaim: Option<fn(&mut T)>,
+    pub(crate) sink: Option<Box<dyn HistorySink<T>>>,
+    // One bit per recent publication, set if readers held the current version
+    // 每次最近发布占一位，若当时读者持有当前版本则置位
+    pub(crate) congestion: u64,
+    pub(crate) congested: bool,
+    // An in-place guard was dropped by a panicking thread
+    // 原地守卫在 panic 的线程中被析构
+    pub(crate) poisoned: bool,
+    // Created by the first `updater()` call
+    // 由首次调用 `updater()` 创建
+    pub(crate) pending: Option<Pending<T>>,
+}
+
+/// Time-based snapshot policy for cells updated mostly in place
+///
+/// 针对主要原地更新的单元的定时快照策略
+pub(crate) struct SnapshotPolicy<T> {
+    pub(crate) interval: Duration,
+    pub(crate) clone: fn(&T) -> T,
+    pub(crate) last: Option<Instant>,
 }
 
 unsafe impl<T: Send + Sync> Send for RetroCell<T> {}
@@ -143,80 +441,549 @@ impl<T> RetroCell<T> {
     /// Create a new RetroCell
     ///
     /// 创建一个新的 RetroCell
+    #[inline]
     pub fn new(initial: T) -> (Self, Reader<T>) {
-        assert!(align_of::<Node<T>>() >= 2);
-        let node = Box::new(Node::new(initial));
+        Self::with_config(initial, Config::default(), Hooks::default())
+    }
+
+    /// Create a new RetroCell restored with the versions that preceded
+    /// `current`, oldest first. The newest of them is readable through
+    /// [`Reader::read_retro`]; older ones are dropped, unless the cell is
+    /// built with a history sink through
+    /// [`RetroCellBuilder::build_with_history`].
+    ///
+    /// The history is numbered as published first, so `current` gets
+    /// version 1 after a single prior version (or 0 without any).
+    ///
+    /// 创建一个恢复了 `current` 之前各版本（从旧到新）的新 RetroCell。其中最新的版本可通过
+    /// [`Reader::read_retro`] 读取；更早的版本会被丢弃，除非通过
+    /// [`RetroCellBuilder::build_with_history`] 使用历史接收器构建单元。
+    ///
+    /// 历史按最先发布的版本编号，因此只有一个先前版本时 `current` 的版本为 1（若无则为 0）。
+    #[inline]
+    pub fn with_initial_history<I>(current: T, history: I) -> (Self, Reader<T>)
+    where
+        I: IntoIterator<Item = T>,
+    {
+        Self::builder().build_with_history(current, history)
+    }
+
+    /// Create a builder for configuring a RetroCell
+    ///
+    /// 创建用于配置 RetroCell 的构建器
+    #[inline]
+    pub fn builder() -> RetroCellBuilder<T> {
+        RetroCellBuilder::new()
+    }
+
+    /// Create a new RetroCell, reporting failures instead of panicking
+    ///
+    /// 创建一个新的 RetroCell，失败时返回错误而非 panic
+    #[inline]
+    pub fn try_new(initial: T) -> Result<(Self, Reader<T>), Error> {
+        Self::try_with_config(initial, Config::default(), Hooks::default())
+    }
+
+    pub(crate) fn with_config(initial: T, config: Config, hooks: Hooks<T>) -> (Self, Reader<T>) {
+        Self::from_node(Box::new(Node::new(initial, Stamp::initial())), config, hooks)
+    }
+
+    pub(crate) fn try_with_config(
+        initial: T,
+        config: Config,
+        hooks: Hooks<T>,
+    ) -> Result<(Self, Reader<T>), Error> {
+        Ok(Self::from_node(
+            Node::try_boxed(initial, Stamp::initial())?,
+            config,
+            hooks,
+        ))
+    }
+
+    fn from_node(node: Box<Node<T>>, config: Config, hooks: Hooks<T>) -> (Self, Reader<T>) {
+        let shared = Shared::Counted(Arc::new(Self::state(node, hooks)));
+        Self::from_shared(shared, config)
+    }
+
+    /// Create a new RetroCell whose shared state lives in `storage` instead
+    /// of behind an `Arc`.
+    ///
+    /// Panics if `storage` already holds a cell.
+    ///
+    /// 创建一个新的 RetroCell，其共享状态位于 `storage` 中而非 `Arc` 之后。
+    ///
+    /// 若 `storage` 已容纳单元则 panic。
+    pub fn new_in(storage: &'static SharedStorage<T>, initial: T) -> (Self, Reader<T>) {
+        let node = Box::new(Node::new(initial, Stamp::initial()));
+        let shared = storage.place(Self::state(node, Hooks::default()));
+        Self::from_shared(shared, Config::default())
+    }
+
+    fn state(node: Box<Node<T>>, mut hooks: Hooks<T>) -> SharedState<T> {
+        #[cfg(feature = "checksum")]
+        node.seal(&hooks);
         let ptr = Box::into_raw(node);
 
-        let shared = Arc::new(SharedState {
+        SharedState {
             current: CachePadded {
                 value: AtomicUsize::new(ptr as usize),
             },
             notifier: CachePadded {
-                value: Notifier::new(),
+                value: Notifier::new(hooks.notify.take()),
             },
             previous: AtomicPtr::new(ptr::null_mut()),
-        });
+            version: AtomicU64::new(0),
+            pressure: AtomicU32::new(0),
+            frozen: AtomicBool::new(false),
+            closed: AtomicBool::new(false),
+            readers: AtomicUsize::new(0),
+            wakers: std::sync::Mutex::new(Vec::new()),
+            has_wakers: AtomicBool::new(false),
+            orphans: UnsafeCell::new(Vec::new()),
+            hooks,
+            id: CellId::next(),
+            slots: Registry::new(),
+        }
+    }
 
+    fn from_shared(shared: Shared<T>, config: Config) -> (Self, Reader<T>) {
         (
             RetroCell {
                 shared: shared.clone(),
-                garbage: VecDeque::new(),
+                garbage: Ring::with_capacity(config.garbage_capacity()),
                 pool: Vec::new(),
+                config,
+                snapshot: None,
+                gc_paused: 0,
+                version: Version::default(),
+                tag: None,
+                audit: AuditLog::new(config.audit_capacity),
+                reclaim: None,
+                sink: None,
+                congestion: 0,
+                congested: false,
+                poisoned: false,
+                pending: None,
             },
-            Reader { shared },
+            Reader::new(shared),
         )
     }
 
+    /// Version of the currently published value
+    ///
+    /// 当前已发布值的版本
     #[inline]
-    fn collect_garbage(&mut self) {
-        while self.garbage.len() > 1 {
-            if let Some(&ptr) = self.garbage.front() {
-                let node = unsafe { &*ptr };
-                // RefCount::count masks the WAITING bit
-                // RefCount::count 已屏蔽 WAITING 位
-                if node.reader_count.count() == 0 {
-                    self.garbage.pop_front();
-                    let node_box = unsafe { Box::from_raw(ptr) };
-                    self.pool.push(node_box);
-                } else {
-                    break;
+    pub fn version(&self) -> Version {
+        self.version
+    }
+
+    /// Number of live [`Reader`] handles, including those owned by wrappers
+    /// such as [`MappedReader`](crate::MappedReader). Useful to stop
+    /// producing once the last subscriber is gone.
+    ///
+    /// 存活的 [`Reader`] 句柄数，包括 [`MappedReader`](crate::MappedReader) 等包装类型所拥有的。
+    /// 可用于在最后一个订阅者离开后停止生产。
+    #[inline]
+    pub fn reader_handles(&self) -> usize {
+        self.shared.readers.load(Ordering::Acquire)
+    }
+
+    /// Number of readers currently holding the current value, i.e. how many
+    /// would block an in-place write. Only a snapshot: readers come and go
+    /// concurrently. The nested guards of one thread (`coalesce` feature)
+    /// count once.
+    ///
+    /// 当前持有当前值的读者数，即会阻塞原地写入的读者数。仅为快照：读者会并发地进出。
+    /// 同一线程的嵌套守卫（`coalesce` 特性）只计一次。
+    #[inline]
+    pub fn active_readers(&self) -> usize {
+        let curr_val = self.shared.current.load(Ordering::Acquire);
+        self.shared.holders((curr_val & PTR_MASK) as *mut Node<T>)
+    }
+
+    /// Create a new reader of this cell, so subsystems started later don't
+    /// need the reader returned by [`new`](Self::new)
+    ///
+    /// 创建此单元的新读取者，使稍后启动的子系统无需 [`new`](Self::new) 返回的读取者
+    #[inline]
+    pub fn new_reader(&self) -> Reader<T> {
+        Reader::new(self.shared.clone())
+    }
+
+    /// Whether an in-place write was interrupted by a panic, so the current
+    /// value may be half-updated
+    ///
+    /// 原地写入是否被 panic 中断，从而当前值可能只更新了一半
+    #[inline]
+    pub fn is_poisoned(&self) -> bool {
+        self.poisoned
+    }
+
+    /// Clear the poisoned state after repairing the value
+    ///
+    /// 修复值之后清除中毒状态
+    #[inline]
+    pub fn clear_poison(&mut self) {
+        self.poisoned = false;
+    }
+
+    /// Attach a metadata tag (e.g. who is making the change) to the next
+    /// publication. It is recorded in the [audit trail](Self::audit).
+    ///
+    /// 为下一次发布附加元数据标签（例如修改者）。该标签会记录在[审计记录](Self::audit)中。
+    #[inline]
+    pub fn tag(&mut self, tag: impl Into<Cow<'static, str>>) -> &mut Self {
+        self.tag = Some(tag.into());
+        self
+    }
+
+    /// The audit trail, oldest first. Empty unless enabled with
+    /// [`RetroCellBuilder::audit_log`].
+    ///
+    /// 审计记录，按从旧到新排列。除非通过 [`RetroCellBuilder::audit_log`] 启用，否则为空。
+    #[inline]
+    pub fn audit(&self) -> impl ExactSizeIterator<Item = &AuditRecord> + '_ {
+        self.audit.iter()
+    }
+
+    /// The current value, as seen by the writer. No guard is needed: only the
+    /// writer mutates it, and it cannot be locked while `self` is borrowed.
+    ///
+    /// 写入者视角的当前值。无需守卫：只有写入者会修改它，且借用 `self` 期间它不会被锁定。
+    #[inline]
+    pub(crate) fn current(&self) -> &T {
+        let curr_ptr = (self.shared.current.load(Ordering::Acquire) & PTR_MASK) as *mut Node<T>;
+        unsafe { &*(*curr_ptr).data.get() }
+    }
+
+    /// The current value, read on the writer side without touching any
+    /// reader count. Useful for read-modify decisions before a write.
+    ///
+    /// 在写入者一侧读取当前值，不触及任何读者计数。适用于写入前的读取-修改决策。
+    #[inline]
+    pub fn peek(&self) -> &T {
+        self.current()
+    }
+
+    /// Metadata for the version about to be published
+    ///
+    /// 即将发布的版本的元数据
+    #[inline]
+    fn next_stamp(&self) -> Stamp {
+        Stamp {
+            version: self.version.next(),
+            time: Instant::now(),
+        }
+    }
+
+    /// Account for a publication that just happened
+    ///
+    /// 记录刚刚完成的一次发布
+    fn published(&mut self, path: WritePath) {
+        self.version = self.version.next();
+        self.shared.version.store(self.version.get(), Ordering::Release);
+        self.congestion = (self.congestion << 1) | u64::from(mem::take(&mut self.congested));
+        self.shared
+            .pressure
+            .store(self.congestion.count_ones(), Ordering::Relaxed);
+        self.shared.wake_tasks();
+        let tag = self.tag.take();
+        if self.audit.is_enabled() {
+            self.audit.record(AuditRecord {
+                version: self.version,
+                timestamp: SystemTime::now(),
+                path,
+                tag,
+            });
+        }
+    }
+
+    /// Take a node for new data: from the pool, by reclaiming the retro node
+    /// (double-buffer mode), or by allocating
+    ///
+    /// 为新数据获取节点：来自池、回收回溯节点（双缓冲模式）或新分配
+    fn acquire_node(&mut self, data: T, stamp: Stamp) -> Box<Node<T>> {
+        let spare = self.spare_node();
+        fill_node(spare, data, stamp)
+    }
+
+    /// Take a retired node whose stale value can be overwritten: from the
+    /// pool, or by reclaiming the retro node (double-buffer mode)
+    ///
+    /// 获取一个其过期值可被覆盖的已退役节点：来自池，或回收回溯节点（双缓冲模式）
+    fn spare_node(&mut self) -> Option<Box<Node<T>>> {
+        if let Some(recycled_node) = self.pooled_node() {
+            return Some(recycled_node);
+        }
+        let retro_ptr = self.unpublish_retro()?;
+        self.shared.wait_idle(retro_ptr);
+        Some(self.reclaim_retro(retro_ptr))
+    }
+
+    /// Like [`spare_node`](Self::spare_node), but never waits: if the retro
+    /// node still has readers it stays published and this fails with
+    /// [`Error::BudgetExhausted`]
+    ///
+    /// 与 [`spare_node`](Self::spare_node) 相同，但从不等待：若回溯节点仍有读者，
+    /// 它保持发布，并以 [`Error::BudgetExhausted`] 失败
+    fn try_spare_node(&mut self) -> Result<Option<Box<Node<T>>>, Error> {
+        if let Some(recycled_node) = self.pooled_node() {
+            return Ok(Some(recycled_node));
+        }
+        let Some(retro_ptr) = self.unpublish_retro() else {
+            return Ok(None);
+        };
+        // Checked after unpublishing, so no reader can retain it in between
+        // 在取消发布之后检查，因此期间没有读者可以持有它
+        if !self.shared.is_idle(retro_ptr) {
+            self.shared.previous.store(retro_ptr, Ordering::Release);
+            self.garbage.push_back(retro_ptr);
+            return Err(Error::BudgetExhausted);
+        }
+        Ok(Some(self.reclaim_retro(retro_ptr)))
+    }
+
+    /// Take a node from the pool
+    ///
+    /// 从池中取出节点
+    #[inline]
+    fn pooled_node(&mut self) -> Option<Box<Node<T>>> {
+        let recycled_node = self.pool.pop()?;
+        // Reset RefCount for reuse
+        // 重置 RefCount 以复用
+        recycled_node.reader_count.reset();
+        Some(recycled_node)
+    }
+
+    /// Double-buffer mode: take the retro node off `previous` so that no new
+    /// reader can retain it. It is the newest retired node; older ones are
+    /// left to [`collect_garbage`](Self::collect_garbage).
+    ///
+    /// 双缓冲模式：将回溯节点从 `previous` 上取下，使新读者无法再持有它。
+    /// 它是最新退役的节点；更旧的节点留给 [`collect_garbage`](Self::collect_garbage)。
+    fn unpublish_retro(&mut self) -> Option<*mut Node<T>> {
+        if !self.config.double_buffer || self.gc_paused > 0 {
+            return None;
+        }
+        let retro_ptr = self.garbage.pop_back()?;
+        debug_assert_eq!(self.shared.previous.load(Ordering::Relaxed), retro_ptr);
+        self.shared
+            .previous
+            .store(ptr::null_mut(), Ordering::SeqCst);
+        Some(retro_ptr)
+    }
+
+    /// Take ownership of an unpublished retro node once it has drained
+    ///
+    /// 在已取消发布的回溯节点排空后取得其所有权
+    fn reclaim_retro(&mut self, retro_ptr: *mut Node<T>) -> Box<Node<T>> {
+        // Only now that no reader holds a `&Node` may a unique Box exist
+        // 只有在没有读者持有 `&Node` 时才可以存在唯一的 Box
+        let mut retro_node = unsafe { Box::from_raw(retro_ptr) };
+        if let Some(sink) = &mut self.sink {
+            archive(&mut **sink, &retro_node);
+        }
+        // Same as a node parked by `collect_garbage`
+        // 与 `collect_garbage` 放入池中的节点相同
+        if let Some(reclaim) = self.reclaim {
+            reclaim(retro_node.data.get_mut());
+        }
+        retro_node.reader_count.reset();
+        retro_node
+    }
+
+    /// Record that a retro data point now exists
+    ///
+    /// 记录已存在新的回溯数据点
+    #[inline]
+    fn snapshot_taken(&mut self) {
+        if let Some(policy) = &mut self.snapshot {
+            policy.last = Some(Instant::now());
+        }
+    }
+
+    /// Before an in-place write, retain a copy of the locked value as the retro
+    /// version if the snapshot interval has elapsed
+    ///
+    /// 原地写入前，若快照间隔已过，则将被锁定的值复制为回溯版本
+    fn maybe_snapshot(&mut self, curr_ptr: *mut Node<T>) {
+        let Some(policy) = &mut self.snapshot else {
+            return;
+        };
+        let now = Instant::now();
+        if policy
+            .last
+            .is_some_and(|last| now.duration_since(last) < policy.interval)
+        {
+            return;
+        }
+        policy.last = Some(now);
+
+        // The snapshot is the version being modified, so it keeps its stamp
+        // 快照即为将被修改的版本，因此保留其元数据
+        let data = (policy.clone)(unsafe { &*(*curr_ptr).data.get() });
+        let stamp = unsafe { &*curr_ptr }.stamp();
+        let node = self.acquire_node(data, stamp);
+        #[cfg(feature = "checksum")]
+        node.seal(&self.shared.hooks);
+        let node_ptr = Box::into_raw(node);
+        self.shared.previous.store(node_ptr, Ordering::Release);
+        self.garbage.push_back(node_ptr);
+    }
+
+    #[inline]
+    pub(crate) fn collect_garbage(&mut self) {
+        if self.gc_paused > 0 {
+            return;
+        }
+        if self.garbage.len() <= 1 {
+            return;
+        }
+        // The newest retired node stays published as 'previous'
+        // 最新退役的节点作为 'previous' 保持发布
+        let retro = self.garbage.pop_back();
+        let pool = &mut self.pool;
+        let shared = &*self.shared;
+        let reclaim = self.reclaim;
+        let mut sink = self.sink.as_deref_mut();
+        self.garbage.retain(|ptr| {
+            // RefCount::count masks the WAITING bit. Busy nodes (including
+            // leaked ones) are skipped so they don't hold back the rest.
+            // RefCount::count 已屏蔽 WAITING 位。跳过繁忙节点（包括泄漏节点），
+            // 以免阻碍其余节点的回收。
+            if shared.is_idle(ptr) {
+                let mut node = unsafe { Box::from_raw(ptr) };
+                if let Some(sink) = sink.as_deref_mut() {
+                    archive(sink, &node);
+                }
+                if let Some(reclaim) = reclaim {
+                    reclaim(node.data.get_mut());
                 }
+                pool.push(node);
+                false
+            } else {
+                true
             }
+        });
+        if let Some(retro) = retro {
+            self.garbage.push_back(retro);
         }
     }
 
+    /// Run `f` with reclamation paused.
+    ///
+    /// No retired node is freed or recycled inside the scope, so old versions
+    /// stay intact (e.g. for a diff/rollback window). Reclamation resumes and
+    /// catches up when the scope ends. In double-buffer mode, COW writes inside
+    /// the scope allocate instead of reclaiming the retro node. If `f` panics,
+    /// reclamation resumes without catching up.
+    ///
+    /// 在暂停回收的情况下运行 `f`。
+    ///
+    /// 作用域内不会释放或复用任何已退役节点，因此旧版本保持完整
+    /// （例如用于差异比较/回滚窗口）。作用域结束时恢复回收并追赶进度。
+    /// 在双缓冲模式下，作用域内的 COW 写入会分配新节点而非回收回溯节点。
+    /// 若 `f` panic，回收会恢复但不追赶进度。
+    pub fn with_gc_paused<F, R>(&mut self, f: F) -> R
+    where
+        F: FnOnce(&mut Self) -> R,
+    {
+        self.gc_paused += 1;
+        let paused = GcPause(self);
+        f(&mut *paused.0)
+    }
+
     /// Try to write to the cell
     ///
     /// 尝试写入单元
     pub fn try_write(&mut self) -> WriteOutcome<'_, T> {
         self.collect_garbage();
+        self.congested = false;
 
-        let curr_val = self.shared.current.load(Ordering::Acquire);
-        let curr_ptr = (curr_val & PTR_MASK) as *mut Node<T>;
-        let curr_node = unsafe { &*curr_ptr };
+        if let Some(locked_val) = self.try_lock_in_place() {
+            self.maybe_snapshot((locked_val & PTR_MASK) as *mut Node<T>);
+            return WriteOutcome::InPlace(InPlaceGuard {
+                cell: self,
+                locked_val,
+                dirty: false,
+            });
+        }
 
-        if curr_node.reader_count.count() == 0 {
-            let locked_val = curr_val | LOCKED;
+        self.congested = true;
+        WriteOutcome::Congested(CongestedWriter { cell: self })
+    }
 
-            // Optimization: AcqRel performs better on ARM
-            // 优化：AcqRel 在 ARM 上性能更佳
-            let _ = self.shared.current.swap(locked_val, Ordering::AcqRel);
+    /// Lock the current node for in-place writing if it has no readers
+    ///
+    /// 若当前节点没有读者，则将其锁定以进行原地写入
+    #[inline]
+    fn try_lock_in_place(&self) -> Option<usize> {
+        hot_path! {
+            let curr_val = self.shared.current.load(Ordering::Acquire);
+            let curr_ptr = (curr_val & PTR_MASK) as *mut Node<T>;
 
-            if curr_node.reader_count.count() == 0 {
-                return WriteOutcome::InPlace(InPlaceGuard {
-                    cell: self,
-                    locked_val: locked_val,
-                });
-            } else {
+            if self.shared.is_idle(curr_ptr) {
+                let locked_val = curr_val | LOCKED;
+
+                // Optimization: AcqRel performs better on ARM
+                // 优化：AcqRel 在 ARM 上性能更佳
+                let _ = self.shared.current.swap(locked_val, Ordering::AcqRel);
+
+                if self.shared.is_idle(curr_ptr) {
+                    return Some(locked_val);
+                }
                 // Rollback lock on failure
                 // 失败时回滚锁
                 self.shared.current.store(curr_val, Ordering::Release);
                 self.shared.notifier.advance_and_wake();
             }
+            None
         }
+    }
 
-        WriteOutcome::Congested(CongestedWriter { cell: self })
+    /// Lock the current node and wait for its readers to drain, returning the
+    /// unlocked pointer value
+    ///
+    /// 锁定当前节点并等待其读者排空，返回未加锁的指针值
+    #[inline]
+    fn lock_in_place(&self) -> usize {
+        let curr_val = hot_path! {
+            let shared = &*self.shared;
+
+            let curr_val = shared.current.load(Ordering::Acquire);
+            let locked_val = curr_val | LOCKED;
+
+            // Forcefully acquire the lock
+            // 强制获取锁
+            shared.current.swap(locked_val, Ordering::AcqRel);
+            curr_val
+        };
+
+        // Wait for active readers to drain (certified separately, not inlined)
+        // 等待活跃读者排空（单独验证，不内联）
+        self.shared.wait_idle((curr_val & PTR_MASK) as *mut Node<T>);
+        curr_val
+    }
+
+    /// Like [`lock_in_place`](Self::lock_in_place), but give up at
+    /// `deadline`, unlocking the node and waking the readers it blocked
+    ///
+    /// 与 [`lock_in_place`](Self::lock_in_place) 相同，但在 `deadline` 时放弃，
+    /// 解锁节点并唤醒被其阻塞的读者
+    fn lock_in_place_until(&self, deadline: Instant) -> Option<usize> {
+        let shared = &*self.shared;
+        let curr_val = shared.current.load(Ordering::Acquire);
+        let curr_ptr = (curr_val & PTR_MASK) as *mut Node<T>;
+        shared.current.swap(curr_val | LOCKED, Ordering::AcqRel);
+
+        if shared.wait_idle_until(curr_ptr, deadline) {
+            return Some(curr_val);
+        }
+        // Rollback lock on timeout
+        // 超时时回滚锁
+        shared.current.store(curr_val, Ordering::Release);
+        shared.notifier.advance_and_wake();
+        None
     }
 
     /// Perform COW update directly
@@ -229,9 +996,551 @@ impl<T> RetroCell<T> {
         F: FnOnce(&mut T) -> R,
     {
         self.collect_garbage();
+        // Chosen by the caller, not forced by readers
+        // 由调用者选择，而非被读者强制
+        self.congested = false;
         CongestedWriter { cell: self }.perform_cow(f)
     }
 
+    /// Write in place when no reader holds the current value, otherwise
+    /// through COW, and report what happened
+    ///
+    /// 没有读者持有当前值时原地写入，否则通过 COW 写入，并报告写入情况
+    pub fn write<F>(&mut self, f: F) -> WriteReport
+    where
+        T: Clone,
+        F: FnOnce(&mut T),
+    {
+        let (path, drained_wait) = match self.try_write() {
+            WriteOutcome::InPlace(mut guard) => {
+                f(&mut guard);
+                (WritePath::InPlace, Duration::ZERO)
+            }
+            WriteOutcome::Congested(writer) => {
+                let cell = writer.cell;
+                let mut value = cell.current().clone();
+                f(&mut value);
+                // Only reclaiming the retro node can wait for readers
+                // 只有回收回溯节点可能需要等待读者
+                let drains = cell.config.double_buffer && cell.pool.is_empty();
+                let start = drains.then(Instant::now);
+                cell.install(value);
+                (WritePath::Cow, start.map_or(Duration::ZERO, |start| start.elapsed()))
+            }
+        };
+        WriteReport {
+            path,
+            version: self.version,
+            drained_wait,
+        }
+    }
+
+    /// Apply every update of `updates` in order and publish them as a single
+    /// version, with one lock acquisition or one COW clone and one wakeup
+    ///
+    /// 按顺序应用 `updates` 中的每个更新并将其作为单个版本发布，仅需一次加锁或一次 COW 克隆以及一次唤醒
+    pub fn write_batch<I>(&mut self, updates: I) -> WriteReport
+    where
+        T: Clone,
+        I: IntoIterator,
+        I::Item: FnOnce(&mut T),
+    {
+        self.write(|value| updates.into_iter().for_each(|update| update(value)))
+    }
+
+    /// Consume a stream of updates, e.g. a replayed log, and publish the
+    /// result as a single version. Returns the number of updates applied;
+    /// nothing is published if the stream is empty.
+    ///
+    /// The stream is drained before the write starts, so a slow source never
+    /// holds the in-place lock; the updates are then applied as by
+    /// [`write_batch`](Self::write_batch).
+    ///
+    /// 消费一个更新流（例如重放的日志），并将结果作为单个版本发布。返回已应用的更新数；
+    /// 若流为空则不发布任何内容。
+    ///
+    /// 流在写入开始前即被取尽，因此缓慢的数据源永远不会占用原地锁；随后各更新的应用方式与
+    /// [`write_batch`](Self::write_batch) 相同。
+    pub fn apply_stream<I>(&mut self, updates: I) -> usize
+    where
+        T: Clone,
+        I: IntoIterator,
+        I::Item: FnOnce(&mut T),
+    {
+        let updates: Vec<_> = updates.into_iter().collect();
+        let count = updates.len();
+        if count > 0 {
+            self.write_batch(updates);
+        }
+        count
+    }
+
+    /// Consume a stream of values and publish the last one, without cloning.
+    /// Returns the number of values consumed; nothing is published if the
+    /// stream is empty.
+    ///
+    /// 消费一个值流并发布其中最后一个值，无需克隆。返回已消费的值数；若流为空则不发布任何内容。
+    pub fn store_stream<I>(&mut self, values: I) -> usize
+    where
+        I: IntoIterator<Item = T>,
+    {
+        let mut count = 0;
+        let last = values.into_iter().inspect(|_| count += 1).last();
+        if let Some(value) = last {
+            self.store(value);
+        }
+        count
+    }
+
+    /// Write in place with `f`, rolling the value back and publishing nothing
+    /// if it returns `Err`. The value is cloned before `f` runs.
+    ///
+    /// 使用 `f` 原地写入；若其返回 `Err`，则回滚值且不发布任何内容。`f` 运行前会克隆该值。
+    pub fn try_write_in_place<F, R, E>(&mut self, f: F) -> Result<R, E>
+    where
+        T: Clone,
+        F: FnOnce(&mut T) -> Result<R, E>,
+    {
+        let mut guard = self.write_in_place();
+        let original = guard.clone();
+        match f(&mut guard) {
+            Ok(result) => Ok(result),
+            Err(err) => {
+                InPlaceGuard::abort(guard, original);
+                Err(err)
+            }
+        }
+    }
+
+    /// Write in place, waiting at most `budget` for the readers of the current
+    /// value to drain, and through COW if they don't. A `budget` too large to
+    /// be represented as a deadline waits like [`write_in_place`](Self::write_in_place).
+    ///
+    /// 原地写入，最多等待当前值的读者排空 `budget` 时长；若未排空则通过 COW 写入。
+    /// 过大而无法表示为截止时间的 `budget` 会像 [`write_in_place`](Self::write_in_place) 一样等待。
+    pub fn write_adaptive<F, R>(&mut self, budget: Duration, f: F) -> R
+    where
+        T: Clone,
+        F: FnOnce(&mut T) -> R,
+    {
+        match self.try_write() {
+            WriteOutcome::InPlace(mut guard) => f(&mut guard),
+            // Falling back to COW is the plan, not a failure
+            // 回退到 COW 是预定方案，而非失败
+            WriteOutcome::Congested(writer) => match Instant::now().checked_add(budget) {
+                Some(deadline) => match writer.lock_until(deadline) {
+                    Ok(mut guard) => f(&mut guard),
+                    Err(writer) => writer.perform_cow(f),
+                },
+                None => f(&mut writer.force_in_place()),
+            },
+        }
+    }
+
+    /// Perform a COW update that can be aborted: if `f` returns `Err`, the
+    /// copy is discarded and nothing is published.
+    ///
+    /// The copy is built before any node is taken, so an aborted write leaves
+    /// the pool, the retro value and the version untouched.
+    ///
+    /// 执行可中止的 COW 更新：若 `f` 返回 `Err`，则丢弃副本且不发布任何内容。
+    ///
+    /// 副本在获取任何节点之前构建，因此中止的写入不会改变池、回溯值和版本。
+    pub fn try_write_cow<F, R, E>(&mut self, f: F) -> Result<R, E>
+    where
+        T: Clone,
+        F: FnOnce(&mut T) -> Result<R, E>,
+    {
+        let mut value = self.current().clone();
+        let result = f(&mut value)?;
+        self.install_cow(value);
+        Ok(result)
+    }
+
+    /// Perform COW update by building the new value from the current one,
+    /// instead of cloning it and mutating the copy.
+    ///
+    /// Useful for values whose `Clone` is a deep copy but which are cheap to
+    /// rebuild from parts (e.g. sharing `Arc`s of the old value).
+    ///
+    /// 通过由当前值构建新值来执行 COW 更新，而不是克隆后修改副本。
+    ///
+    /// 适用于 `Clone` 为深拷贝、但由各部分重建成本较低的值（例如共享旧值的 `Arc`）。
+    #[inline]
+    pub fn write_cow_with<F>(&mut self, f: F)
+    where
+        F: FnOnce(&T) -> T,
+    {
+        let value = f(self.current());
+        self.install_cow(value);
+    }
+
+    /// Publish a fully constructed `value` through the COW path, so the
+    /// current value becomes the retro value
+    ///
+    /// 通过 COW 路径发布一个已完整构造的 `value`，使当前值成为回溯值
+    #[inline]
+    fn install_cow(&mut self, value: T) {
+        self.collect_garbage();
+        self.congested = false;
+        self.install(value);
+    }
+
+    /// Perform COW update, refreshing a retired node with [`Recycle`] instead
+    /// of cloning the current value into a new one.
+    ///
+    /// Useful for values owning resources that are cheap to reset and costly
+    /// to rebuild (buffers, arenas). Clones only when no retired node is
+    /// available for reuse.
+    ///
+    /// 执行 COW 更新，使用 [`Recycle`] 刷新已退役节点，而不是将当前值克隆到新节点。
+    ///
+    /// 适用于持有重置成本低、重建成本高的资源的值（缓冲区、arena）。
+    /// 仅在没有可复用的已退役节点时才克隆。
+    #[inline]
+    pub fn write_cow_recycled<F, R>(&mut self, f: F) -> R
+    where
+        T: Recycle + Clone,
+        F: FnOnce(&mut T) -> R,
+    {
+        self.collect_garbage();
+        self.congested = false;
+        CongestedWriter { cell: self }.perform_cow_recycled(f)
+    }
+
+    /// Mutate the current value in place without waiting for readers to drain.
+    ///
+    /// New readers are blocked while `f` runs, but readers already holding a
+    /// guard keep reading the value as it is being written and may observe
+    /// torn, half-updated values.
+    ///
+    /// The write publishes a new version, which guards already held report
+    /// too (the version is atomic). The publish time is not updated, since
+    /// those guards may be reading it: [`ReadMeta::publish_time`](crate::ReadMeta::publish_time) keeps the
+    /// time of the last regular write.
+    ///
+    /// # Safety
+    ///
+    /// Writing `T` while guards read it is a data race under the Rust memory
+    /// model; the version and checksum are updated atomically and are not
+    /// part of it. The caller must ensure that every reader of this cell
+    /// tolerates torn values, e.g. that `T` is a plain `repr(C)` struct whose
+    /// fields are only ever copied out and used independently. With the
+    /// `checksum` feature, a guard created while `f` runs may hash a torn value
+    /// and report it as corrupted in debug builds.
+    ///
+    /// 原地修改当前值，且不等待读者排空。
+    ///
+    /// `f` 运行期间新读者会被阻塞，但已持有守卫的读者会在值被写入的同时继续读取，
+    /// 可能观察到撕裂的、部分更新的值。
+    ///
+    /// 写入会发布新版本，已持有的守卫也会报告该版本（版本为原子类型）。发布时间不会更新，
+    /// 因为这些守卫可能正在读取它：[`ReadMeta::publish_time`](crate::ReadMeta::publish_time) 保持上一次常规写入的时间。
+    ///
+    /// # 安全性
+    ///
+    /// 在守卫读取 `T` 的同时写入它，在 Rust 内存模型下是数据竞争；版本与校验和以原子方式更新，
+    /// 不属于该竞争。调用者必须确保该单元的每个读者都能容忍撕裂的值，
+    /// 例如 `T` 是普通的 `repr(C)` 结构体，其字段只会被复制出来并各自独立使用。
+    /// 启用 `checksum` 特性时，在 `f` 运行期间创建的守卫可能对撕裂的值计算哈希，
+    /// 并在调试构建中将其报告为已损坏。
+    pub unsafe fn write_in_place_dirty<F, R>(&mut self, f: F) -> R
+    where
+        T: Copy,
+        F: FnOnce(&mut T) -> R,
+    {
+        self.collect_garbage();
+        let curr_val = hot_path! {
+            let curr_val = self.shared.current.load(Ordering::Acquire);
+            self.shared.current.swap(curr_val | LOCKED, Ordering::AcqRel);
+            curr_val
+        };
+        self.maybe_snapshot((curr_val & PTR_MASK) as *mut Node<T>);
+        let mut guard = InPlaceGuard {
+            cell: self,
+            locked_val: curr_val,
+            dirty: true,
+        };
+        f(&mut guard)
+    }
+
+    /// Exchange the current values of two cells, publishing a new version in each.
+    ///
+    /// When neither cell has readers, the values are swapped in place while
+    /// both are locked. Otherwise both values are cloned and published through
+    /// COW, one cell after the other: this never waits for readers, so it
+    /// cannot deadlock with a reader holding a guard on one cell while it
+    /// reads the other. A reader reading both cells between the two
+    /// publications may observe the same value in both.
+    ///
+    /// 交换两个单元的当前值，并在各自发布新版本。
+    ///
+    /// 当两个单元都没有读者时，在同时锁定两者的情况下原地交换值。否则克隆两个值并依次通过 COW
+    /// 发布到各个单元：该操作从不等待读者，因此不会与在一个单元上持有守卫、同时读取另一个单元的读者死锁。
+    /// 在两次发布之间读取两个单元的读者可能在两者中观察到同一个值。
+    pub fn swap_with(&mut self, other: &mut RetroCell<T>)
+    where
+        T: Clone,
+    {
+        if let WriteOutcome::InPlace(mut this) = self.try_write() {
+            match other.try_write() {
+                WriteOutcome::InPlace(mut that) => {
+                    mem::swap(&mut *this, &mut *that);
+                    return;
+                }
+                WriteOutcome::Congested(_) => InPlaceGuard::unlock(this),
+            }
+        }
+        let (this, that) = (self.current().clone(), other.current().clone());
+        self.install_cow(that);
+        other.install_cow(this);
+    }
+
+    /// Perform a COW update, reporting budget exhaustion through the failure
+    /// policy instead of waiting.
+    ///
+    /// In double-buffer mode, a COW write must reclaim the retro node; if that
+    /// node still has active readers this returns [`Error::BudgetExhausted`]
+    /// before running `f`, leaving the retro value published. Other modes
+    /// never fail.
+    ///
+    /// 执行 COW 更新，通过失败策略报告预算耗尽而非等待。
+    ///
+    /// 在双缓冲模式下，COW 写入必须回收回溯节点；若该节点仍有活跃读者，
+    /// 则在运行 `f` 之前返回 [`Error::BudgetExhausted`]，回溯值保持发布。其他模式不会失败。
+    pub fn write_cow_checked<F, R>(&mut self, f: F) -> Result<R, Error>
+    where
+        T: Clone,
+        F: FnOnce(&mut T) -> R,
+    {
+        self.collect_garbage();
+        self.congested = false;
+        // Take the node before running `f`, so a failed write has no effect
+        // 在运行 `f` 之前获取节点，使失败的写入没有任何效果
+        let spare = match self.try_spare_node() {
+            Ok(spare) => spare,
+            Err(err) => return Err(self.shared.hooks.failure.apply(err)),
+        };
+        let mut value = self.current().clone();
+        let result = f(&mut value);
+        let stamp = self.next_stamp();
+        self.publish_node(fill_node(spare, value, stamp));
+        Ok(result)
+    }
+
+    /// Publish `value` as the new current value, reporting which path was
+    /// taken.
+    ///
+    /// Moves it into the current node if no reader holds it, otherwise
+    /// installs it in a new node (COW without the clone). Never clones the
+    /// old value.
+    ///
+    /// 将 `value` 发布为新的当前值，并报告所采用的路径。
+    ///
+    /// 若当前节点没有读者，则将值移入该节点；否则将其安装到新节点中（无需克隆的 COW）。
+    /// 从不克隆旧值。
+    pub fn store(&mut self, value: T) -> WritePath {
+        match self.try_write() {
+            WriteOutcome::InPlace(mut guard) => {
+                *guard = value;
+                WritePath::InPlace
+            }
+            WriteOutcome::Congested(writer) => {
+                writer.cell.install(value);
+                WritePath::Cow
+            }
+        }
+    }
+
+    /// Publish `value` and hand the previous value back to the writer, e.g. to
+    /// recycle the buffers it owns.
+    ///
+    /// The value is exchanged in place once readers of the current version
+    /// have drained, so the previous value never becomes the retro value.
+    /// No `Clone` is required.
+    ///
+    /// 发布 `value` 并将先前的值交还给写入者，例如用于复用其拥有的缓冲区。
+    ///
+    /// 在当前版本的读者排空后原地交换值，因此先前的值不会成为回溯值。无需 `Clone`。
+    pub fn replace(&mut self, value: T) -> T {
+        mem::replace(&mut *self.write_in_place(), value)
+    }
+
+    /// Publish `value` and return the previous value, without waiting for
+    /// readers.
+    ///
+    /// If no reader holds the previous value it is moved out in place.
+    /// Otherwise `value` is published through COW and the previous value,
+    /// which stays readable as the retro value, is cloned: `T: Clone` is only
+    /// used on that path.
+    ///
+    /// 发布 `value` 并返回先前的值，不等待读者。
+    ///
+    /// 若先前的值没有读者持有，则原地将其移出。否则通过 COW 发布 `value`，
+    /// 并克隆仍可作为回溯值读取的先前值：`T: Clone` 仅在该路径上使用。
+    pub fn swap(&mut self, value: T) -> T
+    where
+        T: Clone,
+    {
+        match self.try_write() {
+            WriteOutcome::InPlace(mut guard) => mem::replace(&mut *guard, value),
+            WriteOutcome::Congested(writer) => {
+                let old = writer.cell.current().clone();
+                writer.cell.install(value);
+                old
+            }
+        }
+    }
+
+    /// [`swap`](Self::swap) in the default value. `T: Clone` is only used
+    /// when a reader holds the current value.
+    ///
+    /// 以默认值执行 [`swap`](Self::swap)。`T: Clone` 仅在有读者持有当前值时使用。
+    #[inline]
+    pub fn take(&mut self) -> T
+    where
+        T: Clone + Default,
+    {
+        self.swap(T::default())
+    }
+
+    /// Publish `value` like [`store`](Self::store), unless it equals the current
+    /// value: then no version is published and no reader is woken. Returns
+    /// whether `value` was published.
+    ///
+    /// 与 [`store`](Self::store) 相同地发布 `value`，除非它等于当前值：此时不发布版本，
+    /// 也不唤醒任何读者。返回 `value` 是否被发布。
+    pub fn write_if_changed(&mut self, value: T) -> bool
+    where
+        T: PartialEq,
+    {
+        if *self.current() == value {
+            return false;
+        }
+        self.store(value);
+        true
+    }
+
+    /// Publish `new` only if the current value equals `expected`.
+    ///
+    /// On mismatch nothing is published and a guard on the actual current
+    /// value is returned.
+    ///
+    /// 仅当当前值等于 `expected` 时发布 `new`。
+    ///
+    /// 不相等时不发布任何内容，并返回实际当前值上的守卫。
+    pub fn compare_exchange(&mut self, expected: &T, new: T) -> Result<(), Ref<'_, T>>
+    where
+        T: PartialEq,
+    {
+        if self.current() != expected {
+            let curr_ptr = (self.shared.current.load(Ordering::Acquire) & PTR_MASK) as *mut Node<T>;
+            let node = unsafe { &*curr_ptr };
+            // Only the writer could unpublish or lock it, so no validation is needed
+            // 只有写入者能取消发布或锁定它，因此无需验证
+            node.reader_count.retain();
+            return Err(Ref::new(&self.shared, node));
+        }
+        self.store(new);
+        Ok(())
+    }
+
+    /// Apply `f` only if the cell is still at `expected`, e.g. the version a
+    /// producer based its update on; otherwise return the current version.
+    ///
+    /// Writes in place when no reader holds the current value, otherwise
+    /// through COW.
+    ///
+    /// 仅当单元仍处于 `expected` 版本（例如生产者据以计算更新的版本）时应用 `f`；
+    /// 否则返回当前版本。
+    ///
+    /// 没有读者持有当前值时原地写入，否则通过 COW 写入。
+    pub fn compare_and_update<F, R>(&mut self, expected: Version, f: F) -> Result<R, Version>
+    where
+        T: Clone,
+        F: FnOnce(&mut T) -> R,
+    {
+        if self.version != expected {
+            return Err(self.version);
+        }
+        Ok(match self.try_write() {
+            WriteOutcome::InPlace(mut guard) => f(&mut guard),
+            WriteOutcome::Congested(writer) => writer.perform_cow(f),
+        })
+    }
+
+    /// Apply `f` only if `pred` holds for the current value, returning `None`
+    /// otherwise. The writer is borrowed for both steps, so no other write
+    /// can interleave between the check and the publication.
+    ///
+    /// 仅当当前值满足 `pred` 时应用 `f`，否则返回 `None`。
+    /// 写入者在两个步骤中均被借用，因此检查与发布之间不会插入其他写入。
+    pub fn update_if<P, F, R>(&mut self, pred: P, f: F) -> Option<R>
+    where
+        T: Clone,
+        P: FnOnce(&T) -> bool,
+        F: FnOnce(&mut T) -> R,
+    {
+        if !pred(self.current()) {
+            return None;
+        }
+        Some(match self.try_write() {
+            WriteOutcome::InPlace(mut guard) => f(&mut guard),
+            WriteOutcome::Congested(writer) => writer.perform_cow(f),
+        })
+    }
+
+    /// Publish `value` as `version` through the same `path` as the original
+    /// publication (in-place replication waits for this cell's readers)
+    ///
+    /// 以与原始发布相同的 `path` 将 `value` 发布为 `version`（原地复制会等待本单元的读者）
+    pub(crate) fn replicate(&mut self, value: T, version: Version, path: WritePath) {
+        self.version = version.prev();
+        match path {
+            WritePath::Cow => {
+                self.collect_garbage();
+                self.install(value);
+            }
+            WritePath::InPlace => *self.write_in_place() = value,
+        }
+    }
+
+    /// Install `data` in a new node and publish it, retiring the current one
+    ///
+    /// 将 `data` 安装到新节点并发布，同时退役当前节点
+    pub(crate) fn install(&mut self, data: T) {
+        let stamp = self.next_stamp();
+        let new_node = self.acquire_node(data, stamp);
+        self.publish_node(new_node);
+    }
+
+    /// Publish a stamped node, retiring the current one
+    ///
+    /// 发布一个已标记的节点，同时退役当前节点
+    fn publish_node(&mut self, new_node: Box<Node<T>>) {
+        let curr_val = self.shared.current.load(Ordering::Acquire);
+        let curr_ptr = (curr_val & PTR_MASK) as *mut Node<T>;
+        self.snapshot_taken();
+
+        #[cfg(feature = "checksum")]
+        new_node.seal(&self.shared.hooks);
+        let new_ptr = Box::into_raw(new_node);
+
+        // Publish 'previous' before 'current' so a reader never observes the
+        // new current paired with a stale retro (see `Reader::read_pair`)
+        // 先发布 'previous' 再发布 'current'，确保读者不会观察到新的当前值
+        // 与过期的回溯值配对（参见 `Reader::read_pair`）
+        self.shared.previous.store(curr_ptr, Ordering::Release);
+        self.garbage.push_back(curr_ptr);
+
+        self.shared.current.swap(new_ptr as usize, Ordering::Release);
+
+        // COW complete. Wake up blocked readers
+        // COW 完成。唤醒阻塞的读者
+        self.shared.notifier.advance_and_wake();
+        self.published(WritePath::Cow);
+    }
+
     /// Write in-place after locking the latest data (block until locked)
     ///
     /// 锁定最新数据后写入（阻塞直到锁定）
@@ -242,14 +1551,64 @@ impl<T> RetroCell<T> {
     }
 }
 
+/// Resumes reclamation when a [`RetroCell::with_gc_paused`] scope ends, even
+/// by unwinding
+///
+/// 在 [`RetroCell::with_gc_paused`] 作用域结束时恢复回收，即使是因展开而结束
+struct GcPause<'a, T>(&'a mut RetroCell<T>);
+
+impl<'a, T> Drop for GcPause<'a, T> {
+    fn drop(&mut self) {
+        self.0.gc_paused -= 1;
+        // Catching up runs user hooks (sink, reclaim): not while unwinding
+        // 追赶进度会运行用户钩子（归档、回收）：展开期间不运行
+        if !std::thread::panicking() {
+            self.0.collect_garbage();
+        }
+    }
+}
+
+/// Fill a spare node with new data, or allocate one if there is none
+///
+/// 用新数据填充备用节点；若没有备用节点则分配新节点
+#[inline]
+fn fill_node<T>(spare: Option<Box<Node<T>>>, data: T, stamp: Stamp) -> Box<Node<T>> {
+    match spare {
+        Some(node) => {
+            unsafe { *node.data.get() = data };
+            unsafe { node.set_stamp(stamp) };
+            node
+        }
+        None => Box::new(Node::new(data, stamp)),
+    }
+}
+
+/// Hand an aged-out node's value to the history sink
+///
+/// 将老化淘汰节点的值交给历史接收器
+#[inline]
+fn archive<T>(sink: &mut dyn HistorySink<T>, node: &Node<T>) {
+    let version = node.version();
+    sink.archive(version, unsafe { &*node.data.get() });
+}
+
 impl<T> Drop for RetroCell<T> {
     #[inline]
     fn drop(&mut self) {
+        self.gc_paused = 0;
         self.collect_garbage();
-        while let Some(ptr) = self.garbage.pop_front() {
-            unsafe {
-                drop(Box::from_raw(ptr));
-            }
+        // Readers may still hold guards on retired nodes (or read 'previous'),
+        // so hand them over to the shared state instead of freeing them here
+        // 读者可能仍持有已退役节点的守卫（或读取 'previous'），
+        // 因此将其移交给共享状态，而不是在此释放
+        let orphans = unsafe { &mut *self.shared.orphans.get() };
+        orphans.extend(self.garbage.drain());
+        // Without a writer the value can never change again; unless `freeze`
+        // marked the cell frozen first, the writer was just dropped
+        // 没有写入者后，值再也不会改变；除非 `freeze` 已先将单元标记为封存，否则写入者只是被析构
+        if !self.shared.frozen.swap(true, Ordering::AcqRel) {
+            self.shared.closed.store(true, Ordering::Release);
         }
+        self.shared.wake_tasks();
     }
 }
@@ -231,9 +231,2734 @@ fn test_garbage_collection() {
     assert!(dropped >= 90, "Expected ~99 drops, got {}", dropped);
 }
 
+#[test]
+fn test_garbage_ring_grows_past_capacity() {
+    let (mut cell, reader) = RetroCell::builder().garbage_capacity(2).build(0);
+
+    // Pin more retired versions than the ring was sized for
+    let mut guards = Vec::new();
+    for i in 1..=10 {
+        guards.push(reader.read());
+        cell.write_cow(|v| *v = i);
+    }
+    cell.validate_invariants();
+    let pinned: Vec<i32> = guards.iter().map(|guard| **guard).collect();
+    assert_eq!(pinned, (0..10).collect::<Vec<_>>());
+
+    drop(guards);
+    cell.write_cow(|v| *v = 11);
+    assert_eq!(*reader.read_retro().unwrap(), 10);
+    cell.debug_assert_quiescent();
+}
+
 #[test]
 fn test_no_retro_available() {
     let (_cell, reader) = RetroCell::new(1);
     // No updates yet, so no previous value
     assert!(reader.read_retro().is_none());
 }
+
+// ============================================================================
+// 6. Configuration
+// ============================================================================
+
+#[test]
+fn test_double_buffer_bounds_nodes() {
+    let drop_count = Arc::new(AtomicUsize::new(0));
+    let (mut cell, reader) = RetroCell::builder().double_buffer(true).build(Tracked {
+        _id: 0,
+        counter: drop_count.clone(),
+    });
+
+    for i in 1..=10 {
+        cell.write_cow(|t| {
+            *t = Tracked { _id: i, counter: drop_count.clone() };
+        });
+    }
+
+    // Each COW clones the current value and overwrites the recycled retro one.
+    // Only the current and retro values stay alive: everything else was dropped.
+    let created = 1 + 10 * 2;
+    assert_eq!(drop_count.load(Ordering::SeqCst), created - 2);
+    assert_eq!(reader.read()._id, 10);
+    assert_eq!(reader.read_retro().unwrap()._id, 9);
+}
+
+#[test]
+fn test_double_buffer_waits_for_retro_readers() {
+    let (mut cell, reader) = RetroCell::builder().double_buffer(true).build(0);
+    cell.write_cow(|v| *v = 1);
+
+    let barrier = Arc::new(Barrier::new(2));
+    let b2 = barrier.clone();
+    let r2 = reader.clone();
+    let t = thread::spawn(move || {
+        let retro = r2.read_retro().unwrap();
+        b2.wait();
+        thread::sleep(Duration::from_millis(100));
+        assert_eq!(*retro, 0);
+    });
+
+    barrier.wait();
+    let start = std::time::Instant::now();
+    cell.write_cow(|v| *v = 2);
+    assert!(start.elapsed() >= Duration::from_millis(50), "Should have blocked");
+    t.join().unwrap();
+
+    assert_eq!(*reader.read(), 2);
+    assert_eq!(*reader.read_retro().unwrap(), 1);
+}
+
+#[test]
+fn test_double_buffer_reclaims_retro_not_older_nodes() {
+    let (mut cell, reader) = RetroCell::builder().double_buffer(true).build(0);
+    let pinned = reader.read();
+    // Retire two versions while the oldest one is still held
+    cell.with_gc_paused(|cell| {
+        cell.write_cow(|v| *v = 1);
+        cell.write_cow(|v| *v = 2);
+    });
+
+    // Must recycle the idle retro node (1), not wait on the pinned one (0)
+    cell.write_cow(|v| *v = 3);
+    assert_eq!(*pinned, 0);
+    assert_eq!(*reader.read(), 3);
+    assert_eq!(*reader.read_retro().unwrap(), 2);
+}
+
+#[test]
+fn test_double_buffer_runs_reclaim_hook() {
+    static RECLAIMED: AtomicUsize = AtomicUsize::new(0);
+
+    let (mut cell, reader) = RetroCell::builder()
+        .double_buffer(true)
+        .reclaim_with(|v: &mut Vec<u8>| {
+            RECLAIMED.fetch_add(v.len(), Ordering::Relaxed);
+            *v = Vec::new();
+        })
+        .build(vec![0]);
+    cell.write_cow(|v| v.push(1));
+    assert_eq!(RECLAIMED.load(Ordering::Relaxed), 0);
+
+    // Reclaiming the retro node (`[0]`) for the copy runs the hook on it
+    cell.write_cow(|v| v.push(2));
+    assert_eq!(RECLAIMED.load(Ordering::Relaxed), 1);
+    assert_eq!(*reader.read(), [0, 1, 2]);
+    assert_eq!(*reader.read_retro().unwrap(), [0, 1]);
+}
+
+#[test]
+fn test_read_pair_is_consistent() {
+    let (mut cell, reader) = RetroCell::new(0u64);
+    cell.write_cow(|v| *v = 1);
+
+    let r2 = reader.clone();
+    let t = thread::spawn(move || {
+        for _ in 0..10_000 {
+            let (current, retro) = r2.read_pair();
+            let retro = retro.expect("retro exists after the first write");
+            assert_eq!(*current, *retro + 1);
+        }
+    });
+
+    for _ in 0..10_000 {
+        cell.write_cow(|v| *v += 1);
+    }
+    t.join().unwrap();
+}
+
+#[test]
+fn test_snapshot_interval_retains_in_place_history() {
+    let (mut cell, reader) = RetroCell::builder()
+        .snapshot_interval(Duration::from_millis(50))
+        .build(0);
+
+    // First in-place write always snapshots the value it overwrites
+    *cell.write_in_place() = 1;
+    assert_eq!(*reader.read_retro().unwrap(), 0);
+
+    // Within the interval, no new snapshot is taken
+    *cell.write_in_place() = 2;
+    assert_eq!(*reader.read_retro().unwrap(), 0);
+
+    thread::sleep(Duration::from_millis(60));
+    *cell.write_in_place() = 3;
+    assert_eq!(*reader.read_retro().unwrap(), 2);
+    assert_eq!(*reader.read(), 3);
+}
+
+#[test]
+fn test_with_gc_paused_keeps_old_versions() {
+    let drop_count = Arc::new(AtomicUsize::new(0));
+    let (mut cell, _reader) = RetroCell::new(Tracked {
+        _id: 0,
+        counter: drop_count.clone(),
+    });
+
+    cell.with_gc_paused(|cell| {
+        for i in 1..=10 {
+            cell.write_cow(|t| *t = Tracked { _id: i, counter: drop_count.clone() });
+        }
+        // Only the clones overwritten inside the closures were dropped:
+        // no retired version was recycled.
+        assert_eq!(drop_count.load(Ordering::SeqCst), 10);
+    });
+
+    // Catching up moved old versions to the pool; the next writes recycle them
+    for i in 11..=20 {
+        cell.write_cow(|t| *t = Tracked { _id: i, counter: drop_count.clone() });
+    }
+    assert!(drop_count.load(Ordering::SeqCst) > 20);
+}
+
+#[test]
+fn test_with_gc_paused_resumes_after_panic() {
+    use std::panic::{AssertUnwindSafe, catch_unwind};
+
+    let drop_count = Arc::new(AtomicUsize::new(0));
+    let (mut cell, _reader) = RetroCell::new(Tracked {
+        _id: 0,
+        counter: drop_count.clone(),
+    });
+
+    let result = catch_unwind(AssertUnwindSafe(|| {
+        cell.with_gc_paused(|cell| {
+            cell.write_cow(|t| t._id = 1);
+            panic!("scope failed");
+        })
+    }));
+    assert!(result.is_err());
+    assert_eq!(drop_count.load(Ordering::SeqCst), 0);
+
+    // Reclamation is no longer paused: retired versions get recycled
+    for i in 2..=10 {
+        cell.write_cow(|t| *t = Tracked { _id: i, counter: drop_count.clone() });
+    }
+    assert!(drop_count.load(Ordering::SeqCst) > 9);
+}
+
+#[test]
+fn test_try_new() {
+    let (mut cell, reader) = RetroCell::try_new(String::from("a")).expect("allocation succeeds");
+    cell.write_cow(|s| s.push('b'));
+    assert_eq!(*reader.read(), "ab");
+
+    let (_cell, reader) = RetroCell::builder()
+        .double_buffer(true)
+        .try_build(1)
+        .expect("allocation succeeds");
+    assert_eq!(*reader.read(), 1);
+}
+
+#[test]
+fn test_failure_policy_budget_exhausted() {
+    use retro_cell::{Error, FailurePolicy};
+    use std::sync::atomic::AtomicBool;
+
+    static HOOKED: AtomicBool = AtomicBool::new(false);
+
+    let (mut cell, reader) = RetroCell::builder()
+        .double_buffer(true)
+        .failure_policy(FailurePolicy::Hook(|err| {
+            assert_eq!(*err, Error::BudgetExhausted);
+            HOOKED.store(true, Ordering::SeqCst);
+        }))
+        .build(0);
+    cell.write_cow(|v| *v = 1);
+
+    let retro = reader.read_retro().unwrap();
+    assert_eq!(cell.write_cow_checked(|v| *v = 2), Err(Error::BudgetExhausted));
+    assert!(HOOKED.load(Ordering::SeqCst));
+    drop(retro);
+
+    assert_eq!(cell.write_cow_checked(|v| *v = 2), Ok(()));
+    assert_eq!(*reader.read(), 2);
+}
+
+#[test]
+fn test_write_cow_checked_failure_keeps_retro() {
+    let (mut cell, reader) = RetroCell::builder().double_buffer(true).build(0);
+    cell.write_cow(|v| *v = 1);
+
+    let retro = reader.read_retro().unwrap();
+    let mut ran = false;
+    assert!(cell.write_cow_checked(|_| ran = true).is_err());
+    assert!(!ran);
+    assert_eq!(cell.version().get(), 1);
+    // The busy retro node is still published
+    assert_eq!(*reader.read_retro().unwrap(), 0);
+    drop(retro);
+
+    assert_eq!(cell.write_cow_checked(|v| *v = 2), Ok(()));
+    assert_eq!(*reader.read(), 2);
+    assert_eq!(*reader.read_retro().unwrap(), 1);
+}
+
+#[test]
+fn test_failure_policy_timeouts() {
+    use retro_cell::{Error, FailurePolicy};
+
+    static TIMEOUTS: AtomicUsize = AtomicUsize::new(0);
+
+    let (mut cell, reader) = RetroCell::builder()
+        .failure_policy(FailurePolicy::Hook(|err| {
+            assert_eq!(*err, Error::TimedOut);
+            TIMEOUTS.fetch_add(1, Ordering::SeqCst);
+        }))
+        .build(0);
+
+    let guard = reader.read();
+    match cell.try_write() {
+        WriteOutcome::Congested(writer) => {
+            let writer = writer.wait_in_place_timeout(Duration::ZERO).err().unwrap();
+            assert_eq!(TIMEOUTS.load(Ordering::SeqCst), 1);
+            writer.perform_cow(|v| *v = 1);
+        }
+        WriteOutcome::InPlace(_) => panic!("expected congestion"),
+    }
+    drop(guard);
+
+    // Falling back to COW is not a failure
+    let guard = reader.read();
+    cell.write_adaptive(Duration::ZERO, |v| *v = 2);
+    assert_eq!(TIMEOUTS.load(Ordering::SeqCst), 1);
+    drop(guard);
+
+    let lock = cell.write_in_place();
+    assert!(reader.try_read_for(Duration::ZERO).err().is_some());
+    assert_eq!(TIMEOUTS.load(Ordering::SeqCst), 2);
+    // A retro value is not a failure either
+    match reader.try_read() {
+        ReadResult::Blocked(blocked) => assert!(blocked.wait_or_retro(Duration::ZERO).is_ok()),
+        ReadResult::Success(_) => panic!("expected a blocked read"),
+    }
+    assert_eq!(TIMEOUTS.load(Ordering::SeqCst), 2);
+    drop(lock);
+}
+
+#[test]
+#[should_panic(expected = "node budget exhausted")]
+fn test_failure_policy_panic() {
+    let (mut cell, reader) = RetroCell::builder()
+        .double_buffer(true)
+        .failure_policy(retro_cell::FailurePolicy::Panic)
+        .build(0);
+    cell.write_cow(|v| *v = 1);
+    let _retro = reader.read_retro().unwrap();
+    let _ = cell.write_cow_checked(|v| *v = 2);
+}
+
+#[test]
+fn test_swap_with() {
+    let (mut front, front_reader) = RetroCell::new(vec![1]);
+    let (mut back, back_reader) = RetroCell::new(vec![2, 2]);
+
+    front.swap_with(&mut back);
+    assert_eq!(*front_reader.read(), vec![2, 2]);
+    assert_eq!(*back_reader.read(), vec![1]);
+}
+
+#[test]
+fn test_swap_with_held_guard() {
+    let (mut front, front_reader) = RetroCell::new(vec![1]);
+    let (mut back, back_reader) = RetroCell::new(vec![2, 2]);
+
+    // A guard on `back` must not make the swap wait for it
+    let guard = back_reader.read();
+    front.swap_with(&mut back);
+    assert_eq!(*guard, vec![2, 2]);
+    assert_eq!(*front_reader.read(), vec![2, 2]);
+    assert_eq!(*back_reader.read(), vec![1]);
+    drop(guard);
+
+    // Both cells keep the value they had as the retro value
+    assert_eq!(*front_reader.read_retro().unwrap(), vec![1]);
+    assert_eq!(*back_reader.read_retro().unwrap(), vec![2, 2]);
+    assert_eq!((front.version().get(), back.version().get()), (1, 1));
+}
+
+#[test]
+fn test_read_result_conversions() {
+    let (mut cell, reader) = RetroCell::new(1);
+    assert_eq!(*reader.try_read().ok().unwrap(), 1);
+    assert!(reader.try_read().err().is_none());
+
+    let guard = cell.write_in_place();
+    let result: Result<_, _> = reader.try_read().into();
+    assert!(result.is_err());
+    assert!(reader.try_read().ok().is_none());
+    drop(guard);
+}
+
+#[test]
+fn test_ref_leak_outlives_cell() {
+    let (mut cell, reader) = RetroCell::new(String::from("config"));
+    let leaked: &'static String = retro_cell::Ref::leak(reader.read());
+    assert!(retro_cell::leaked_pins() >= 1);
+
+    // The leaked version is retired and survives GC, the writer and readers
+    for _ in 0..4 {
+        cell.write_cow(|s| s.push('!'));
+    }
+    drop(cell);
+    drop(reader);
+    assert_eq!(leaked, "config");
+}
+
+#[cfg(feature = "checksum")]
+#[test]
+fn test_checksum_verifies_published_versions() {
+    use std::collections::hash_map::DefaultHasher;
+
+    let (mut cell, reader) = RetroCell::builder()
+        .checksum_with::<DefaultHasher>()
+        .build(vec![1]);
+    cell.write_in_place().push(2);
+    cell.write_cow(|v| v.push(3));
+    assert_eq!(*reader.read(), vec![1, 2, 3]);
+    assert_eq!(*reader.read_retro().unwrap(), vec![1, 2]);
+}
+
+// Verification only runs in debug builds
+#[cfg(all(feature = "checksum", debug_assertions))]
+#[test]
+#[should_panic(expected = "published value corrupted")]
+fn test_checksum_detects_corruption() {
+    // Simulate corruption by changing what the checksum observes
+    static SALT: AtomicUsize = AtomicUsize::new(0);
+    let (_cell, reader) = RetroCell::builder()
+        .checksum(|v: &u64| *v ^ SALT.load(Ordering::SeqCst) as u64)
+        .build(7);
+    assert_eq!(*reader.read(), 7);
+    SALT.store(1, Ordering::SeqCst);
+    let _ = reader.read();
+}
+
+#[cfg(all(feature = "checksum", debug_assertions))]
+#[test]
+fn test_checksum_failure_hook() {
+    use retro_cell::{Error, FailurePolicy};
+
+    static SALT: AtomicUsize = AtomicUsize::new(0);
+    static CORRUPTED: AtomicUsize = AtomicUsize::new(0);
+
+    let (_cell, reader) = RetroCell::builder()
+        .checksum(|v: &u64| *v ^ SALT.load(Ordering::SeqCst) as u64)
+        .failure_policy(FailurePolicy::Hook(|err| {
+            assert!(matches!(err, Error::Corrupted { .. }));
+            CORRUPTED.fetch_add(1, Ordering::SeqCst);
+        }))
+        .build(7);
+    SALT.store(1, Ordering::SeqCst);
+    // The hook observes the mismatch and the read goes on
+    assert_eq!(*reader.read(), 7);
+    assert_eq!(CORRUPTED.load(Ordering::SeqCst), 1);
+}
+
+#[test]
+fn test_audit_log_records_publications() {
+    use retro_cell::WritePath;
+
+    let (mut cell, _reader) = RetroCell::builder().audit_log(2).build(0);
+    assert_eq!(cell.version().get(), 0);
+
+    cell.write_cow(|v| *v = 1);
+    cell.tag("alice").write_cow(|v| *v = 2);
+    *cell.tag("bob").write_in_place() = 3;
+    assert_eq!(cell.version().get(), 3);
+
+    // Bounded: only the last two publications are kept
+    let records: Vec<_> = cell.audit().collect();
+    assert_eq!(records.len(), 2);
+    assert_eq!(records[0].version.get(), 2);
+    assert_eq!(records[0].path, WritePath::Cow);
+    assert_eq!(records[0].tag.as_deref(), Some("alice"));
+    assert_eq!(records[1].version.get(), 3);
+    assert_eq!(records[1].path, WritePath::InPlace);
+    assert_eq!(records[1].tag.as_deref(), Some("bob"));
+    assert!(records[0].timestamp <= records[1].timestamp);
+
+    // Disabled by default
+    let (mut plain, _reader) = RetroCell::new(0);
+    plain.write_cow(|v| *v = 1);
+    assert_eq!(plain.audit().len(), 0);
+}
+
+#[cfg(feature = "coalesce")]
+#[test]
+fn test_nested_reads_coalesce() {
+    let (mut cell, reader) = RetroCell::new(1);
+    let outer = reader.read_nested();
+    let plain = reader.read();
+    assert_eq!(cell.active_readers(), 2);
+    drop(plain);
+
+    thread::scope(|s| {
+        let writer = s.spawn(|| *cell.write_in_place() = 2);
+        thread::sleep(Duration::from_millis(50));
+
+        // The writer waits for 'outer'; a nested read shares its retain
+        // instead of blocking
+        let nested = reader.read_nested();
+        assert_eq!(*nested, 1);
+        drop(nested);
+        assert!(!writer.is_finished());
+
+        drop(outer);
+        writer.join().unwrap();
+    });
+    assert_eq!(*reader.read(), 2);
+}
+
+#[cfg(feature = "mmap")]
+#[test]
+fn test_mmap_slice_storage() {
+    use retro_cell::MmapSlice;
+
+    let (mut cell, reader) = RetroCell::builder()
+        .reclaim_with(MmapSlice::release)
+        .build(MmapSlice::filled(1 << 20, 0u32));
+    for i in 1..=4 {
+        cell.write_cow(|table| table[i] = i as u32);
+    }
+    let table = reader.read();
+    assert_eq!(table.len(), 1 << 20);
+    assert_eq!(&table[..5], &[0, 1, 2, 3, 4]);
+    assert_eq!(reader.read_retro().unwrap()[4], 0);
+
+    let mut released = MmapSlice::from_slice(&[1u8, 2, 3]);
+    MmapSlice::release(&mut released);
+    assert!(released.is_empty());
+}
+
+#[cfg(all(feature = "page-cow", target_os = "linux"))]
+#[test]
+fn test_cow_pages() {
+    use retro_cell::CowPages;
+
+    // 256 pages of 4 KiB (or fewer, larger pages)
+    let len = 1 << 18;
+    let (mut cell, reader) = RetroCell::new(CowPages::filled(len, 7u32));
+    assert_eq!(reader.read().dirty_pages(), 0);
+
+    // Each version copies only the pages modified since the base
+    let held = reader.read();
+    cell.write_cow(|table| table.set(3, 1));
+    cell.write_cow(|table| table.slice_mut(len - 2..len).fill(2));
+    assert!(held.iter().all(|&v| v == 7));
+    drop(held);
+
+    let table = reader.read();
+    assert_eq!((table[2], table[3], table[len - 3], table[len - 1]), (7, 1, 7, 2));
+    assert_eq!(table.dirty_pages(), 2);
+    let retro = reader.read_retro().unwrap();
+    assert_eq!((retro[3], retro[len - 1]), (1, 7));
+    assert_eq!(retro.dirty_pages(), 1);
+
+    // A clone keeps the modified pages; rebasing folds them into a new base
+    let mut copy = (*table).clone();
+    assert_eq!((copy[3], copy[len - 1], copy.dirty_pages()), (1, 2, 2));
+    copy.rebase();
+    assert_eq!((copy[3], copy[len - 1], copy.dirty_pages()), (1, 2, 0));
+    assert_eq!(CowPages::from_slice(&[1u8, 2]).clone()[..], [1, 2]);
+    assert!(CowPages::<u64>::filled(0, 0).clone().is_empty());
+}
+
+#[test]
+fn test_local_readers_under_churn() {
+    let (mut cell, reader) = RetroCell::new(vec![0usize; 16]);
+    let factory = retro_cell::ReaderFactory::from(reader);
+    let stop = std::sync::atomic::AtomicBool::new(false);
+
+    thread::scope(|s| {
+        for _ in 0..4 {
+            s.spawn(|| {
+                let mut local = factory.register();
+                while !stop.load(Ordering::Relaxed) {
+                    // Every write keeps all elements equal; a torn read would not
+                    let v = local.read();
+                    assert!(v.iter().all(|&x| x == v[0]));
+                    drop(v);
+                    if let Some(retro) = local.read_retro() {
+                        assert!(retro.iter().all(|&x| x == retro[0]));
+                    }
+                }
+            });
+        }
+
+        for i in 1..=200 {
+            if i % 2 == 0 {
+                cell.write_cow(|v| v.iter_mut().for_each(|x| *x = i));
+            } else {
+                cell.write_in_place().iter_mut().for_each(|x| *x = i);
+            }
+        }
+        stop.store(true, Ordering::Relaxed);
+    });
+
+    // Slots are recycled after the handles are dropped
+    let mut local = factory.register();
+    assert_eq!(local.read()[0], 200);
+}
+
+#[test]
+fn test_reader_pin() {
+    let (mut cell, reader) = RetroCell::new(vec![0usize; 16]);
+    let stop = std::sync::atomic::AtomicBool::new(false);
+
+    thread::scope(|s| {
+        for _ in 0..2 {
+            let reader = reader.clone();
+            let stop = &stop;
+            s.spawn(move || {
+                let mut pinned = reader.pin();
+                while !stop.load(Ordering::Relaxed) {
+                    let v = pinned.read();
+                    assert!(v.iter().all(|&x| x == v[0]));
+                }
+            });
+        }
+
+        for i in 1..=100 {
+            if i % 2 == 0 {
+                cell.write_cow(|v| v.iter_mut().for_each(|x| *x = i));
+            } else {
+                cell.write_in_place().iter_mut().for_each(|x| *x = i);
+            }
+        }
+        stop.store(true, Ordering::Relaxed);
+    });
+
+    assert_eq!(reader.pin().read()[0], 100);
+}
+
+#[test]
+fn test_guard_debug_shows_cell_id() {
+    let (mut a, reader_a) = RetroCell::new(1);
+    let (_b, reader_b) = RetroCell::new(1);
+
+    let guard_a = format!("{:?}", reader_a.read());
+    let guard_b = format!("{:?}", reader_b.read());
+    assert!(guard_a.starts_with("Ref { value: 1, cell: "));
+    if cfg!(debug_assertions) {
+        assert_ne!(guard_a, guard_b);
+    }
+    assert!(format!("{:?}", a.write_in_place()).starts_with("InPlaceGuard { value: 1, cell: "));
+}
+
+#[test]
+fn test_invariant_checks_pass_when_quiescent() {
+    let (mut cell, reader) = RetroCell::builder().snapshot_interval(Duration::ZERO).build(vec![0]);
+    cell.debug_assert_quiescent();
+    for i in 1..=8 {
+        if i % 3 == 0 {
+            cell.write_in_place().push(i);
+        } else {
+            cell.write_cow(|v| v.push(i));
+        }
+        cell.debug_assert_quiescent();
+    }
+    let guard = reader.read();
+    cell.assert_unlocked();
+    cell.validate_invariants();
+    drop(guard);
+    cell.assert_no_readers();
+}
+
+#[cfg(debug_assertions)]
+#[test]
+#[should_panic(expected = "reader(s) still hold the retro version")]
+fn test_assert_no_readers_detects_leaked_guard() {
+    let (mut cell, reader) = RetroCell::new(0);
+    cell.write_cow(|v| *v = 1);
+    let _retro = reader.read_retro().unwrap();
+    cell.assert_no_readers();
+}
+
+#[cfg(debug_assertions)]
+#[test]
+#[should_panic(expected = "still locked in place")]
+fn test_assert_unlocked_detects_leaked_lock() {
+    let (mut cell, _reader) = RetroCell::new(0);
+    std::mem::forget(cell.write_in_place());
+    cell.assert_unlocked();
+}
+
+#[test]
+fn test_read_versioned() {
+    let (mut cell, reader) = RetroCell::new(0);
+    let (guard, meta) = reader.read_versioned();
+    assert_eq!((*guard, meta.version.get(), meta.was_retro), (0, 0, false));
+    let first_published = meta.publish_time;
+    drop(guard);
+
+    cell.write_cow(|v| *v = 1);
+    *cell.write_in_place() = 2;
+    let (guard, meta) = reader.read_versioned();
+    assert_eq!((*guard, meta.version, meta.was_retro), (2, cell.version(), false));
+    assert!(meta.publish_time >= first_published);
+    // The crate's own type whatever the clock backend
+    let age: Duration = retro_cell::Instant::now() - meta.publish_time;
+    assert!(age < Duration::from_secs(60));
+    assert_eq!(retro_cell::Ref::version(&reader.read_retro().unwrap()).get(), 0);
+    assert_eq!(reader.current_version(), meta.version);
+    drop(guard);
+
+    // While an in-place write is in progress the retro value is returned
+    let lock = cell.write_in_place();
+    let (guard, meta) = reader.read_versioned();
+    assert_eq!((*guard, meta.version.get(), meta.was_retro), (0, 0, true));
+    assert_eq!(reader.current_version().get(), 2);
+    drop(guard);
+    drop(lock);
+    // Committing the in-place write publishes a version
+    assert_eq!(reader.current_version().get(), 3);
+}
+
+#[test]
+fn test_ref_is_stale() {
+    use retro_cell::Ref;
+
+    let (mut cell, reader) = RetroCell::new(0);
+    let guard = reader.read();
+    assert!(!Ref::is_stale(&guard));
+    drop(guard);
+
+    cell.write_cow(|v| *v = 1);
+    let guard = reader.read();
+    assert!(!Ref::is_stale(&guard));
+    assert!(Ref::is_stale(&reader.read_retro().unwrap()));
+    drop(guard);
+
+    *cell.write_in_place() = 2;
+    let guard = reader.read();
+    cell.write_cow(|v| *v = 3);
+    assert!(Ref::is_stale(&guard));
+}
+
+#[test]
+fn test_ref_is_stale_before_version_published() {
+    use retro_cell::{Reader, Ref};
+    use std::sync::atomic::AtomicU32;
+    use std::sync::Mutex;
+
+    // Reads the cell when an in-place write unlocks it, before the writer
+    // publishes the shared version
+    #[derive(Default)]
+    struct Probe {
+        reader: Mutex<Option<Reader<i32>>>,
+        stale: Mutex<Vec<bool>>,
+    }
+
+    impl retro_cell::Notify for Probe {
+        fn wait(&self, _word: &AtomicU32, _expected: u32) {
+            thread::yield_now();
+        }
+
+        fn wake_all(&self, _word: &AtomicU32) {
+            if let Some(reader) = &*self.reader.lock().unwrap() {
+                let guard = reader.read();
+                self.stale.lock().unwrap().push(Ref::is_stale(&guard));
+            }
+        }
+    }
+
+    let probe = Arc::new(Probe::default());
+    let (mut cell, reader) = RetroCell::builder().notify(probe.clone()).build(0);
+    *probe.reader.lock().unwrap() = Some(reader);
+
+    *cell.write_in_place() = 1;
+    assert_eq!(*probe.stale.lock().unwrap(), [false]);
+    // Break the cycle through the cell's notifier
+    probe.reader.lock().unwrap().take();
+}
+
+#[test]
+fn test_reader_is_locked_and_has_retro() {
+    let (mut cell, reader) = RetroCell::new(0);
+    assert!(!reader.is_locked());
+    assert!(!reader.has_retro());
+
+    let guard = cell.write_in_place();
+    assert!(reader.is_locked());
+    assert!(matches!(reader.try_read(), ReadResult::Blocked(_)));
+    drop(guard);
+    assert!(!reader.is_locked());
+
+    cell.write_cow(|v| *v = 1);
+    assert!(reader.has_retro());
+}
+
+#[test]
+fn test_reader_has_changed() {
+    let (mut cell, reader) = RetroCell::new(0);
+    let seen = retro_cell::Ref::version(&reader.read());
+    assert!(!reader.has_changed(seen));
+
+    *cell.write_in_place() = 1;
+    assert!(reader.has_changed(seen));
+
+    let seen = reader.current_version();
+    assert!(!reader.has_changed(seen));
+    cell.write_cow(|v| *v = 2);
+    assert!(reader.has_changed(seen));
+}
+
+#[test]
+fn test_ref_is_retro_and_is_current() {
+    use retro_cell::Ref;
+
+    let (mut cell, reader) = RetroCell::new(0);
+    let (_other, other_reader) = RetroCell::new(0);
+    cell.write_cow(|v| *v = 1);
+
+    let current = reader.read();
+    let retro = reader.read_retro().unwrap();
+    assert!(!Ref::is_retro(&current) && Ref::is_current(&current, &reader));
+    assert!(Ref::is_retro(&retro) && !Ref::is_current(&retro, &reader));
+    assert!(!Ref::is_current(&current, &other_reader));
+    drop((current, retro));
+
+    // A blocked read falling back to the retro value is marked as such
+    let lock = cell.write_in_place();
+    let ReadResult::Blocked(blocked) = reader.try_read() else {
+        panic!("expected the in-place lock to block");
+    };
+    assert!(Ref::is_retro(&blocked.read_retro().unwrap()));
+    drop(lock);
+}
+
+#[test]
+fn test_ref_clone() {
+    use retro_cell::Ref;
+
+    let (mut cell, reader) = RetroCell::new(vec![1]);
+    let guard = reader.read();
+    let copy = Ref::clone(&guard);
+    // Method syntax still clones the value
+    let value: Vec<i32> = guard.clone();
+    let version = Ref::version(&guard);
+    drop(guard);
+
+    cell.write_cow(|v| v.push(2));
+    assert_eq!(*copy, value);
+    assert_eq!(Ref::version(&copy), version);
+
+    let retro = Ref::clone(&reader.read_retro().unwrap());
+    assert!(Ref::is_retro(&retro));
+    drop((copy, retro));
+
+    // Both retains are released, so an in-place write does not block
+    cell.write_in_place().push(3);
+    assert_eq!(*reader.read(), vec![1, 2, 3]);
+}
+
+#[test]
+fn test_read_with_policy() {
+    use retro_cell::{ReadPolicy, Ref};
+
+    let (mut cell, reader) = RetroCell::new(0);
+    for policy in [ReadPolicy::Latest, ReadPolicy::RetroOk, ReadPolicy::NonBlocking] {
+        assert_eq!(*reader.read_with_policy(policy).ok().unwrap(), 0);
+    }
+
+    cell.write_cow(|v| *v = 1);
+    let lock = cell.write_in_place();
+    assert!(reader.read_with_policy(ReadPolicy::NonBlocking).is_err());
+    let retro = reader.read_with_policy(ReadPolicy::RetroOk).ok().unwrap();
+    assert!(Ref::is_retro(&retro));
+    assert_eq!(*retro, 0);
+    drop(retro);
+
+    let waiter = thread::spawn(move || {
+        let guard = reader.read_with_policy(ReadPolicy::Latest).ok().unwrap();
+        *guard
+    });
+    thread::sleep(Duration::from_millis(10));
+    drop(lock);
+    assert_eq!(waiter.join().unwrap(), 1);
+}
+
+#[test]
+fn test_blocked_reader_wait_timeout() {
+    let (mut cell, reader) = RetroCell::new(0);
+    let lock = cell.write_in_place();
+    let ReadResult::Blocked(blocked) = reader.try_read() else {
+        panic!("expected the in-place lock to block");
+    };
+    // The lock outlives the timeout: the blocked reader comes back
+    let Err(blocked) = blocked.wait_timeout(Duration::from_millis(5)) else {
+        panic!("expected the wait to time out");
+    };
+
+    thread::scope(|s| {
+        s.spawn(move || {
+            let guard = blocked.wait_timeout(Duration::from_secs(10)).ok().unwrap();
+            assert_eq!(*guard, 1);
+        });
+        let mut lock = lock;
+        *lock = 1;
+        thread::sleep(Duration::from_millis(10));
+        drop(lock);
+    });
+}
+
+#[test]
+fn test_blocked_reader_wait_or_retro() {
+    use retro_cell::Ref;
+
+    let (mut cell, reader) = RetroCell::new(0);
+    {
+        // No retro value yet: the blocked reader comes back
+        let _lock = cell.write_in_place();
+        let blocked = reader.try_read().err().unwrap();
+        assert!(blocked.wait_or_retro(Duration::from_millis(1)).is_err());
+    }
+
+    cell.write_cow(|v| *v = 1);
+    let _lock = cell.write_in_place();
+    let blocked = reader.try_read().err().unwrap();
+    let guard = blocked.wait_or_retro(Duration::from_millis(1)).ok().unwrap();
+    assert!(Ref::is_retro(&guard));
+    assert_eq!(*guard, 0);
+}
+
+#[test]
+fn test_read_result_resolve() {
+    let (mut cell, reader) = RetroCell::new(0);
+    assert_eq!(*reader.try_read().resolve(), 0);
+    assert_eq!(*reader.try_read().resolve_or_retro().unwrap(), 0);
+
+    {
+        let _lock = cell.write_in_place();
+        assert!(reader.try_read().resolve_or_retro().is_none());
+    }
+    cell.write_cow(|v| *v = 1);
+
+    let lock = cell.write_in_place();
+    assert_eq!(*reader.try_read().resolve_or_retro().unwrap(), 0);
+    thread::scope(|s| {
+        let waiter = s.spawn(|| *reader.try_read().resolve());
+        thread::sleep(Duration::from_millis(10));
+        drop(lock);
+        assert_eq!(waiter.join().unwrap(), 1);
+    });
+}
+
+#[test]
+fn test_read_wait_free() {
+    use retro_cell::Ref;
+
+    let (mut cell, reader) = RetroCell::new(0);
+    assert_eq!(*reader.read_wait_free().unwrap(), 0);
+    {
+        let _lock = cell.write_in_place();
+        assert!(reader.read_wait_free().is_none());
+    }
+
+    cell.write_cow(|v| *v = 1);
+    let lock = cell.write_in_place();
+    let guard = reader.read_wait_free().unwrap();
+    assert!(Ref::is_retro(&guard));
+    assert_eq!(*guard, 0);
+    drop((guard, lock));
+    assert_eq!(*reader.read_wait_free().unwrap(), 1);
+}
+
+#[test]
+fn test_ref_to_owned_and_as_cow() {
+    use retro_cell::Ref;
+    use std::borrow::Cow;
+
+    #[derive(Clone, Debug, PartialEq)]
+    struct Settings {
+        retries: u32,
+    }
+
+    fn with_retries(settings: Cow<'_, Settings>, retries: u32) -> Cow<'_, Settings> {
+        if settings.retries == retries {
+            return settings;
+        }
+        let mut settings = settings.into_owned();
+        settings.retries = retries;
+        Cow::Owned(settings)
+    }
+
+    let (mut cell, reader) = RetroCell::new(Settings { retries: 1 });
+    let guard = reader.read();
+    assert!(matches!(with_retries(Ref::as_cow(&guard), 1), Cow::Borrowed(_)));
+    assert_eq!(with_retries(Ref::as_cow(&guard), 2).retries, 2);
+    let owned = Ref::to_owned(guard);
+
+    // The guard is released, so the write goes in place
+    assert!(matches!(cell.try_write(), WriteOutcome::InPlace(_)));
+    assert_eq!(owned, Settings { retries: 1 });
+}
+
+#[test]
+fn test_ref_ptr_eq_and_node_id() {
+    use retro_cell::Ref;
+
+    let (mut cell, reader) = RetroCell::new(vec![0u8; 64]);
+    let a = reader.read();
+    let b = reader.read();
+    assert!(Ref::ptr_eq(&a, &b));
+    assert_eq!(Ref::node_id(&a), Ref::node_id(&b));
+
+    cell.write_cow(|v| v[0] = 1);
+    let c = reader.read();
+    assert!(!Ref::ptr_eq(&a, &c));
+    assert_ne!(Ref::node_id(&a), Ref::node_id(&c));
+    assert!(Ref::ptr_eq(&a, &reader.read_retro().unwrap()));
+}
+
+#[test]
+fn test_try_read_for() {
+    let (mut cell, reader) = RetroCell::new(0);
+    assert_eq!(*reader.try_read_for(Duration::ZERO).ok().unwrap(), 0);
+
+    let mut lock = cell.write_in_place();
+    assert!(reader.try_read_for(Duration::from_millis(5)).err().is_some());
+
+    thread::scope(|s| {
+        let waiter = s.spawn(|| *reader.try_read_for(Duration::from_secs(10)).ok().unwrap());
+        *lock = 1;
+        thread::sleep(Duration::from_millis(10));
+        drop(lock);
+        assert_eq!(waiter.join().unwrap(), 1);
+    });
+}
+
+#[test]
+fn test_read_session() {
+    let (mut cell, reader) = RetroCell::new([0u64; 8]);
+    let mut session = reader.session();
+    let sum: u64 = (0..100).map(|_| session.get()[0]).sum();
+    assert_eq!(sum, 0);
+    let version = session.version();
+
+    // The session keeps the version retained, so writes copy
+    match cell.try_write() {
+        WriteOutcome::Congested(writer) => writer.perform_cow(|v| v[0] = 1),
+        WriteOutcome::InPlace(_) => panic!("the session must keep the version retained"),
+    }
+    assert_eq!(session.cached()[0], 0);
+    assert_eq!(session.get()[0], 1);
+    assert!(session.version() > version);
+    assert_eq!(session.into_ref()[0], 1);
+}
+
+#[test]
+fn test_reader_handles() {
+    let (cell, reader) = RetroCell::new(0);
+    assert_eq!(cell.reader_handles(), 1);
+
+    let clones: Vec<_> = (0..3).map(|_| reader.clone()).collect();
+    assert_eq!(cell.reader_handles(), 4);
+    // Guards are not handles
+    let _guard = reader.read();
+    assert_eq!(cell.reader_handles(), 4);
+
+    thread::spawn(move || drop(clones)).join().unwrap();
+    assert_eq!(cell.reader_handles(), 1);
+    drop(_guard);
+    drop(reader);
+    assert_eq!(cell.reader_handles(), 0);
+}
+
+#[test]
+fn test_store_without_clone() {
+    // Neither `Clone` nor `Default`
+    #[derive(Debug, PartialEq)]
+    struct Handle(u32);
+
+    let (mut cell, reader) = RetroCell::new(Handle(1));
+    let guard = reader.read();
+    cell.store(Handle(2));
+    drop(guard);
+    let guard = reader.read();
+    cell.store(Handle(3));
+    drop(guard);
+    assert_eq!(*reader.read(), Handle(3));
+    assert_eq!(*reader.read_retro().unwrap(), Handle(2));
+    assert_eq!(cell.version().get(), 2);
+}
+
+#[test]
+fn test_write_cow_with() {
+    // Rebuilt from parts: the large table is shared, never deep-copied
+    struct Routes {
+        table: Arc<Vec<u32>>,
+        generation: u32,
+    }
+
+    let (mut cell, reader) = RetroCell::new(Routes {
+        table: Arc::new(vec![0; 1024]),
+        generation: 0,
+    });
+    cell.write_cow_with(|old| Routes {
+        table: old.table.clone(),
+        generation: old.generation + 1,
+    });
+
+    let current = reader.read();
+    let retro = reader.read_retro().unwrap();
+    assert_eq!((current.generation, retro.generation), (1, 0));
+    assert!(Arc::ptr_eq(&current.table, &retro.table));
+}
+
+#[test]
+fn test_replace() {
+    let (mut cell, reader) = RetroCell::new(Vec::with_capacity(64));
+    cell.write_in_place().push(1u8);
+
+    let guard = reader.read();
+    let old = thread::scope(|s| {
+        // Waits for the guard to be released
+        let writer = s.spawn(|| cell.replace(vec![2]));
+        thread::sleep(Duration::from_millis(10));
+        assert_eq!(*guard, [1]);
+        drop(guard);
+        writer.join().unwrap()
+    });
+    assert_eq!(old, [1]);
+    assert!(old.capacity() >= 64);
+    assert_eq!(*reader.read(), [2]);
+    assert!(reader.read_retro().is_none());
+}
+
+#[test]
+fn test_swap_and_take() {
+    let (mut cell, reader) = RetroCell::new(String::from("a"));
+    let guard = reader.read();
+
+    // Never waits for the guard
+    assert_eq!(cell.swap(String::from("b")), "a");
+    assert_eq!(*reader.read(), "b");
+    assert_eq!(*reader.read_retro().unwrap(), "a");
+    assert_eq!(*guard, "a");
+
+    // Nobody holds "b": it is moved out in place, keeping the retro value
+    assert_eq!(cell.take(), "b");
+    assert_eq!(*reader.read(), "");
+    assert_eq!(*reader.read_retro().unwrap(), "a");
+    assert_eq!(cell.version().get(), 2);
+}
+
+#[test]
+fn test_store_reports_path() {
+    use retro_cell::WritePath;
+
+    // Not `Clone`: the old value is never copied
+    #[derive(Debug, PartialEq)]
+    struct Big(Vec<u8>);
+
+    let (mut cell, reader) = RetroCell::new(Big(vec![0]));
+    assert_eq!(cell.store(Big(vec![1])), WritePath::InPlace);
+
+    let guard = reader.read();
+    assert_eq!(cell.store(Big(vec![2])), WritePath::Cow);
+    assert_eq!(*guard, Big(vec![1]));
+    drop(guard);
+    assert_eq!(*reader.read(), Big(vec![2]));
+}
+
+#[test]
+fn test_compare_and_update() {
+    use retro_cell::{Ref, WriterToken};
+
+    let (cell, reader) = RetroCell::new(0);
+    let token = WriterToken::new(cell);
+
+    // Two producers based on the same version: only the first one wins
+    let seen = Ref::version(&reader.read());
+    let first = token.acquire().unwrap().compare_and_update(seen, |v| *v += 1);
+    let second = token.acquire().unwrap().compare_and_update(seen, |v| *v += 10);
+    assert_eq!(first, Ok(()));
+    let current = second.unwrap_err();
+    assert!(current > seen);
+
+    // Retrying from the reported version succeeds
+    let retried = token.acquire().unwrap().compare_and_update(current, |v| *v += 10);
+    assert_eq!(retried, Ok(()));
+    assert_eq!(*reader.read(), 11);
+}
+
+#[test]
+fn test_update_if() {
+    let (mut cell, reader) = RetroCell::new(3u32);
+    assert_eq!(cell.update_if(|v| *v > 5, |v| *v = 0), None);
+    assert_eq!(cell.version().get(), 0);
+
+    let guard = reader.read();
+    assert_eq!(cell.update_if(|v| *v == 3, |v| std::mem::replace(v, 4)), Some(3));
+    assert_eq!((*guard, *reader.read()), (3, 4));
+}
+
+#[test]
+fn test_try_write_cow() {
+    let (mut cell, reader) = RetroCell::new(vec![1, 2]);
+
+    let aborted: Result<(), &str> = cell.try_write_cow(|v| {
+        v.push(3);
+        Err("invalid")
+    });
+    assert_eq!(aborted, Err("invalid"));
+    assert_eq!(*reader.read(), [1, 2]);
+    assert_eq!(cell.version().get(), 0);
+    assert!(reader.read_retro().is_none());
+
+    let len = cell.try_write_cow(|v| {
+        v.push(3);
+        Ok::<_, ()>(v.len())
+    });
+    assert_eq!(len, Ok(3));
+    assert_eq!(*reader.read(), [1, 2, 3]);
+    assert_eq!(*reader.read_retro().unwrap(), [1, 2]);
+}
+
+#[test]
+fn test_write_if_changed() {
+    let (mut cell, reader) = RetroCell::new(String::from("v1"));
+    let seen = reader.current_version();
+
+    assert!(!cell.write_if_changed(String::from("v1")));
+    assert!(!reader.has_changed(seen));
+
+    assert!(cell.write_if_changed(String::from("v2")));
+    assert!(reader.has_changed(seen));
+    assert_eq!(*reader.read(), "v2");
+}
+
+#[test]
+fn test_write_report() {
+    use retro_cell::WritePath;
+
+    let (mut cell, reader) = RetroCell::new(0);
+    let report = cell.write(|v| *v = 1);
+    assert_eq!(report.path, WritePath::InPlace);
+    assert_eq!(report.version, cell.version());
+    assert_eq!(report.drained_wait, Duration::ZERO);
+
+    let guard = reader.read();
+    let report = cell.write(|v| *v = 2);
+    assert_eq!(report.path, WritePath::Cow);
+    assert_eq!(report.version.get(), 2);
+    assert_eq!((*guard, *reader.read()), (1, 2));
+}
+
+#[test]
+fn test_wait_in_place_timeout() {
+    let (mut cell, reader) = RetroCell::new(0);
+    let guard = reader.read();
+
+    // A reader that never lets go: give up and fall back to COW
+    match cell.try_write() {
+        WriteOutcome::Congested(writer) => match writer.wait_in_place_timeout(Duration::from_millis(5)) {
+            Ok(_) => panic!("the guard must keep the node busy"),
+            Err(writer) => writer.perform_cow(|v| *v = 1),
+        },
+        WriteOutcome::InPlace(_) => panic!("expected congestion"),
+    }
+    // Readers were not left blocked by the rolled-back lock
+    assert_eq!(*reader.read(), 1);
+    drop(guard);
+
+    // A reader that lets go in time
+    let held = Barrier::new(2);
+    thread::scope(|s| {
+        s.spawn(|| {
+            let guard = reader.read();
+            held.wait();
+            thread::sleep(Duration::from_millis(10));
+            drop(guard);
+        });
+        held.wait();
+        match cell.try_write() {
+            WriteOutcome::Congested(writer) => {
+                let start = std::time::Instant::now();
+                let mut lock = writer.wait_in_place_timeout(Duration::from_secs(10)).ok().unwrap();
+                // Woken by the last reader rather than by the timeout
+                assert!(start.elapsed() < Duration::from_secs(5));
+                *lock = 2;
+            }
+            WriteOutcome::InPlace(_) => panic!("expected congestion"),
+        }
+    });
+    assert_eq!(*reader.read(), 2);
+
+    // A timeout past the end of time waits without overflowing the deadline
+    thread::scope(|s| {
+        s.spawn(|| {
+            let guard = reader.read();
+            held.wait();
+            thread::sleep(Duration::from_millis(10));
+            drop(guard);
+        });
+        held.wait();
+        match cell.try_write() {
+            WriteOutcome::Congested(writer) => *writer.wait_in_place_timeout(Duration::MAX).ok().unwrap() = 3,
+            WriteOutcome::InPlace(_) => panic!("expected congestion"),
+        }
+    });
+    assert_eq!(*reader.read(), 3);
+}
+
+#[test]
+fn test_force_in_place_until() {
+    use retro_cell::Instant;
+
+    let (mut cell, reader) = RetroCell::new(0);
+    let guard = reader.read();
+    let WriteOutcome::Congested(writer) = cell.try_write() else {
+        panic!("expected congestion");
+    };
+    // A deadline in the past gives up immediately
+    let Err(writer) = writer.force_in_place_until(Instant::now()) else {
+        panic!("the guard must keep the node busy");
+    };
+    drop(guard);
+
+    let start = Instant::now();
+    let mut lock = writer
+        .force_in_place_until(start + Duration::from_secs(60))
+        .ok()
+        .unwrap();
+    // Readers drained: no need to wait for the deadline
+    assert!(start.elapsed() < Duration::from_secs(30));
+    *lock = 1;
+    drop(lock);
+    assert_eq!(*reader.read(), 1);
+}
+
+#[test]
+fn test_write_adaptive() {
+    let (mut cell, reader) = RetroCell::new(0);
+    let budget = Duration::from_millis(5);
+
+    assert_eq!(cell.write_adaptive(budget, |v| std::mem::replace(v, 1)), 0);
+    assert!(reader.read_retro().is_none());
+
+    // A guard outlasting the budget forces COW
+    let guard = reader.read();
+    cell.write_adaptive(budget, |v| *v = 2);
+    assert_eq!((*guard, *reader.read()), (1, 2));
+    assert_eq!(*reader.read_retro().unwrap(), 1);
+    drop(guard);
+
+    // An unbounded budget waits for the reader instead of overflowing
+    let held = Barrier::new(2);
+    thread::scope(|s| {
+        s.spawn(|| {
+            let guard = reader.read();
+            held.wait();
+            thread::sleep(Duration::from_millis(10));
+            drop(guard);
+        });
+        held.wait();
+        cell.write_adaptive(Duration::MAX, |v| *v = 3);
+    });
+    assert_eq!(*reader.read(), 3);
+    assert_eq!(*reader.read_retro().unwrap(), 1);
+}
+
+#[test]
+fn test_in_place_guard_downgrade() {
+    use retro_cell::{InPlaceGuard, Ref};
+
+    let (mut cell, reader) = RetroCell::new(0);
+    let mut lock = cell.write_in_place();
+    *lock = 1;
+    let guard = InPlaceGuard::downgrade(lock);
+    assert_eq!(*guard, 1);
+    assert_eq!(Ref::version(&guard).get(), 1);
+    // Published: readers no longer block
+    assert_eq!(*reader.try_read().ok().unwrap(), 1);
+    drop(guard);
+
+    // The downgraded guard was released, so the next write goes in place
+    assert!(matches!(cell.try_write(), WriteOutcome::InPlace(_)));
+}
+
+#[test]
+fn test_in_place_abort() {
+    use retro_cell::InPlaceGuard;
+
+    let (mut cell, reader) = RetroCell::new(vec![1, 2]);
+    let mut lock = cell.write_in_place();
+    let original = lock.clone();
+    lock.clear();
+    InPlaceGuard::abort(lock, original);
+    assert_eq!(*reader.read(), [1, 2]);
+    assert_eq!(cell.version().get(), 0);
+    assert!(!cell.is_poisoned());
+
+    let failed: Result<(), &str> = cell.try_write_in_place(|v| {
+        v.push(3);
+        Err("rejected")
+    });
+    assert_eq!(failed, Err("rejected"));
+    assert_eq!(*reader.read(), [1, 2]);
+
+    let len = cell.try_write_in_place(|v| {
+        v.push(3);
+        Ok::<_, ()>(v.len())
+    });
+    assert_eq!(len, Ok(3));
+    assert_eq!(*reader.read(), [1, 2, 3]);
+    assert_eq!(cell.version().get(), 1);
+}
+
+#[test]
+fn test_in_place_map_mut() {
+    use retro_cell::InPlaceGuard;
+    use std::ops::DerefMut;
+
+    #[derive(Debug)]
+    struct Config {
+        name: String,
+        limits: Vec<u32>,
+    }
+
+    fn bump(mut limits: impl DerefMut<Target = Vec<u32>>) {
+        limits.push(30);
+    }
+
+    let (mut cell, reader) = RetroCell::new(Config {
+        name: "app".to_string(),
+        limits: vec![10, 20],
+    });
+    bump(InPlaceGuard::map_mut(cell.write_in_place(), |c| &mut c.limits));
+    assert_eq!(reader.read().limits, [10, 20, 30]);
+    assert_eq!(cell.version().get(), 1);
+
+    let guard = InPlaceGuard::map_mut(cell.write_in_place(), |c| &mut c.name);
+    let mut first = retro_cell::MappedInPlaceGuard::map_mut(guard, |n| n.as_mut_str());
+    first.make_ascii_uppercase();
+    assert_eq!(format!("{:?}", &*first), "\"APP\"");
+    drop(first);
+    assert_eq!(reader.read().name, "APP");
+    assert_eq!(cell.version().get(), 2);
+}
+
+#[test]
+fn test_new_reader() {
+    let (mut cell, reader) = RetroCell::new(1);
+    drop(reader);
+    assert_eq!(cell.reader_handles(), 0);
+
+    let late = cell.new_reader();
+    let sibling = late.new_reader();
+    assert_eq!(cell.reader_handles(), 2);
+    cell.store(2);
+    assert_eq!(*late.read(), 2);
+    assert_eq!(*sibling.read(), 2);
+}
+
+#[test]
+fn test_writer_peek() {
+    let (mut cell, reader) = RetroCell::new(vec![1]);
+    let guard = reader.read();
+    assert_eq!(cell.peek(), &*guard);
+    drop(guard);
+
+    if cell.peek().len() < 2 {
+        cell.write_in_place().push(2);
+    }
+    assert_eq!(cell.peek(), &[1, 2]);
+    assert_eq!(*reader.read(), [1, 2]);
+}
+
+#[test]
+fn test_shared_writer() {
+    use retro_cell::{SharedWriter, WritePath};
+
+    let (cell, reader) = RetroCell::new(0u64);
+    let writer = SharedWriter::new(cell);
+    let handles: Vec<_> = (0..4)
+        .map(|_| {
+            let writer = writer.clone();
+            thread::spawn(move || {
+                for _ in 0..100 {
+                    writer.write(|v| *v += 1).unwrap();
+                }
+            })
+        })
+        .collect();
+    for handle in handles {
+        handle.join().unwrap();
+    }
+    assert_eq!(*reader.read(), 400);
+    assert_eq!(writer.version().get(), 400);
+
+    let guard = reader.read();
+    assert_eq!(writer.store(7), Ok(WritePath::Cow));
+    drop(guard);
+    assert_eq!(writer.with(|cell| *cell.peek()), Ok(7));
+    assert_eq!(*writer.new_reader().read(), 7);
+
+    let other = writer.clone();
+    let writer = writer.into_inner();
+    assert!(writer.is_none());
+    let cell = other.into_inner().unwrap();
+    assert_eq!(*cell.peek(), 7);
+}
+
+#[test]
+fn test_shared_writer_poison() {
+    use retro_cell::{Error, SharedWriter};
+
+    let (cell, reader) = RetroCell::new(vec![0]);
+    let writer = SharedWriter::new(cell);
+
+    // A producer dies in the middle of an in-place write
+    let crashed = {
+        let writer = writer.clone();
+        thread::spawn(move || {
+            let _ = writer.with(|cell| {
+                cell.write_in_place().push(1);
+                panic!("producer crashed");
+            });
+        })
+    };
+    assert!(crashed.join().is_err());
+
+    assert!(writer.is_poisoned());
+    assert_eq!(writer.store(vec![2]), Err(Error::Poisoned));
+    assert!(writer.write(|v| v.push(2)).is_err());
+
+    // Repair, then producers can write again
+    writer.recover(|cell| cell.store(vec![0]));
+    writer.clear_poison();
+    assert!(!writer.is_poisoned());
+    assert!(writer.store(vec![3]).is_ok());
+    assert_eq!(*reader.read(), vec![3]);
+}
+
+#[test]
+fn test_writer_handoff() {
+    let (cell, reader) = RetroCell::new(0u64);
+    let writer = cell.into_shared();
+
+    let mut lease = writer.try_lock_writer().unwrap();
+    assert!(writer.is_locked());
+    assert!(writer.clone().try_lock_writer().is_none());
+    lease.store(1);
+    drop(lease);
+    assert!(!writer.is_locked());
+
+    let handles: Vec<_> = (0..4)
+        .map(|_| {
+            let writer = writer.clone();
+            thread::spawn(move || {
+                let mut done = 0;
+                while done < 100 {
+                    if let Some(mut lease) = writer.try_lock_writer() {
+                        lease.write(|v| *v += 1);
+                        done += 1;
+                    } else {
+                        thread::yield_now();
+                    }
+                }
+            })
+        })
+        .collect();
+    for handle in handles {
+        handle.join().unwrap();
+    }
+    assert_eq!(*reader.read(), 401);
+
+    let cell = writer.into_inner().unwrap();
+    assert_eq!(cell.version().get(), 401);
+}
+
+#[test]
+fn test_write_batch() {
+    use retro_cell::WritePath;
+
+    let (mut cell, reader) = RetroCell::new(Vec::new());
+    let report = cell.write_batch((1..=3).map(|i| move |v: &mut Vec<u32>| v.push(i)));
+    assert_eq!(report.path, WritePath::InPlace);
+    assert_eq!(report.version.get(), 1);
+    assert_eq!(*reader.read(), [1, 2, 3]);
+
+    let guard = reader.read();
+    let report = cell.write_batch([|v: &mut Vec<u32>| v.clear(), |v: &mut Vec<u32>| v.push(9)]);
+    assert_eq!(report.path, WritePath::Cow);
+    assert_eq!(report.version.get(), 2);
+    assert_eq!(*guard, [1, 2, 3]);
+    assert_eq!(*reader.read(), [9]);
+}
+
+#[test]
+fn test_updater_queue() {
+    let (mut cell, reader) = RetroCell::new(Vec::new());
+    assert_eq!(cell.apply_pending(), 0);
+
+    let updater = cell.updater();
+    let handles: Vec<_> = (0..4u32)
+        .map(|i| {
+            let updater = updater.clone();
+            thread::spawn(move || updater.update(move |v: &mut Vec<u32>| v.push(i)).unwrap())
+        })
+        .collect();
+    for handle in handles {
+        handle.join().unwrap();
+    }
+    assert!(reader.read().is_empty());
+    assert_eq!(cell.apply_pending(), 4);
+    assert_eq!(cell.version().get(), 1);
+    let mut applied = reader.read().clone();
+    applied.sort();
+    assert_eq!(applied, [0, 1, 2, 3]);
+
+    cell.updater().set(vec![7]).unwrap();
+    assert_eq!(cell.apply_pending(), 1);
+    assert_eq!(cell.apply_pending(), 0);
+    assert_eq!(*reader.read(), [7]);
+
+    assert!(!updater.is_closed());
+    drop(cell);
+    assert!(updater.is_closed());
+    assert!(matches!(updater.set(vec![]), Err(retro_cell::Error::Closed)));
+}
+
+#[test]
+fn test_apply_stream() {
+    let (mut cell, reader) = RetroCell::new(0u64);
+    let log = (1..=1000u64).map(|delta| move |v: &mut u64| *v += delta);
+    assert_eq!(cell.apply_stream(log), 1000);
+    assert_eq!(*reader.read(), 500_500);
+    assert_eq!(cell.version().get(), 1);
+    assert_eq!(cell.apply_stream(std::iter::empty::<fn(&mut u64)>()), 0);
+    assert_eq!(cell.version().get(), 1);
+
+    assert_eq!(cell.store_stream(vec![1, 2, 3]), 3);
+    assert_eq!(*reader.read(), 3);
+    assert_eq!(cell.version().get(), 2);
+    assert_eq!(cell.store_stream(std::iter::empty()), 0);
+    assert_eq!(cell.version().get(), 2);
+}
+
+#[test]
+fn test_active_readers() {
+    let (mut cell, reader) = RetroCell::new(0);
+    assert_eq!(cell.active_readers(), 0);
+
+    let first = reader.read();
+    let second = reader.read();
+    let mut local = reader.pin();
+    let pinned = local.read();
+    assert_eq!(cell.active_readers(), 3);
+    drop(pinned);
+    drop(second);
+
+    match cell.try_write() {
+        WriteOutcome::Congested(writer) => {
+            assert_eq!(writer.readers(), 1);
+            writer.perform_cow(|v| *v += 1);
+        }
+        WriteOutcome::InPlace(_) => panic!("a reader holds the current value"),
+    }
+    assert_eq!(cell.active_readers(), 0);
+    drop(first);
+}
+
+#[test]
+fn test_reader_refresh() {
+    let (mut cell, reader) = RetroCell::new(0);
+    let mut guard = reader.read();
+    assert!(!reader.refresh(&mut guard));
+    assert_eq!(*guard, 0);
+
+    // The old version stays pinned until the new one is retained
+    let writer = thread::spawn(move || {
+        cell.write_cow(|v| *v = 1);
+        cell.write_cow(|v| *v = 2);
+        cell
+    });
+    let cell = writer.join().unwrap();
+    assert!(reader.refresh(&mut guard));
+    assert_eq!(*guard, 2);
+    drop(guard);
+
+    // Retro guards are refreshed to the current value
+    let mut retro = reader.read_retro().unwrap();
+    assert_eq!(*retro, 1);
+    assert!(reader.refresh(&mut retro));
+    assert_eq!(*retro, 2);
+    drop(retro);
+    cell.debug_assert_quiescent();
+}
+
+#[test]
+fn test_reader_refreshed() {
+    use retro_cell::Ref;
+
+    let (mut cell, reader) = RetroCell::new(0);
+    let guard = reader.refreshed(reader.read());
+    let version = Ref::version(&guard);
+    assert_eq!(*guard, 0);
+
+    cell.write_cow(|v| *v = 1);
+    let guard = reader.refreshed(guard);
+    assert_eq!(*guard, 1);
+    assert!(Ref::version(&guard) > version);
+}
+
+#[test]
+fn test_read_cloned() {
+    let (mut cell, reader) = RetroCell::new(String::from("a"));
+    assert_eq!(reader.read_cloned(), "a");
+    assert_eq!(reader.try_read_cloned().as_deref(), Some("a"));
+
+    let guard = cell.write_in_place();
+    assert_eq!(reader.try_read_cloned(), None);
+    drop(guard);
+
+    // No guard is left behind: in-place writes don't have to wait
+    let _ = reader.read_cloned();
+    assert!(matches!(cell.try_write(), WriteOutcome::InPlace(_)));
+}
+
+#[test]
+fn test_reader_with() {
+    let (mut cell, reader) = RetroCell::new(vec![1, 2, 3]);
+    assert_eq!(reader.with(|v| v.iter().sum::<i32>()), 6);
+
+    // No guard outlives the call, so the next write goes in place
+    assert!(matches!(cell.try_write(), WriteOutcome::InPlace(_)));
+}
+
+#[test]
+fn test_get_copy() {
+    let (mut cell, reader) = RetroCell::new(7u64);
+    assert_eq!(reader.get(), 7);
+    cell.write_cow(|v| *v += 1);
+    *cell.write_in_place() += 1;
+    assert_eq!(reader.get(), 9);
+}
+
+#[test]
+fn test_store() {
+    let (mut cell, reader) = RetroCell::new(String::from("a"));
+
+    // No readers: moved in place, no retro version
+    cell.store(String::from("b"));
+    assert_eq!(*reader.read(), "b");
+    assert!(reader.read_retro().is_none());
+
+    // Held by a reader: installed as a new version
+    let guard = reader.read();
+    cell.store(String::from("c"));
+    assert_eq!(*guard, "b");
+    assert_eq!(*reader.read(), "c");
+    assert_eq!(*reader.read_retro().unwrap(), "b");
+    assert_eq!(cell.version().get(), 2);
+}
+
+#[test]
+fn test_atomic_cell_facade() {
+    let mut atomic = retro_cell::AtomicCell::new(String::from("a"));
+    let reader = atomic.reader();
+    assert_eq!(atomic.load(), "a");
+
+    atomic.store(String::from("b"));
+    assert_eq!(atomic.swap(String::from("c")), "b");
+
+    // Swapping out a value a reader still holds clones it
+    let guard = reader.read();
+    assert_eq!(atomic.swap(String::from("d")), "c");
+    assert_eq!(*guard, "c");
+    drop(guard);
+
+    assert_eq!(
+        atomic.compare_exchange(String::from("x"), String::from("e")),
+        Err(String::from("d"))
+    );
+    assert_eq!(
+        atomic.compare_exchange(String::from("d"), String::from("e")),
+        Ok(String::from("d"))
+    );
+    assert_eq!(atomic.take(), "e");
+    assert_eq!(*reader.read(), "");
+    assert_eq!(atomic.cell().version().get(), 5);
+}
+
+#[test]
+fn test_compare_exchange() {
+    let (mut cell, reader) = RetroCell::new(String::from("idle"));
+
+    match cell.compare_exchange(&String::from("running"), String::from("done")) {
+        Err(actual) => assert_eq!(*actual, "idle"),
+        Ok(()) => panic!("Should fail on mismatch"),
+    }
+    assert_eq!(cell.version().get(), 0);
+
+    assert!(cell
+        .compare_exchange(&String::from("idle"), String::from("running"))
+        .is_ok());
+    assert_eq!(*reader.read(), "running");
+    assert_eq!(cell.version().get(), 1);
+
+    // The returned guard is released, so the next write is in place
+    assert!(matches!(cell.try_write(), WriteOutcome::InPlace(_)));
+}
+
+#[test]
+fn test_static_storage() {
+    static STORAGE: retro_cell::SharedStorage<u64> = retro_cell::SharedStorage::new();
+
+    let (mut cell, reader) = RetroCell::new_in(&STORAGE, 1);
+    let readers: Vec<_> = (0..4).map(|_| reader.clone()).collect();
+    let guard = reader.read();
+    cell.store(2);
+    assert_eq!(*guard, 1);
+    drop(guard);
+    for r in &readers {
+        assert_eq!(*r.read(), 2);
+        assert_eq!(*r.read_retro().unwrap(), 1);
+    }
+
+    // Readers outlive the writer without any reference counting
+    drop(cell);
+    assert_eq!(*readers[0].read(), 2);
+}
+
+#[test]
+#[should_panic(expected = "already holds a cell")]
+fn test_static_storage_reuse_panics() {
+    static STORAGE: retro_cell::SharedStorage<u64> = retro_cell::SharedStorage::new();
+    let _first = RetroCell::new_in(&STORAGE, 1);
+    let _second = RetroCell::new_in(&STORAGE, 2);
+}
+
+#[test]
+fn test_mirror_to() {
+    let (mut primary, _primary_reader) = RetroCell::new(0);
+    primary.store(1);
+    let (mut standby, standby_reader) = RetroCell::new(0);
+
+    {
+        // Catches up on creation
+        let mut mirror = primary.mirror_to(&mut standby);
+        assert_eq!(mirror.standby().version().get(), 1);
+
+        mirror.write(|v| *v = 2);
+        let guard = standby_reader.read();
+        mirror.write_cow(|v| *v = 3);
+        drop(guard);
+        mirror.store(4);
+    }
+
+    assert_eq!(standby.version(), primary.version());
+    assert_eq!(standby.version().get(), 4);
+    assert_eq!(*standby_reader.read(), 4);
+    assert_eq!(*standby_reader.read_retro().unwrap(), 2);
+
+    // Failover: the standby takes over writing
+    standby.store(5);
+    assert_eq!(standby.version().get(), 5);
+}
+
+#[test]
+fn test_reader_pressure() {
+    let (mut cell, reader) = RetroCell::new(0);
+    cell.store(1);
+    assert_eq!(reader.pressure(), 0);
+
+    for i in 0..3 {
+        let _guard = reader.read();
+        cell.store(i);
+    }
+    assert_eq!(reader.pressure(), 3);
+
+    // Deliberate COW writes don't count
+    cell.write_cow(|v| *v += 1);
+    assert_eq!(reader.pressure(), 3);
+
+    // Congestion slides out of the 64-publication window
+    for i in 0..64 {
+        cell.store(i);
+    }
+    assert_eq!(reader.pressure(), 0);
+}
+
+#[test]
+fn test_write_in_place_dirty() {
+    #[derive(Clone, Copy)]
+    #[repr(C)]
+    struct Metrics {
+        count: u64,
+        total: u64,
+    }
+
+    let (mut cell, reader) = RetroCell::new(Metrics { count: 0, total: 0 });
+    let before = reader.read_versioned().1.publish_time;
+    let guard = reader.read();
+
+    // Does not wait for the guard, and publishes no new node
+    unsafe {
+        cell.write_in_place_dirty(|m| {
+            m.count += 1;
+            m.total += 10;
+        })
+    };
+    // The held guard reports the new version, but keeps the publish time
+    assert_eq!(retro_cell::Ref::version(&guard).get(), 1);
+    drop(guard);
+
+    let value = reader.get();
+    assert_eq!((value.count, value.total), (1, 10));
+    assert!(reader.read_retro().is_none());
+    assert_eq!(cell.version().get(), 1);
+    let (_, meta) = reader.read_versioned();
+    assert_eq!(meta.version.get(), 1);
+    assert_eq!(meta.publish_time, before);
+}
+
+#[test]
+fn test_with_initial_history() {
+    let (cell, reader) = RetroCell::with_initial_history(3, Some(2));
+    assert_eq!(cell.version().get(), 1);
+
+    let (current, meta) = reader.read_versioned();
+    assert_eq!((*current, meta.version.get()), (3, 1));
+    let retro = reader.read_retro().unwrap();
+    assert_eq!((*retro, retro_cell::Ref::version(&retro).get()), (2, 0));
+
+    let (cell, reader) = RetroCell::with_initial_history(7, None);
+    assert_eq!(cell.version().get(), 0);
+    assert!(reader.read_retro().is_none());
+}
+
+#[test]
+fn test_build_with_history_archives_older_versions() {
+    use std::sync::Mutex;
+
+    let archived = Arc::new(Mutex::new(Vec::new()));
+    let sink = {
+        let archived = archived.clone();
+        move |version: retro_cell::Version, value: &i32| {
+            archived.lock().unwrap().push((version.get(), *value));
+        }
+    };
+    let (cell, reader) = RetroCell::builder()
+        .history_sink(sink)
+        .build_with_history(40, [0, 10, 20, 30]);
+
+    assert_eq!(cell.version().get(), 4);
+    assert_eq!(*reader.read(), 40);
+    let retro = reader.read_retro().unwrap();
+    assert_eq!((*retro, retro_cell::Ref::version(&retro).get()), (30, 3));
+    assert_eq!(*archived.lock().unwrap(), vec![(0, 0), (1, 10), (2, 20)]);
+}
+
+#[test]
+fn test_custom_notify() {
+    use std::sync::atomic::AtomicU32;
+    use std::sync::{Condvar, Mutex};
+
+    #[derive(Default)]
+    struct CondvarNotify {
+        lock: Mutex<()>,
+        cond: Condvar,
+        wakes: AtomicUsize,
+    }
+
+    impl retro_cell::Notify for CondvarNotify {
+        fn wait(&self, word: &AtomicU32, expected: u32) {
+            let guard = self.lock.lock().unwrap();
+            if word.load(Ordering::Acquire) == expected {
+                let _ = self.cond.wait_timeout(guard, Duration::from_millis(10));
+            }
+        }
+
+        fn wake_all(&self, _word: &AtomicU32) {
+            self.wakes.fetch_add(1, Ordering::Relaxed);
+            let _guard = self.lock.lock().unwrap();
+            self.cond.notify_all();
+        }
+    }
+
+    let notify = Arc::new(CondvarNotify::default());
+    let (mut cell, reader) = RetroCell::builder().notify(notify.clone()).build(0);
+
+    let guard = cell.write_in_place();
+    let handle = thread::spawn(move || *reader.read());
+    thread::sleep(Duration::from_millis(20));
+    drop(guard);
+
+    assert_eq!(handle.join().unwrap(), 0);
+    assert!(notify.wakes.load(Ordering::Relaxed) >= 1);
+}
+
+#[test]
+fn test_retro_slot() {
+    let slot = Arc::new(retro_cell::RetroSlot::new());
+    assert_eq!(slot.take_if_present(), None);
+    assert!(slot.last_taken().is_none());
+
+    assert_eq!(slot.put(String::from("a")), None);
+    assert_eq!(slot.put(String::from("b")), Some(String::from("a")));
+    assert_eq!(slot.peek().as_deref(), Some("b"));
+    assert_eq!(slot.take_if_present().as_deref(), Some("b"));
+    assert_eq!(*slot.peek(), None);
+    assert_eq!(slot.last_taken().unwrap().as_deref(), Some("b"));
+
+    // Blocking take is woken by a put from another thread
+    let consumer = {
+        let slot = slot.clone();
+        thread::spawn(move || slot.take())
+    };
+    thread::sleep(Duration::from_millis(20));
+    slot.put(String::from("c"));
+    assert_eq!(consumer.join().unwrap(), "c");
+    assert_eq!(slot.last_taken().unwrap().as_deref(), Some("c"));
+}
+
+#[cfg(feature = "futures-signals")]
+#[test]
+fn test_reader_signal() {
+    use futures_signals::signal::Signal;
+    use std::pin::Pin;
+    use std::task::{Context, Poll, Waker};
+
+    let (mut cell, reader) = RetroCell::new(1);
+    let mut signal = reader.to_signal();
+    let mut cx = Context::from_waker(Waker::noop());
+    let mut poll = || Pin::new(&mut signal).poll_change(&mut cx);
+
+    assert_eq!(poll(), Poll::Ready(Some(1)));
+    assert_eq!(poll(), Poll::Pending);
+
+    // Latest wins: intermediate versions are skipped
+    cell.store(2);
+    cell.store(3);
+    assert_eq!(poll(), Poll::Ready(Some(3)));
+    assert_eq!(poll(), Poll::Pending);
+}
+
+#[test]
+fn test_feed_from() {
+    use std::sync::mpsc;
+
+    let (cell, reader) = RetroCell::new(0);
+    let (tx, rx) = mpsc::channel();
+    let feeder = cell.feed_from(rx, false);
+    for i in 1..=5 {
+        tx.send(i).unwrap();
+    }
+    drop(tx);
+    let cell = feeder.join();
+    assert_eq!(*reader.read(), 5);
+    assert_eq!(cell.version().get(), 5);
+
+    // Coalescing publishes fewer versions but always ends on the latest
+    let (tx, rx) = mpsc::channel();
+    for i in 6..=10 {
+        tx.send(i).unwrap();
+    }
+    let feeder = cell.feed_from(rx, true);
+    while *reader.read() != 10 {
+        thread::yield_now();
+    }
+    let cell = feeder.stop();
+    assert!(cell.version().get() < 10);
+    drop(tx);
+}
+
+#[cfg(feature = "stream")]
+#[test]
+fn test_feed_from_stream() {
+    use std::future::Future;
+    use std::pin::pin;
+    use std::task::{Context, Poll, Waker};
+
+    struct Iter(std::vec::IntoIter<i32>);
+
+    impl futures_core::Stream for Iter {
+        type Item = i32;
+        fn poll_next(mut self: std::pin::Pin<&mut Self>, _: &mut Context<'_>) -> Poll<Option<i32>> {
+            Poll::Ready(self.0.next())
+        }
+    }
+
+    let mut cx = Context::from_waker(Waker::noop());
+    let (mut cell, reader) = RetroCell::new(0);
+
+    {
+        let feed = pin!(cell.feed_from_stream(Iter(vec![1, 2, 3].into_iter()), false));
+        assert_eq!(feed.poll(&mut cx), Poll::Ready(()));
+    }
+    assert_eq!((*reader.read(), cell.version().get()), (3, 3));
+
+    // Everything is ready at once: a single publication
+    {
+        let feed = pin!(cell.feed_from_stream(Iter(vec![4, 5, 6].into_iter()), true));
+        assert_eq!(feed.poll(&mut cx), Poll::Ready(()));
+    }
+    assert_eq!((*reader.read(), cell.version().get()), (6, 4));
+}
+
+#[test]
+fn test_writer_token_recovery() {
+    let (cell, reader) = RetroCell::new(vec![0]);
+    let token = retro_cell::WriterToken::new(cell);
+
+    // The writing thread dies in the middle of an in-place write
+    let crashed = {
+        let token = token.clone();
+        thread::spawn(move || {
+            let mut writer = token.acquire().unwrap();
+            let mut guard = writer.write_in_place();
+            guard.push(1);
+            panic!("writer crashed");
+        })
+    };
+    assert!(crashed.join().is_err());
+
+    assert!(token.is_poisoned());
+    assert_eq!(token.acquire().err(), Some(retro_cell::Error::Poisoned));
+
+    // A supervisor repairs the value and appoints a new writer
+    token.recover().store(vec![0]);
+    token.clear_poison();
+    assert!(!token.is_poisoned());
+
+    let successor = {
+        let token = token.clone();
+        thread::spawn(move || token.acquire().unwrap().store(vec![2]))
+    };
+    successor.join().unwrap();
+    assert_eq!(*reader.read(), vec![2]);
+    assert!(token.into_inner().is_some());
+}
+
+#[cfg(feature = "stats")]
+#[test]
+fn test_reader_local_stats() {
+    let (mut cell, reader) = RetroCell::new(0);
+    let guard = reader.read();
+    cell.store(1);
+    drop(guard);
+    assert_eq!(reader.local_stats().reads, 1);
+
+    // Clones count separately, from zero
+    let fallback = reader.clone();
+    let waiter = reader.clone();
+    assert_eq!(fallback.local_stats(), retro_cell::ReaderStats::default());
+
+    let guard = cell.write_in_place();
+    let stats = thread::spawn(move || {
+        let (value, meta) = fallback.read_versioned();
+        assert_eq!((*value, meta.was_retro), (0, true));
+        drop(value);
+        fallback.local_stats()
+    })
+    .join()
+    .unwrap();
+    assert_eq!((stats.reads, stats.blocked, stats.retro_fallbacks), (0, 1, 1));
+
+    let handle = thread::spawn(move || {
+        drop(waiter.read());
+        waiter.local_stats()
+    });
+    thread::sleep(Duration::from_millis(20));
+    drop(guard);
+    let stats = handle.join().unwrap();
+    assert_eq!((stats.reads, stats.blocked), (1, 1));
+    assert!(stats.wait_time >= Duration::from_millis(5));
+    assert_eq!(reader.local_stats().reads, 1);
+}
+
+#[cfg(feature = "test-util")]
+#[test]
+fn test_mock_clock_drives_snapshot_interval() {
+    let clock = retro_cell::MockClock::new();
+    clock.run(|| {
+        let (mut cell, reader) = RetroCell::builder()
+            .snapshot_interval(Duration::from_secs(60))
+            .build(0);
+
+        *cell.write_in_place() = 1;
+        *cell.write_in_place() = 2;
+        assert_eq!(*reader.read_retro().unwrap(), 0);
+
+        clock.advance(Duration::from_secs(59));
+        *cell.write_in_place() = 3;
+        assert_eq!(*reader.read_retro().unwrap(), 0);
+
+        clock.advance(Duration::from_secs(1));
+        *cell.write_in_place() = 4;
+        assert_eq!(*reader.read_retro().unwrap(), 3);
+
+        let (_, meta) = reader.read_versioned();
+        assert_eq!(meta.publish_time, retro_cell::Instant::now());
+    });
+}
+
+#[cfg(feature = "test-util")]
+#[test]
+fn test_mock_clock_drives_timeouts() {
+    let clock = retro_cell::MockClock::new();
+    clock.run(|| {
+        let (mut cell, reader) = RetroCell::new(0);
+        let guard = reader.read();
+        let deadline = retro_cell::Instant::now() + Duration::from_secs(3600);
+        let WriteOutcome::Congested(writer) = cell.try_write() else {
+            panic!("expected congestion");
+        };
+
+        // The deadline has passed on the virtual clock only
+        clock.advance(Duration::from_secs(3601));
+        let start = std::time::Instant::now();
+        let Err(writer) = writer.force_in_place_until(deadline) else {
+            panic!("the guard must keep the node busy");
+        };
+        assert!(start.elapsed() < Duration::from_secs(60));
+
+        drop(guard);
+        let later = deadline + Duration::from_secs(3600);
+        let Ok(mut lock) = writer.force_in_place_until(later) else {
+            panic!("readers drained before the deadline");
+        };
+        *lock = 1;
+        drop(lock);
+        assert_eq!(*reader.read(), 1);
+    });
+}
+
+#[cfg(not(feature = "loom"))]
+#[test]
+fn test_wait_observer() {
+    use std::sync::Mutex;
+
+    #[derive(Default)]
+    struct Trace(Mutex<Vec<&'static str>>);
+    impl retro_cell::WaitObserver for Trace {
+        fn on_spin_phase(&self) {
+            self.0.lock().unwrap().push("spin");
+        }
+        fn on_park(&self) {
+            self.0.lock().unwrap().push("park");
+        }
+        fn on_wake(&self) {
+            self.0.lock().unwrap().push("wake");
+        }
+    }
+
+    let (mut cell, mut reader) = RetroCell::new(0);
+    let trace = Arc::new(Trace::default());
+    reader.set_wait_observer(trace.clone());
+
+    // Unblocked reads report nothing
+    assert_eq!(*reader.read(), 0);
+    assert!(trace.0.lock().unwrap().is_empty());
+
+    let timed = reader.clone();
+    let mut guard = cell.write_in_place();
+    let t = thread::spawn(move || *reader.read());
+    thread::sleep(Duration::from_millis(50));
+    *guard = 1;
+    drop(guard);
+    assert_eq!(t.join().unwrap(), 1);
+
+    let events = trace.0.lock().unwrap().clone();
+    assert_eq!(events.first(), Some(&"spin"));
+    assert!(events.contains(&"park"));
+    let parks = events.iter().filter(|&&e| e == "park").count();
+    let wakes = events.iter().filter(|&&e| e == "wake").count();
+    assert_eq!(parks, wakes);
+
+    // Timed reads park too, and the unlock wakes them before the timeout
+    trace.0.lock().unwrap().clear();
+    let mut guard = cell.write_in_place();
+    let t = thread::spawn(move || {
+        let start = std::time::Instant::now();
+        let value = *timed.try_read_for(Duration::from_secs(10)).ok().unwrap();
+        (value, start.elapsed())
+    });
+    thread::sleep(Duration::from_millis(50));
+    *guard = 2;
+    drop(guard);
+    let (value, waited) = t.join().unwrap();
+    assert_eq!(value, 2);
+    assert!(waited < Duration::from_secs(5));
+    assert!(trace.0.lock().unwrap().contains(&"park"));
+}
+
+#[test]
+fn test_history_sink_archives_aged_out_versions() {
+    use std::sync::Mutex;
+
+    let archived = Arc::new(Mutex::new(Vec::new()));
+    let sink = {
+        let archived = archived.clone();
+        move |version: retro_cell::Version, value: &i32| {
+            archived.lock().unwrap().push((version.get(), *value));
+        }
+    };
+    let (mut cell, reader) = RetroCell::builder().history_sink(sink).build(0);
+
+    for i in 1..=4 {
+        cell.write_cow(|v| *v = i * 10);
+    }
+    // Reclamation runs at the start of a write: versions 0 and 1 aged out,
+    // 2 is waiting and 3 is still the retro version
+    assert_eq!(*archived.lock().unwrap(), vec![(0, 0), (1, 10)]);
+    assert_eq!(*reader.read_retro().unwrap(), 30);
+
+    // A pinned version is archived only once its reader lets go
+    let pinned = reader.read_retro().unwrap();
+    cell.write_cow(|v| *v = 50);
+    cell.write_cow(|v| *v = 60);
+    assert_eq!(archived.lock().unwrap()[2..], [(2, 20)]);
+    drop(pinned);
+    cell.write_cow(|v| *v = 70);
+    assert_eq!(archived.lock().unwrap()[2..], [(2, 20), (3, 30), (4, 40)]);
+}
+
+#[test]
+fn test_write_cow_recycled() {
+    static CLONES: AtomicUsize = AtomicUsize::new(0);
+
+    #[derive(Debug, PartialEq)]
+    struct Buffer(Vec<u32>);
+    impl Clone for Buffer {
+        fn clone(&self) -> Self {
+            CLONES.fetch_add(1, Ordering::Relaxed);
+            Buffer(self.0.clone())
+        }
+    }
+    impl retro_cell::Recycle for Buffer {
+        fn recycle(&mut self, src: &Self) {
+            self.0.clone_from(&src.0);
+        }
+    }
+
+    let (mut cell, reader) = RetroCell::new(Buffer(vec![0]));
+    for i in 1..=10 {
+        cell.write_cow_recycled(|buf| buf.0.push(i));
+    }
+    assert_eq!(reader.read().0, (0..=10).collect::<Vec<_>>());
+    assert_eq!(reader.read_retro().unwrap().0, (0..10).collect::<Vec<_>>());
+    // Only the writes before the pool filled up had to clone
+    assert!(CLONES.load(Ordering::Relaxed) <= 3);
+    cell.debug_assert_quiescent();
+}
+
+#[test]
+fn test_dyn_reader() {
+    use retro_cell::{AtomicCell, DynReader, ReadAccess};
+
+    fn total(sources: &[DynReader<i32>]) -> i32 {
+        sources.iter().map(|source| *source.read()).sum()
+    }
+
+    let (mut cell, reader) = RetroCell::new(1);
+    let atomic = AtomicCell::new(10);
+    let sources = vec![DynReader::from(reader), DynReader::new(atomic)];
+    assert_eq!(total(&sources), 11);
+
+    cell.write_cow(|v| *v = 2);
+    assert_eq!(total(&sources), 12);
+    assert_eq!(*sources[0].clone().read_retro().unwrap(), 1);
+    assert!(sources[1].read_retro().is_none());
+
+    // Boxed trait objects work too
+    let boxed: Box<dyn ReadAccess<i32>> = Box::new(sources[0].clone());
+    assert_eq!(*boxed.read(), 2);
+}
+
+#[test]
+fn test_read_access_guards() {
+    use retro_cell::{DynReader, ReadAccess, ReadGuard};
+    use std::sync::Mutex;
+
+    // A source outside the crate, handing out its own guards
+    struct Locked(Mutex<String>);
+
+    impl ReadAccess<str> for Locked {
+        fn read(&self) -> ReadGuard<'_, str> {
+            struct Guard<'a>(std::sync::MutexGuard<'a, String>);
+            impl std::ops::Deref for Guard<'_> {
+                type Target = str;
+                fn deref(&self) -> &str {
+                    &self.0
+                }
+            }
+            ReadGuard::new(Guard(self.0.lock().unwrap()))
+        }
+
+        fn read_retro(&self) -> Option<ReadGuard<'_, str>> {
+            None
+        }
+    }
+
+    let (mut cell, reader) = RetroCell::new((String::from("a"), 0));
+    let names = [
+        DynReader::new(reader.map(|(name, _)| name.as_str())),
+        DynReader::new(Locked(Mutex::new(String::from("b")))),
+    ];
+    cell.write_cow(|(name, _)| *name = String::from("c"));
+
+    let read: Vec<_> = names.iter().map(|name| name.read().to_owned()).collect();
+    assert_eq!(read, ["c", "b"]);
+    assert_eq!(&*names[0].read_retro().unwrap(), "a");
+    assert!(names[1].read_retro().is_none());
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_write_cow_serde() {
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    // Not `Clone`: rebuilt from its id on deserialize
+    #[derive(Debug, PartialEq)]
+    struct Handle {
+        id: u32,
+    }
+    impl Serialize for Handle {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            serializer.serialize_u32(self.id)
+        }
+    }
+    impl<'de> Deserialize<'de> for Handle {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            u32::deserialize(deserializer).map(|id| Handle { id })
+        }
+    }
+
+    let (mut cell, reader) = RetroCell::new(Handle { id: 1 });
+    let guard = reader.read();
+    let old = cell
+        .write_cow_serde(|handle| std::mem::replace(&mut handle.id, 2))
+        .unwrap();
+    assert_eq!(old, 1);
+
+    // COW semantics: the held guard still sees the old value
+    assert_eq!(*guard, Handle { id: 1 });
+    drop(guard);
+    assert_eq!(*reader.read(), Handle { id: 2 });
+    assert_eq!(*reader.read_retro().unwrap(), Handle { id: 1 });
+}
+
+#[test]
+fn test_select() {
+    use retro_cell::{AtomicCell, Waitable, select};
+
+    let (mut a, reader_a) = RetroCell::new(0);
+    let (mut b, reader_b) = RetroCell::new(0);
+    let c = AtomicCell::new(0);
+
+    let t = thread::spawn(move || {
+        thread::sleep(Duration::from_millis(50));
+        b.store(1);
+        b
+    });
+    assert_eq!(select(&[&reader_a, &reader_b, &c]), 1);
+    let _b = t.join().unwrap();
+
+    // Already-changed cells are only detected relative to the start of the call
+    a.store(1);
+    let t = thread::spawn(move || {
+        thread::sleep(Duration::from_millis(50));
+        a.store(2);
+    });
+    assert_eq!(select(&[&reader_b, &reader_a]), 1);
+    t.join().unwrap();
+    assert_eq!(reader_a.published().get(), 2);
+}
+
+#[test]
+fn test_select_async() {
+    use std::future::Future;
+    use std::pin::pin;
+    use std::task::{Context, Poll, Waker};
+
+    let (_a, reader_a) = RetroCell::new(0);
+    let (mut b, reader_b) = RetroCell::new(0);
+    let mut cx = Context::from_waker(Waker::noop());
+
+    let cells: [&dyn retro_cell::Waitable; 2] = [&reader_a, &reader_b];
+    let mut select = pin!(retro_cell::select_async(&cells));
+    assert_eq!(select.as_mut().poll(&mut cx), Poll::Pending);
+    b.store(1);
+    assert_eq!(select.as_mut().poll(&mut cx), Poll::Ready(1));
+}
+
+#[test]
+fn test_select_async_drop_deregisters() {
+    use std::future::Future;
+    use std::pin::pin;
+    use std::task::{Context, Poll, Wake, Waker};
+
+    struct Count(AtomicUsize);
+
+    impl Wake for Count {
+        fn wake(self: Arc<Self>) {
+            self.0.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    let (mut cell, reader) = RetroCell::new(0);
+    let count = Arc::new(Count(AtomicUsize::new(0)));
+    let waker = Waker::from(count.clone());
+    let cells: [&dyn retro_cell::Waitable; 1] = [&reader];
+    {
+        let mut select = pin!(retro_cell::select_async(&cells));
+        assert_eq!(select.as_mut().poll(&mut Context::from_waker(&waker)), Poll::Pending);
+    }
+    // The dropped future's waker is not woken (nor kept alive)
+    cell.store(1);
+    assert_eq!(count.0.load(Ordering::SeqCst), 0);
+    assert_eq!(Arc::strong_count(&count), 2);
+}
+
+#[test]
+fn test_freeze() {
+    let (mut cell, reader) = RetroCell::new(String::from("draft"));
+    cell.write_cow(|s| *s = String::from("final"));
+    assert!(!reader.is_frozen());
+
+    let frozen = cell.freeze();
+    assert!(reader.is_frozen());
+    // Freezing is not closing
+    assert!(!reader.is_closed());
+    assert_eq!(*reader.read_or_closed().unwrap(), "final");
+    assert_eq!(frozen.get(), "final");
+    assert_eq!(frozen.version().get(), 1);
+
+    let handles: Vec<_> = (0..4)
+        .map(|_| {
+            let frozen = frozen.clone();
+            thread::spawn(move || frozen.len())
+        })
+        .collect();
+    for handle in handles {
+        assert_eq!(handle.join().unwrap(), 5);
+    }
+
+    // Regular readers keep working and outlive the frozen handle
+    drop(frozen);
+    assert_eq!(*reader.read(), "final");
+    assert_eq!(*reader.read_retro().unwrap(), "draft");
+}
+
+#[test]
+fn test_reader_closed() {
+    let (mut cell, reader) = RetroCell::new(0);
+    cell.write_cow(|v| *v = 1);
+    assert!(!reader.is_closed());
+    assert_eq!(*reader.read_or_closed().unwrap(), 1);
+
+    drop(cell);
+    assert!(reader.is_closed() && reader.is_frozen());
+    assert_eq!(reader.read_or_closed().unwrap_err(), retro_cell::Error::Closed);
+    assert_eq!(*reader.read(), 1);
+}
+
+#[cfg(not(feature = "loom"))]
+#[test]
+fn test_weak_reader() {
+    let (mut cell, reader) = RetroCell::new(vec![0u8; 1024]);
+    let weak = reader.downgrade();
+    cell.write_cow(|v| v[0] = 1);
+    assert_eq!(weak.upgrade().unwrap().read()[0], 1);
+
+    // The writer alone keeps the state alive
+    drop(reader);
+    assert_eq!(weak.upgrade().unwrap().read()[0], 1);
+
+    drop(cell);
+    assert!(weak.upgrade().is_none());
+    assert!(weak.clone().upgrade().is_none());
+}
+
+#[test]
+fn test_ref_map() {
+    use retro_cell::{MappedRef, Ref};
+
+    #[derive(Clone)]
+    struct Config {
+        name: String,
+        limits: Vec<u32>,
+    }
+
+    fn name(reader: &retro_cell::Reader<Config>) -> MappedRef<'_, str> {
+        Ref::map(reader.read(), |config| config.name.as_str())
+    }
+
+    let (mut cell, reader) = RetroCell::new(Config {
+        name: String::from("primary"),
+        limits: vec![1, 2, 3],
+    });
+
+    let mapped = name(&reader);
+    let limit = MappedRef::map(Ref::map(reader.read(), |c| &c.limits), |l| &l[1]);
+    assert_eq!(&*mapped, "primary");
+    assert_eq!(*limit, 2);
+    assert_eq!(MappedRef::version(&limit).get(), 0);
+
+    // Mapped guards keep the version retained, forcing COW
+    match cell.try_write() {
+        WriteOutcome::Congested(writer) => writer.perform_cow(|c| c.name = String::from("backup")),
+        WriteOutcome::InPlace(_) => panic!("mapped guards must keep the version retained"),
+    }
+    assert_eq!(&*mapped, "primary");
+    drop((mapped, limit));
+    assert_eq!(&*name(&reader), "backup");
+    cell.debug_assert_quiescent();
+}
+
+#[test]
+fn test_iter_updates() {
+    let (mut cell, reader) = RetroCell::new(0);
+    cell.write_cow(|v| *v = 1);
+
+    // Starts after version 1, whenever the consumer gets to run
+    let updates = reader.iter_updates();
+    let changes = thread::scope(|s| {
+        let consumer = s.spawn(move || {
+            updates
+                .map(|change| (*change.value, change.skipped))
+                .collect::<Vec<_>>()
+        });
+        for i in 2..=5 {
+            cell.write_cow(|v| *v = i);
+        }
+        drop(cell);
+        consumer.join().unwrap()
+    });
+
+    let (first, skipped) = changes[0];
+    assert_eq!(skipped, first - 2);
+    assert_eq!(changes.last().unwrap().0, 5);
+    // Every later version is either yielded or counted as skipped
+    let total: u64 = changes.iter().map(|&(_, skipped)| skipped + 1).sum();
+    assert_eq!(total, 4);
+}
+
+#[test]
+fn test_updates_fork() {
+    let (mut cell, reader) = RetroCell::new(0);
+    let mut updates = reader.iter_updates();
+    cell.write_cow(|v| *v = 1);
+    assert_eq!(*updates.next().unwrap().value, 1);
+
+    // The fork starts where the original is, and each advances on its own
+    let mut fork = updates.fork();
+    cell.write_cow(|v| *v = 2);
+    assert_eq!(*updates.next().unwrap().value, 2);
+    cell.write_cow(|v| *v = 3);
+    assert_eq!(*updates.next().unwrap().value, 3);
+
+    let change = fork.next().unwrap();
+    assert_eq!((*change.value, change.skipped), (3, 1));
+    drop(change);
+    drop(cell);
+    assert!(fork.next().is_none());
+    assert!(updates.next().is_none());
+}
+
+#[test]
+fn test_guards_are_send_and_sync() {
+    use retro_cell::{BlockedReader, InPlaceGuard, MappedRef, Ref};
+    use std::future::Future;
+    use std::pin::pin;
+    use std::task::{Context, Poll, Waker};
+
+    fn assert_send<T: Send>() {}
+    fn assert_sync<T: Sync>() {}
+
+    assert_send::<BlockedReader<'static, Vec<u8>>>();
+    assert_sync::<BlockedReader<'static, Vec<u8>>>();
+    assert_send::<InPlaceGuard<'static, Vec<u8>>>();
+    assert_sync::<InPlaceGuard<'static, Vec<u8>>>();
+    assert_send::<Ref<'static, Vec<u8>>>();
+    assert_sync::<Ref<'static, Vec<u8>>>();
+    assert_send::<MappedRef<'static, [u8]>>();
+    assert_sync::<MappedRef<'static, [u8]>>();
+
+    // A guard can be held across an await point of a `Send` future
+    let (_cell, reader) = RetroCell::new(vec![1u8]);
+    let future = async {
+        let guard = reader.read();
+        std::future::ready(()).await;
+        guard[0]
+    };
+    let value = thread::scope(|s| {
+        s.spawn(|| {
+            let mut cx = Context::from_waker(Waker::noop());
+            pin!(future).poll(&mut cx)
+        })
+        .join()
+        .unwrap()
+    });
+    assert_eq!(value, Poll::Ready(1));
+}
+
+#[test]
+fn test_reader_map() {
+    use retro_cell::MappedReader;
+
+    #[derive(Clone)]
+    struct Config {
+        tls: Tls,
+        name: String,
+    }
+    #[derive(Clone)]
+    struct Tls {
+        cert: String,
+    }
+
+    let (mut cell, reader) = RetroCell::new(Config {
+        tls: Tls { cert: String::from("a.pem") },
+        name: String::from("primary"),
+    });
+    let tls: MappedReader<Config, Tls> = reader.clone().map(|c| &c.tls);
+    let cert = tls.clone().map(|t| t.cert.as_str());
+    assert_eq!(&*cert.read(), "a.pem");
+    assert!(cert.read_retro().is_none());
+
+    cell.write_cow(|c| c.tls.cert = String::from("b.pem"));
+    assert_eq!(tls.read().cert, "b.pem");
+    assert_eq!(&*cert.read(), "b.pem");
+    assert_eq!(&*cert.read_retro().unwrap(), "a.pem");
+    assert_eq!(tls.reader().read().name, "primary");
+}
+
+#[test]
+fn test_wait_for_change() {
+    use retro_cell::Ref;
+
+    let (mut cell, reader) = RetroCell::new(0);
+    let seen = Ref::version(&reader.read());
+
+    let waiter = {
+        let reader = reader.clone();
+        thread::spawn(move || {
+            let mut seen = seen;
+            let mut values = Vec::new();
+            while let Some(guard) = reader.wait_for_change(seen) {
+                values.push(*guard);
+                seen = Ref::version(&guard);
+            }
+            values
+        })
+    };
+
+    thread::sleep(Duration::from_millis(20));
+    cell.write_cow(|v| *v = 1);
+    thread::sleep(Duration::from_millis(20));
+    *cell.write_in_place() = 2;
+    thread::sleep(Duration::from_millis(20));
+    drop(cell);
+
+    let values = waiter.join().unwrap();
+    assert_eq!(values.last(), Some(&2));
+    assert!(values.windows(2).all(|pair| pair[0] < pair[1]));
+    assert!(reader.is_frozen());
+    assert!(reader.wait_for_change(seen).is_some());
+}
@@ -254,3 +254,46 @@ fn test_try_read_blocked() {
         t1.join().unwrap();
     });
 }
+
+#[test]
+fn test_read_pair_consistent() {
+    let mut builder = Builder::new();
+    builder.preemption_bound = Some(3);
+    builder.check(|| {
+        let (mut cell, reader) = RetroCell::new(0usize);
+        cell.write_cow(|val| *val = 1);
+
+        let t1 = thread::spawn({
+            let reader = reader.clone();
+            move || {
+                let (current, retro) = reader.read_pair();
+                assert_eq!(*current, *retro.unwrap() + 1);
+            }
+        });
+
+        cell.write_cow(|val| *val = 2);
+
+        t1.join().unwrap();
+    });
+}
+
+#[test]
+fn test_local_reader_vs_in_place() {
+    let mut builder = Builder::new();
+    builder.preemption_bound = Some(3);
+    builder.check(|| {
+        let (mut cell, reader) = RetroCell::new(0usize);
+        let factory = Arc::new(retro_cell::ReaderFactory::from(reader));
+
+        let f = factory.clone();
+        let t = thread::spawn(move || {
+            let mut local = f.register();
+            let v = *local.read();
+            assert!(v == 0 || v == 1);
+        });
+
+        *cell.write_in_place() = 1;
+        t.join().unwrap();
+        assert_eq!(*factory.register().read(), 1);
+    });
+}